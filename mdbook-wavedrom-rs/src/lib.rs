@@ -3,12 +3,14 @@ use std::fmt::Display;
 use pulldown_cmark::{CodeBlockKind, Event, Options, Parser, Tag};
 use wavedrom::json5::Error as JsonError;
 use wavedrom::signal::options::{PathAssembleOptions, RenderOptions};
+use wavedrom::wavejson::diagnostics::{Diagnostic, StrictError};
 use wavedrom::Figure;
 
 #[derive(Debug)]
 pub enum InsertionError {
     Json(JsonError),
     InvalidFigure,
+    Strict(Vec<Diagnostic>),
     WriteSvg,
     InvalidUtf8,
 }
@@ -25,6 +27,7 @@ impl Display for InsertionError {
         match self {
             Self::Json(err) => write!(f, "{err}"),
             Self::InvalidFigure => write!(f, "Failed to form a figure from the given WaveJson"),
+            Self::Strict(_) => write!(f, "The WaveJson contains errors in strict mode"),
             Self::WriteSvg => write!(f, "Failed to write svg of WaveDrom figure"),
             Self::InvalidUtf8 => write!(f, "Wavedrom returned invalid UTF-8"),
         }
@@ -38,6 +41,21 @@ pub fn insert_wavedrom(
     assemble_options: PathAssembleOptions,
     render_options: &RenderOptions,
 ) -> Result<String, InsertionError> {
+    insert_wavedrom_with_diagnostics(content, assemble_options, render_options, false)
+        .map(|(out, _)| out)
+}
+
+/// Same as [`insert_wavedrom`], but also returns the diagnostics of all the diagrams. The
+/// locations of the diagnostics are relative to `content`.
+///
+/// When `strict` is set, the diagrams are loaded in strict mode and the diagnostics of the first
+/// diagram with an error are returned in [`InsertionError::Strict`].
+pub fn insert_wavedrom_with_diagnostics(
+    content: &str,
+    assemble_options: PathAssembleOptions,
+    render_options: &RenderOptions,
+    strict: bool,
+) -> Result<(String, Vec<Diagnostic>), InsertionError> {
     let mut opts = Options::empty();
     opts.insert(Options::ENABLE_TABLES);
     opts.insert(Options::ENABLE_FOOTNOTES);
@@ -45,6 +63,7 @@ pub fn insert_wavedrom(
     opts.insert(Options::ENABLE_TASKLISTS);
 
     let mut diagrams = Vec::new();
+    let mut diagnostics = Vec::new();
     let mut current_spans = Vec::new();
     let mut wavedrom_block_start = None;
     let mut keep_source_code_tag = None;
@@ -99,6 +118,10 @@ pub fn insert_wavedrom(
 
             let block_end = span.end;
 
+            let line_offset = current_spans
+                .first()
+                .map_or(0, |span| content[..span.start].matches('\n').count());
+
             let mut diagram_content = String::with_capacity(block_end - block_start);
             for span in std::mem::take(&mut current_spans).into_iter() {
                 diagram_content.push_str(&content[span]);
@@ -106,13 +129,32 @@ pub fn insert_wavedrom(
 
             let mut wavedrom_code = Vec::new();
 
-            let wavedrom_figure =
-                Figure::from_json5(&diagram_content).map_err(|_| InsertionError::InvalidFigure)?;
+            let offset_lines = |mut diagnostic: Diagnostic| {
+                diagnostic.span.start.line += line_offset;
+                diagnostic.span.end.line += line_offset;
+                diagnostic
+            };
+
+            let result = if strict {
+                Figure::from_json5_strict(&diagram_content)
+            } else {
+                Figure::from_json5_with_diagnostics(&diagram_content).map_err(StrictError::Parse)
+            };
+
+            let (wavedrom_figure, figure_diagnostics) = match result {
+                Ok(result) => result,
+                Err(StrictError::Invalid(figure_diagnostics)) => {
+                    return Err(InsertionError::Strict(
+                        figure_diagnostics.into_iter().map(offset_lines).collect(),
+                    ));
+                }
+                Err(StrictError::Parse(_)) => return Err(InsertionError::InvalidFigure),
+            };
+
+            diagnostics.extend(figure_diagnostics.into_iter().map(offset_lines));
 
-            let Figure::Signal(wavedrom_figure) = wavedrom_figure;
             wavedrom_figure
-                .assemble_with_options(assemble_options)
-                .write_svg_with_options(&mut wavedrom_code, render_options)
+                .write_svg_with_options(&mut wavedrom_code, assemble_options, render_options)
                 .map_err(|_| InsertionError::WriteSvg)?;
 
             let wavedrom_code =
@@ -146,7 +188,7 @@ pub fn insert_wavedrom(
 
     out.push_str(&content[end_prev..]);
 
-    Ok(out)
+    Ok((out, diagnostics))
 }
 
 #[cfg(test)]
@@ -242,4 +284,41 @@ ABC:
 <pre class="wavedrom"><svg/></pre>
         "#.trim())
     }
+
+    #[test]
+    fn diagnostics_locations() {
+        let content = r#"
+# Header
+
+```wavedrom
+{
+    signal: [{ wave: "01?" }]
+}
+```
+        "#;
+
+        let (_, diagnostics) = insert_wavedrom_with_diagnostics(
+            content,
+            PathAssembleOptions::default(),
+            &RenderOptions::default(),
+            false,
+        )
+        .unwrap();
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].span.start.line, 6);
+        assert_eq!(diagnostics[0].span.start.column, 25);
+
+        let result = insert_wavedrom_with_diagnostics(
+            content,
+            PathAssembleOptions::default(),
+            &RenderOptions::default(),
+            true,
+        );
+
+        let Err(InsertionError::Strict(diagnostics)) = result else {
+            panic!("Expected a strict mode error");
+        };
+        assert_eq!(diagnostics[0].span.start.line, 6);
+    }
 }
@@ -69,9 +69,10 @@ fn handle_supports(pre: &dyn Preprocessor, sub_args: &ArgMatches) -> ! {
 
 mod nop_lib {
     use mdbook::BookItem;
-    use mdbook_wavedrom_rs::insert_wavedrom;
+    use mdbook_wavedrom_rs::{insert_wavedrom_with_diagnostics, InsertionError};
     use wavedrom::signal::options::{PathAssembleOptions, RenderOptions};
     use wavedrom::skin::Skin;
+    use wavedrom::wavejson::diagnostics::Diagnostic;
 
     use super::*;
 
@@ -99,39 +100,56 @@ mod nop_lib {
                         std::process::exit(1);
                     };
 
-                    let skin = match std::fs::read_to_string(skin_path) {
-                        Ok(content) => content,
-                        Err(err) => {
-                            eprintln!("[ERROR]: Failed to read content from WaveDrom skin file. Reason: {err}");
-                            std::process::exit(1);
-                        }
+                    let skin = match Skin::named(skin_path) {
+                        // A skin that is not an existing file may be the name of a built-in skin.
+                        Some(skin) if !std::path::Path::new(skin_path).exists() => skin,
+                        _ => read_skin_file(skin_path),
                     };
 
-                    match Skin::from_json5(&skin) {
-                        Ok(skin) => {
-                            if let Some(assemble) = skin.assemble {
-                                assemble_options.merge_in(assemble);
-                            }
-
-                            if let Some(render) = skin.render {
-                                render_options.merge_in(render);
-                            }
-                        }
-                        Err(err) => {
-                            eprintln!(
-                                "[ERROR]: Failed to parse WaveDrom skin content. Reason: {err}"
-                            );
-                            std::process::exit(1);
-                        }
-                    }
+                    skin.merge_into(&mut assemble_options, &mut render_options);
                 }
             }
 
+            let strict = match ctx
+                .config
+                .get_preprocessor(self.name())
+                .and_then(|config| config.get("strict"))
+            {
+                None => false,
+                Some(strict) => {
+                    let Some(strict) = strict.as_bool() else {
+                        eprintln!("[ERROR]: WaveDrom strict has invalid value type");
+                        std::process::exit(1);
+                    };
+
+                    strict
+                }
+            };
+
+            let mut has_strict_errors = false;
+
             book.for_each_mut(|item| match item {
                 BookItem::Separator | BookItem::PartTitle(_) => {}
                 BookItem::Chapter(chapter) => {
-                    match insert_wavedrom(&chapter.content, assemble_options, &render_options) {
-                        Ok(new_content) => chapter.content = new_content,
+                    let chapter_path = chapter
+                        .source_path
+                        .as_ref()
+                        .map_or_else(|| chapter.name.clone(), |path| path.display().to_string());
+
+                    match insert_wavedrom_with_diagnostics(
+                        &chapter.content,
+                        assemble_options,
+                        &render_options,
+                        strict,
+                    ) {
+                        Ok((new_content, diagnostics)) => {
+                            print_diagnostics(&chapter_path, &diagnostics);
+                            chapter.content = new_content;
+                        }
+                        Err(InsertionError::Strict(diagnostics)) => {
+                            print_diagnostics(&chapter_path, &diagnostics);
+                            has_strict_errors = true;
+                        }
                         Err(err) => {
                             eprintln!("Failed to render wavedrom. Reason: {err}");
                         }
@@ -139,6 +157,12 @@ mod nop_lib {
                 }
             });
 
+            if has_strict_errors {
+                return Err(Error::msg(
+                    "WaveDrom diagrams contain errors in strict mode",
+                ));
+            }
+
             Ok(book)
         }
 
@@ -146,4 +170,28 @@ mod nop_lib {
             renderer != "not-supported"
         }
     }
+
+    fn print_diagnostics(chapter_path: &str, diagnostics: &[Diagnostic]) {
+        for diagnostic in diagnostics {
+            eprintln!("{chapter_path}:{diagnostic}");
+        }
+    }
+
+    fn read_skin_file(skin_path: &str) -> Skin {
+        let skin = match std::fs::read_to_string(skin_path) {
+            Ok(content) => content,
+            Err(err) => {
+                eprintln!("[ERROR]: Failed to read content from WaveDrom skin file. Reason: {err}");
+                std::process::exit(1);
+            }
+        };
+
+        match Skin::from_json5(&skin) {
+            Ok(skin) => skin,
+            Err(err) => {
+                eprintln!("[ERROR]: Failed to parse WaveDrom skin content. Reason: {err}");
+                std::process::exit(1);
+            }
+        }
+    }
 }
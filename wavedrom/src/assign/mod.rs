@@ -0,0 +1,403 @@
+//! The logic to render assign / logic circuit diagrams
+//!
+//! An assign diagram shows how a set of outputs is formed from a set of named inputs with a tree
+//! of logic gates. The inputs are placed on the left, the outputs on the right and the gates are
+//! placed in columns in between.
+
+mod render;
+
+/// A gate in an [`AssignNode`] tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AssignGate {
+    /// A buffer. The operator for this is `=`.
+    Buf,
+    /// An inverter. The operator for this is `~`.
+    Not,
+    /// An AND gate. The operator for this is `&`.
+    And,
+    /// A NAND gate. The operator for this is `~&`.
+    Nand,
+    /// An OR gate. The operator for this is `|`.
+    Or,
+    /// A NOR gate. The operator for this is `~|`.
+    Nor,
+    /// A XOR gate. The operator for this is `^`.
+    Xor,
+    /// A XNOR gate. The operator for this is `~^`.
+    Xnor,
+    /// Any other operator. This is drawn as a box containing the operator.
+    Box(String),
+}
+
+/// A node in the tree that forms an output of an [`AssignFigure`].
+#[derive(Debug, Clone)]
+pub enum AssignNode {
+    /// A named input
+    Input(String),
+    /// A gate with a set of inputs
+    Gate(AssignGate, Vec<AssignNode>),
+}
+
+/// An encapsulation of everything to form an assign / logic circuit diagram.
+///
+/// # Examples
+///
+/// ```
+/// use wavedrom::assign::{AssignFigure, AssignGate, AssignNode};
+///
+/// let figure = AssignFigure::new()
+///                  .add_output(
+///                      "out",
+///                      AssignNode::gate(AssignGate::Or, [
+///                          AssignNode::gate(AssignGate::And, [
+///                              AssignNode::input("a"),
+///                              AssignNode::input("b"),
+///                          ]),
+///                          AssignNode::gate(AssignGate::Not, [AssignNode::input("c")]),
+///                      ]),
+///                  );
+///
+/// let mut svg = Vec::new();
+/// figure.assemble().write_svg(&mut svg)?;
+/// # <Result<(), std::io::Error>>::Ok(())
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct AssignFigure {
+    outputs: Vec<(String, AssignNode)>,
+}
+
+/// An [`AssignFigure`] that has been assembled with the [`AssignFigure::assemble`] method.
+///
+/// An assembled figure contains the placement of all inputs, gates and outputs. Rows are counted
+/// from the top and columns are counted from the left.
+#[derive(Debug)]
+pub struct AssembledAssignFigure<'a> {
+    num_rows: u32,
+    num_columns: u32,
+
+    inputs: Vec<AssembledAssignInput<'a>>,
+    gates: Vec<AssembledAssignGate<'a>>,
+    outputs: Vec<AssembledAssignOutput<'a>>,
+}
+
+/// The source of a wire within an [`AssembledAssignFigure`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AssignSource {
+    /// The input with a specific index
+    Input(usize),
+    /// The output of the gate with a specific index
+    Gate(usize),
+}
+
+/// A placed input of an [`AssembledAssignFigure`].
+#[derive(Debug, Clone)]
+pub struct AssembledAssignInput<'a> {
+    name: &'a str,
+    row: u32,
+}
+
+/// A placed gate of an [`AssembledAssignFigure`].
+#[derive(Debug, Clone)]
+pub struct AssembledAssignGate<'a> {
+    gate: &'a AssignGate,
+    column: u32,
+    row: f64,
+    inputs: Vec<AssignSource>,
+}
+
+/// A placed output of an [`AssembledAssignFigure`].
+#[derive(Debug, Clone)]
+pub struct AssembledAssignOutput<'a> {
+    name: &'a str,
+    row: f64,
+    source: AssignSource,
+}
+
+impl AssignGate {
+    /// Get the [`AssignGate`] that belongs to a WaveDrom operator.
+    ///
+    /// Operators that are not a logic gate result in a [`AssignGate::Box`].
+    pub fn from_operator(operator: &str) -> Self {
+        match operator {
+            "=" | "buf" | "BUF" => Self::Buf,
+            "~" | "!" | "not" | "NOT" => Self::Not,
+            "&" | "and" | "AND" => Self::And,
+            "~&" | "nand" | "NAND" => Self::Nand,
+            "|" | "or" | "OR" => Self::Or,
+            "~|" | "nor" | "NOR" => Self::Nor,
+            "^" | "xor" | "XOR" => Self::Xor,
+            "~^" | "xnor" | "XNOR" => Self::Xnor,
+            _ => Self::Box(operator.to_string()),
+        }
+    }
+
+    /// Returns whether the output of the [`AssignGate`] is inverted.
+    #[inline]
+    pub fn is_inverted(&self) -> bool {
+        matches!(self, Self::Not | Self::Nand | Self::Nor | Self::Xnor)
+    }
+}
+
+impl AssignNode {
+    /// Create a new [`AssignNode`] that refers to a named input.
+    #[inline]
+    pub fn input(name: impl Into<String>) -> Self {
+        Self::Input(name.into())
+    }
+
+    /// Create a new [`AssignNode`] with a [`AssignGate`] and a set of inputs.
+    #[inline]
+    pub fn gate(gate: AssignGate, inputs: impl IntoIterator<Item = AssignNode>) -> Self {
+        Self::Gate(gate, inputs.into_iter().collect())
+    }
+
+    /// The number of gates on the longest path from this [`AssignNode`] to an input.
+    pub fn depth(&self) -> u32 {
+        match self {
+            Self::Input(_) => 0,
+            Self::Gate(_, inputs) => 1 + inputs.iter().map(Self::depth).max().unwrap_or_default(),
+        }
+    }
+}
+
+impl AssignFigure {
+    /// Create a new empty [`AssignFigure`].
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a named output to the [`AssignFigure`] that is formed by `node`.
+    #[inline]
+    pub fn add_output(mut self, name: impl Into<String>, node: AssignNode) -> Self {
+        self.outputs.push((name.into(), node));
+        self
+    }
+
+    /// Get the outputs of the [`AssignFigure`].
+    #[inline]
+    pub fn outputs(&self) -> &[(String, AssignNode)] {
+        &self.outputs
+    }
+
+    /// Place all the inputs, gates and outputs.
+    ///
+    /// Every input occupies its own row and the outputs are separated by an empty row. Gates are
+    /// placed in the vertical middle of their first and last input and in a column according to
+    /// their distance to the output.
+    pub fn assemble(&self) -> AssembledAssignFigure<'_> {
+        let num_columns = self
+            .outputs
+            .iter()
+            .map(|(_, node)| node.depth())
+            .max()
+            .unwrap_or_default();
+
+        let mut assembled = AssembledAssignFigure {
+            num_rows: 0,
+            num_columns,
+
+            inputs: Vec::new(),
+            gates: Vec::new(),
+            outputs: Vec::with_capacity(self.outputs.len()),
+        };
+
+        for (i, (name, node)) in self.outputs.iter().enumerate() {
+            if i != 0 {
+                assembled.num_rows += 1;
+            }
+
+            let (source, row) = assembled.place(node, 0);
+            assembled
+                .outputs
+                .push(AssembledAssignOutput { name, row, source });
+        }
+
+        assembled
+    }
+}
+
+impl<'a> AssembledAssignFigure<'a> {
+    fn place(&mut self, node: &'a AssignNode, depth: u32) -> (AssignSource, f64) {
+        match node {
+            AssignNode::Input(name) => {
+                let row = self.num_rows;
+                self.num_rows += 1;
+
+                self.inputs.push(AssembledAssignInput { name, row });
+                (AssignSource::Input(self.inputs.len() - 1), f64::from(row))
+            }
+            AssignNode::Gate(gate, inputs) => {
+                let mut sources = Vec::with_capacity(inputs.len());
+                let mut first_row = None;
+                let mut last_row = 0.0;
+
+                for input in inputs {
+                    let (source, row) = self.place(input, depth + 1);
+
+                    sources.push(source);
+                    first_row.get_or_insert(row);
+                    last_row = row;
+                }
+
+                let row = match first_row {
+                    Some(first_row) => (first_row + last_row) / 2.0,
+                    None => {
+                        // A gate without inputs still occupies a row.
+                        let row = self.num_rows;
+                        self.num_rows += 1;
+                        f64::from(row)
+                    }
+                };
+
+                self.gates.push(AssembledAssignGate {
+                    gate,
+                    column: self.num_columns - 1 - depth,
+                    row,
+                    inputs: sources,
+                });
+                (AssignSource::Gate(self.gates.len() - 1), row)
+            }
+        }
+    }
+
+    /// Get the number of rows in the [`AssembledAssignFigure`].
+    #[inline]
+    pub fn num_rows(&self) -> u32 {
+        self.num_rows
+    }
+
+    /// Get the number of gate columns in the [`AssembledAssignFigure`].
+    #[inline]
+    pub fn num_columns(&self) -> u32 {
+        self.num_columns
+    }
+
+    /// Get the placed inputs of the [`AssembledAssignFigure`].
+    #[inline]
+    pub fn inputs(&self) -> &[AssembledAssignInput<'a>] {
+        &self.inputs
+    }
+
+    /// Get the placed gates of the [`AssembledAssignFigure`].
+    #[inline]
+    pub fn gates(&self) -> &[AssembledAssignGate<'a>] {
+        &self.gates
+    }
+
+    /// Get the placed outputs of the [`AssembledAssignFigure`].
+    #[inline]
+    pub fn outputs(&self) -> &[AssembledAssignOutput<'a>] {
+        &self.outputs
+    }
+}
+
+impl<'a> AssembledAssignInput<'a> {
+    /// Get the name of the input.
+    #[inline]
+    pub fn name(&self) -> &'a str {
+        self.name
+    }
+
+    /// Get the row of the input.
+    #[inline]
+    pub fn row(&self) -> u32 {
+        self.row
+    }
+}
+
+impl<'a> AssembledAssignGate<'a> {
+    /// Get the [`AssignGate`] that is placed.
+    #[inline]
+    pub fn gate(&self) -> &'a AssignGate {
+        self.gate
+    }
+
+    /// Get the column of the gate.
+    #[inline]
+    pub fn column(&self) -> u32 {
+        self.column
+    }
+
+    /// Get the row of the gate. This is in the middle of two rows for gates with an even number
+    /// of inputs.
+    #[inline]
+    pub fn row(&self) -> f64 {
+        self.row
+    }
+
+    /// Get the sources of the inputs of the gate from top to bottom.
+    #[inline]
+    pub fn inputs(&self) -> &[AssignSource] {
+        &self.inputs
+    }
+}
+
+impl<'a> AssembledAssignOutput<'a> {
+    /// Get the name of the output.
+    #[inline]
+    pub fn name(&self) -> &'a str {
+        self.name
+    }
+
+    /// Get the row of the output.
+    #[inline]
+    pub fn row(&self) -> f64 {
+        self.row
+    }
+
+    /// Get the source that drives the output.
+    #[inline]
+    pub fn source(&self) -> AssignSource {
+        self.source
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn assemble_placement() {
+        let figure = AssignFigure::new()
+            .add_output(
+                "x",
+                AssignNode::gate(
+                    AssignGate::Or,
+                    [
+                        AssignNode::gate(
+                            AssignGate::And,
+                            [AssignNode::input("a"), AssignNode::input("b")],
+                        ),
+                        AssignNode::input("c"),
+                    ],
+                ),
+            )
+            .add_output("y", AssignNode::input("d"));
+
+        let assembled = figure.assemble();
+
+        assert_eq!(assembled.num_columns(), 2);
+        assert_eq!(assembled.num_rows(), 5);
+
+        let rows = assembled
+            .inputs()
+            .iter()
+            .map(|input| (input.name(), input.row()))
+            .collect::<Vec<_>>();
+        assert_eq!(rows, [("a", 0), ("b", 1), ("c", 2), ("d", 4)]);
+
+        let gates = assembled.gates();
+        assert_eq!(gates[0].gate(), &AssignGate::And);
+        assert_eq!((gates[0].column(), gates[0].row()), (0, 0.5));
+        assert_eq!(gates[1].gate(), &AssignGate::Or);
+        assert_eq!((gates[1].column(), gates[1].row()), (1, 1.25));
+        assert_eq!(
+            gates[1].inputs(),
+            [AssignSource::Gate(0), AssignSource::Input(2)]
+        );
+
+        assert_eq!(assembled.outputs()[0].source(), AssignSource::Gate(1));
+        assert_eq!(assembled.outputs()[1].source(), AssignSource::Input(3));
+    }
+}
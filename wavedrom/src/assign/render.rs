@@ -0,0 +1,365 @@
+use std::io;
+
+use crate::escape::escape_str;
+use crate::signal::options::{AssignOptions, RenderOptions};
+use crate::Font;
+
+use super::{AssembledAssignFigure, AssembledAssignGate, AssignGate, AssignSource};
+
+/// The radius of the bubble at the output of an inverting gate
+const BUBBLE_RADIUS: f64 = 3.0;
+
+/// The gap between the two back curves of a XOR gate
+const XOR_GAP: f64 = 5.0;
+
+/// The pixel positions of the columns and rows of a figure.
+struct Grid {
+    gates_x: f64,
+    gates_y: f64,
+    gate_width: f64,
+    row_height: f64,
+    column_spacing: f64,
+}
+
+impl Grid {
+    #[inline]
+    fn x(&self, column: u32) -> f64 {
+        self.gates_x
+            + self.column_spacing
+            + f64::from(column) * (self.gate_width + self.column_spacing)
+    }
+
+    #[inline]
+    fn y(&self, row: f64) -> f64 {
+        self.gates_y + row * self.row_height + self.row_height / 2.0
+    }
+}
+
+/// The shape of a single gate in pixels.
+struct GateShape {
+    x: f64,
+    y: f64,
+    body_width: f64,
+    height: f64,
+}
+
+impl GateShape {
+    fn new(gate: &AssembledAssignGate, grid: &Grid, options: &AssignOptions) -> Self {
+        let num_inputs = gate.inputs().len() as u32;
+        let height = u32::max(options.gate_height, num_inputs * options.gate_height / 3);
+
+        let mut body_width = f64::from(options.gate_width);
+        if gate.gate().is_inverted() {
+            body_width -= 2.0 * BUBBLE_RADIUS;
+        }
+
+        Self {
+            x: grid.x(gate.column()),
+            y: grid.y(gate.row()),
+            body_width,
+            height: f64::from(height),
+        }
+    }
+
+    #[inline]
+    fn top(&self) -> f64 {
+        self.y - self.height / 2.0
+    }
+
+    #[inline]
+    fn bottom(&self) -> f64 {
+        self.y + self.height / 2.0
+    }
+
+    /// The depth of the curved back of OR-like gates
+    #[inline]
+    fn back_depth(&self, gate: &AssignGate) -> f64 {
+        match gate {
+            AssignGate::Or | AssignGate::Nor => self.body_width / 4.0,
+            AssignGate::Xor | AssignGate::Xnor => (self.body_width - XOR_GAP) / 4.0,
+            _ => 0.0,
+        }
+    }
+
+    /// The position where the wire for input `i` out of `n` ends.
+    fn input_pin(&self, gate: &AssignGate, i: usize, n: usize) -> (f64, f64) {
+        let y = self.top() + self.height * (i + 1) as f64 / (n + 1) as f64;
+
+        // The back of OR-like gates is a quadratic bezier curve with its control point in the
+        // vertical middle of the gate.
+        let t = (self.bottom() - y) / self.height;
+        let x = self.x + 2.0 * t * (1.0 - t) * self.back_depth(gate);
+
+        (x, y)
+    }
+
+    /// The position of the output of the gate.
+    #[inline]
+    fn output_pin(&self, gate: &AssignGate) -> (f64, f64) {
+        let mut x = self.x + self.body_width;
+        if gate.is_inverted() {
+            x += 2.0 * BUBBLE_RADIUS;
+        }
+
+        (x, self.y)
+    }
+
+    fn write_svg(&self, writer: &mut impl io::Write, gate: &AssignGate) -> io::Result<()> {
+        let Self {
+            x,
+            y,
+            body_width: w,
+            height: h,
+        } = *self;
+        let top = self.top();
+        let bottom = self.bottom();
+
+        match gate {
+            AssignGate::Buf | AssignGate::Not => {
+                write!(
+                    writer,
+                    r#"<path d="M{x},{top}L{right},{y}L{x},{bottom}z"/>"#,
+                    right = x + w,
+                )?;
+            }
+            AssignGate::And | AssignGate::Nand => {
+                let rx = f64::min(w, h / 2.0);
+                write!(
+                    writer,
+                    r#"<path d="M{x},{top}H{arc}A{rx},{ry} 0 0 1 {arc},{bottom}H{x}z"/>"#,
+                    arc = x + w - rx,
+                    ry = h / 2.0,
+                )?;
+            }
+            AssignGate::Or | AssignGate::Nor | AssignGate::Xor | AssignGate::Xnor => {
+                let (x, w) = if matches!(gate, AssignGate::Xor | AssignGate::Xnor) {
+                    write!(
+                        writer,
+                        r#"<path fill="none" d="M{x},{bottom}Q{control},{y} {x},{top}"/>"#,
+                        control = x + self.back_depth(gate),
+                    )?;
+
+                    (x + XOR_GAP, w - XOR_GAP)
+                } else {
+                    (x, w)
+                };
+
+                write!(
+                    writer,
+                    r#"<path d="M{x},{top}Q{front},{top} {right},{y}Q{front},{bottom} {x},{bottom}Q{back},{y} {x},{top}z"/>"#,
+                    front = x + w * 0.6,
+                    right = x + w,
+                    back = x + w / 4.0,
+                )?;
+            }
+            AssignGate::Box(_) => {
+                write!(
+                    writer,
+                    r#"<path d="M{x},{top}H{right}V{bottom}H{x}z"/>"#,
+                    right = x + w,
+                )?;
+            }
+        }
+
+        if gate.is_inverted() {
+            write!(
+                writer,
+                r#"<circle cx="{cx}" cy="{y}" r="{BUBBLE_RADIUS}"/>"#,
+                cx = x + w + BUBBLE_RADIUS,
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> AssembledAssignFigure<'a> {
+    /// Render a [`AssembledAssignFigure`] into a `writer`.
+    #[inline]
+    pub fn write_svg(&self, writer: &mut impl io::Write) -> io::Result<()> {
+        self.write_svg_with_options(writer, &RenderOptions::default())
+    }
+
+    /// Render a [`AssembledAssignFigure`] into a `writer` with a set of options.
+    pub fn write_svg_with_options(
+        &self,
+        writer: &mut impl io::Write,
+        options: &RenderOptions,
+    ) -> io::Result<()> {
+        #[cfg(feature = "embed_font")]
+        if options.font.embed_subset || options.font.outline_text {
+            return crate::font::write_with_font_options(writer, &options.font, |svg| {
+                self.write_svg_document(svg, options)
+            });
+        }
+
+        self.write_svg_document(writer, options)
+    }
+
+    fn write_svg_document(
+        &self,
+        writer: &mut impl io::Write,
+        options: &RenderOptions,
+    ) -> io::Result<()> {
+        let RenderOptions {
+            background,
+            padding,
+            spacing,
+            assign,
+            ..
+        } = options;
+
+        let font = Font::default();
+        let font_family = font
+            .get_font_family_name()
+            .unwrap_or_else(|| "Helvetica".to_string());
+        let font_size = assign.font_size;
+
+        let input_width = self
+            .inputs
+            .iter()
+            .map(|input| font.get_text_width(input.name(), font_size))
+            .max()
+            .map_or(0, |width| width + spacing.textbox_to_schema);
+        let output_width = self
+            .outputs
+            .iter()
+            .map(|output| font.get_text_width(output.name(), font_size))
+            .max()
+            .map_or(0, |width| width + spacing.textbox_to_schema);
+
+        let grid = Grid {
+            gates_x: f64::from(padding.figure_left + input_width),
+            gates_y: f64::from(padding.figure_top),
+            gate_width: f64::from(assign.gate_width),
+            row_height: f64::from(assign.row_height),
+            column_spacing: f64::from(assign.column_spacing),
+        };
+
+        // The outputs are placed after the last column of gates.
+        let outputs_x = grid.x(self.num_columns);
+
+        let figure_width = outputs_x.ceil() as u32 + output_width + padding.figure_right;
+        let figure_height =
+            padding.figure_top + self.num_rows * assign.row_height + padding.figure_bottom;
+
+        write!(
+            writer,
+            r#"<svg version="1.1" xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink" viewport="0 0 {figure_width} {figure_height}" overflow="hidden" width="{figure_width}" height="{figure_height}">"#,
+        )?;
+
+        // Background
+        if let Some(background) = background {
+            write!(
+                writer,
+                r##"<rect width="100%" height="100%" fill="{background}"/>"##
+            )?;
+        }
+
+        let shapes = self
+            .gates
+            .iter()
+            .map(|gate| GateShape::new(gate, &grid, assign))
+            .collect::<Vec<_>>();
+
+        let source_pin = |source: AssignSource| match source {
+            AssignSource::Input(i) => (grid.gates_x, grid.y(f64::from(self.inputs[i].row()))),
+            AssignSource::Gate(i) => shapes[i].output_pin(self.gates[i].gate()),
+        };
+
+        // Wires
+        write!(writer, r#"<path fill="none" d=""#)?;
+        for (gate, shape) in self.gates.iter().zip(shapes.iter()) {
+            let num_inputs = gate.inputs().len();
+
+            for (i, source) in gate.inputs().iter().enumerate() {
+                let (source_x, source_y) = source_pin(*source);
+                let (pin_x, pin_y) = shape.input_pin(gate.gate(), i, num_inputs);
+
+                if source_y == pin_y {
+                    write!(writer, "M{source_x},{source_y}H{pin_x}")?;
+                    continue;
+                }
+
+                // Wires that bend down turn closer to the gate the higher they are and wires that
+                // bend up turn closer to the gate the lower they are. This way the wires of a
+                // single gate never cross.
+                let turn = if source_y < pin_y {
+                    i + 1
+                } else {
+                    num_inputs - i
+                };
+                let turn_x = shape.x - grid.column_spacing * turn as f64 / (num_inputs + 1) as f64;
+
+                write!(writer, "M{source_x},{source_y}H{turn_x}V{pin_y}H{pin_x}")?;
+            }
+        }
+        for output in self.outputs.iter() {
+            let (source_x, source_y) = source_pin(output.source());
+            write!(writer, "M{source_x},{source_y}H{outputs_x}")?;
+        }
+        write!(
+            writer,
+            r#"" stroke="{color}" stroke-width="1"/>"#,
+            color = assign.wire_color,
+        )?;
+
+        // Gates
+        write!(
+            writer,
+            r#"<g fill="{fill}" stroke="{stroke}" stroke-width="1">"#,
+            fill = assign
+                .gate_background
+                .map_or_else(|| "none".to_string(), |color| color.to_string()),
+            stroke = assign.gate_color,
+        )?;
+        for (gate, shape) in self.gates.iter().zip(shapes.iter()) {
+            shape.write_svg(writer, gate.gate())?;
+        }
+        write!(writer, "</g>")?;
+
+        // Box Operators
+        for (gate, shape) in self.gates.iter().zip(shapes.iter()) {
+            let AssignGate::Box(operator) = gate.gate() else {
+                continue;
+            };
+
+            write!(
+                writer,
+                r##"<text x="{x}" y="{y}" text-anchor="middle" dominant-baseline="middle" font-family="{font_family}" font-size="{font_size}" fill="{color}" letter-spacing="0"><tspan>{text}</tspan></text>"##,
+                x = shape.x + shape.body_width / 2.0,
+                y = shape.y,
+                color = assign.text_color,
+                text = escape_str(operator),
+            )?;
+        }
+
+        // Input Names
+        for input in self.inputs.iter() {
+            write!(
+                writer,
+                r##"<text x="{x}" y="{y}" text-anchor="end" dominant-baseline="middle" font-family="{font_family}" font-size="{font_size}" fill="{color}" letter-spacing="0"><tspan>{text}</tspan></text>"##,
+                x = grid.gates_x - f64::from(spacing.textbox_to_schema),
+                y = grid.y(f64::from(input.row())),
+                color = assign.text_color,
+                text = escape_str(input.name()),
+            )?;
+        }
+
+        // Output Names
+        for output in self.outputs.iter() {
+            write!(
+                writer,
+                r##"<text x="{x}" y="{y}" dominant-baseline="middle" font-family="{font_family}" font-size="{font_size}" fill="{color}" letter-spacing="0"><tspan>{text}</tspan></text>"##,
+                x = outputs_x + f64::from(spacing.textbox_to_schema),
+                y = grid.y(output.row()),
+                color = assign.text_color,
+                text = escape_str(output.name()),
+            )?;
+        }
+
+        write!(writer, "</svg>")?;
+
+        Ok(())
+    }
+}
@@ -19,3 +19,16 @@ pub fn escape_str(s: &str) -> Cow<str> {
     Cow::Owned(output)
 }
 
+#[cfg(feature = "embed_font")]
+pub fn unescape_str(s: &str) -> Cow<'_, str> {
+    if !s.contains('&') {
+        return Cow::Borrowed(s);
+    }
+
+    Cow::Owned(
+        s.replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&quot;", "\"")
+            .replace("&amp;", "&"),
+    )
+}
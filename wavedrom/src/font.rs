@@ -1,5 +1,19 @@
 use std::marker::PhantomData;
 
+#[cfg(feature = "embed_font")]
+pub(crate) mod metrics;
+#[cfg(feature = "embed_font")]
+mod outline;
+#[cfg(feature = "embed_font")]
+mod subset;
+
+#[cfg(feature = "embed_font")]
+use crate::signal::options::FontOptions;
+
+/// The Helvetica TTF file that is embedded into the binary.
+#[cfg(any(feature = "embed_font", feature = "raster"))]
+pub(crate) static HELVETICA_TTF: &[u8] = include_bytes!("../helvetica.ttf");
+
 #[cfg(feature = "embed_font")]
 static EMBEDDED_HELVETICA: std::sync::OnceLock<ttf_parser::Face<'static>> = std::sync::OnceLock::new(); 
 
@@ -72,10 +86,8 @@ impl Font {
 
 #[cfg(feature = "embed_font")]
 impl Font {
-    fn get_face(&self) -> &ttf_parser::Face<'static> {
-        EMBEDDED_HELVETICA.get_or_init(|| {
-            ttf_parser::Face::parse(include_bytes!("../helvetica.ttf"), 0).unwrap()
-        })
+    pub(crate) fn get_face(&self) -> &ttf_parser::Face<'static> {
+        EMBEDDED_HELVETICA.get_or_init(|| ttf_parser::Face::parse(HELVETICA_TTF, 0).unwrap())
     }
 
     #[inline]
@@ -124,6 +136,27 @@ impl Font {
     }
 }
 
+/// Render a SVG document with `render` and apply the [`FontOptions`] to its text before writing it
+/// into the `writer`.
+#[cfg(feature = "embed_font")]
+pub(crate) fn write_with_font_options<W: std::io::Write>(
+    writer: &mut W,
+    options: &FontOptions,
+    render: impl FnOnce(&mut Vec<u8>) -> std::io::Result<()>,
+) -> std::io::Result<()> {
+    let mut svg = Vec::new();
+    render(&mut svg)?;
+
+    // Outlined text does not need a font, so there is nothing to embed.
+    if options.outline_text {
+        outline::write_with_outlined_text(&svg, writer)
+    } else if options.embed_subset {
+        subset::write_with_font_subset(&svg, writer)
+    } else {
+        writer.write_all(&svg)
+    }
+}
+
 #[ignore]
 #[test]
 #[cfg(all(feature = "gen_lut", feature = "embed_font"))]
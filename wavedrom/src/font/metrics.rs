@@ -0,0 +1,105 @@
+//! Placement of text with the embedded font
+//!
+//! The PDF output and the outlined text of the SVG output both draw the glyphs of the embedded
+//! font themselves. They use these metrics to place the text the way a SVG renderer would.
+
+use crate::text::BaselineShift;
+
+/// The slant of italic text, because the font has no italic variant.
+pub(crate) const ITALIC_SKEW: f32 = 0.2;
+
+/// The stroke width relative to the font size that is used to embolden bold text, because the
+/// font has no bold variant.
+pub(crate) const BOLD_STROKE_WIDTH: f32 = 0.03;
+
+/// The baseline that the `y` position of a text refers to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum DominantBaseline {
+    Alphabetic,
+    Middle,
+    Central,
+    /// Also used for `text-before-edge`
+    Hanging,
+    /// Also used for `ideographic`
+    TextAfterEdge,
+}
+
+/// A line that is drawn along a span of text
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TextDecoration {
+    Underline,
+    Overline,
+    LineThrough,
+}
+
+/// The metrics of the font at a specific font size
+pub(crate) struct TextMetrics<'a> {
+    face: &'a ttf_parser::Face<'static>,
+    scale: f32,
+}
+
+impl<'a> TextMetrics<'a> {
+    pub(crate) fn new(face: &'a ttf_parser::Face<'static>, font_size: f32) -> Self {
+        Self {
+            face,
+            scale: font_size / f32::from(face.units_per_em()),
+        }
+    }
+
+    /// The offset of the alphabetic baseline with regards to the `y` position of the text.
+    pub(crate) fn dominant_baseline(&self, baseline: DominantBaseline) -> f32 {
+        let face = self.face;
+
+        let offset = match baseline {
+            DominantBaseline::Alphabetic => 0.,
+            DominantBaseline::Middle => {
+                f32::from(face.x_height().unwrap_or(face.ascender() / 2)) / 2.
+            }
+            DominantBaseline::Central => {
+                (f32::from(face.ascender()) + f32::from(face.descender())) / 2.
+            }
+            DominantBaseline::Hanging => f32::from(face.ascender()),
+            DominantBaseline::TextAfterEdge => f32::from(face.descender()),
+        };
+
+        offset * self.scale
+    }
+
+    /// How far the baseline is raised by a `shift`.
+    pub(crate) fn baseline_shift(&self, shift: BaselineShift) -> f32 {
+        let shift = match shift {
+            BaselineShift::Subscript => -self
+                .face
+                .subscript_metrics()
+                .map_or(0., |metrics| f32::from(metrics.y_offset)),
+            BaselineShift::Superscript => self
+                .face
+                .superscript_metrics()
+                .map_or(0., |metrics| f32::from(metrics.y_offset)),
+        };
+
+        shift * self.scale
+    }
+
+    /// The offset of the top of a `decoration` line with regards to the baseline and the
+    /// thickness of the line.
+    pub(crate) fn decoration(&self, decoration: TextDecoration) -> (f32, f32) {
+        let face = self.face;
+        let line_metrics = face.underline_metrics();
+
+        let thickness = line_metrics.map_or(f32::from(face.units_per_em()) / 12., |metrics| {
+            f32::from(metrics.thickness)
+        }) * self.scale;
+        let position = match decoration {
+            TextDecoration::Underline => {
+                line_metrics.map_or(0., |metrics| f32::from(metrics.position))
+            }
+            TextDecoration::Overline => f32::from(face.ascender()),
+            TextDecoration::LineThrough => face
+                .strikeout_metrics()
+                .map_or(0., |metrics| f32::from(metrics.position)),
+        };
+
+        (-position * self.scale - thickness / 2., thickness)
+    }
+}
@@ -0,0 +1,288 @@
+//! Replacement of the text in SVG documents by the outlines of its glyphs
+//!
+//! The `<text>` elements are laid out with the same font and metrics that are used to measure the
+//! text. Each text span becomes a `<path>`, so the transformations of the surrounding elements are
+//! kept.
+
+use std::fmt::Write as _;
+use std::io;
+
+use super::metrics::{
+    DominantBaseline, TextDecoration, TextMetrics, BOLD_STROKE_WIDTH, ITALIC_SKEW,
+};
+use super::Font;
+use crate::escape::unescape_str;
+use crate::text::BaselineShift;
+
+/// Write a SVG document into the `writer` where every `<text>` element is replaced by the outlines
+/// of its glyphs.
+pub(super) fn write_with_outlined_text(svg: &[u8], writer: &mut impl io::Write) -> io::Result<()> {
+    let svg = String::from_utf8_lossy(svg);
+    let font = Font::default();
+    let face = font.get_face();
+
+    let mut rest = &svg[..];
+    while let Some(start) = rest.find("<text") {
+        writer.write_all(&rest.as_bytes()[..start])?;
+        rest = &rest[start..];
+
+        let Some(end) = rest.find("</text>") else {
+            break;
+        };
+        writer.write_all(outline_text_element(face, &rest[..end]).as_bytes())?;
+        rest = &rest[end + "</text>".len()..];
+    }
+
+    writer.write_all(rest.as_bytes())
+}
+
+/// Get the value of the attribute with `name` from the attributes of an element.
+fn attribute<'a>(attributes: &'a str, name: &str) -> Option<&'a str> {
+    let mut parts = attributes.split('"');
+
+    while let (Some(key), Some(value)) = (parts.next(), parts.next()) {
+        let key = key.trim().trim_end_matches('=');
+        if key.rsplit(' ').next() == Some(name) {
+            return Some(value);
+        }
+    }
+
+    None
+}
+
+fn number_attribute(attributes: &str, name: &str) -> Option<f32> {
+    attribute(attributes, name).and_then(|value| value.parse().ok())
+}
+
+/// Round a coordinate to two decimals to keep the paths short.
+fn round(value: f32) -> f32 {
+    (value * 100.).round() / 100.
+}
+
+/// A piece of text with a single style
+struct Span<'a> {
+    text: String,
+    attributes: &'a str,
+    font_size: f32,
+    fill: &'a str,
+}
+
+/// The spans in the content of a `<text>` element. Text outside of a `<tspan>` is its own span.
+fn spans<'a>(content: &'a str, text_attributes: &'a str) -> Vec<Span<'a>> {
+    let font_size = number_attribute(text_attributes, "font-size").unwrap_or(16.);
+    let fill = attribute(text_attributes, "fill").unwrap_or("#000");
+
+    let mut spans = Vec::new();
+    let mut rest = content;
+    while !rest.is_empty() {
+        let (attributes, text) = if let Some(tspan) = rest.strip_prefix("<tspan") {
+            let attributes_end = tspan.find('>').unwrap_or(tspan.len());
+            let text_end = tspan.find("</tspan>").unwrap_or(tspan.len());
+
+            let attributes = &tspan[..attributes_end];
+            let text = tspan.get(attributes_end + 1..text_end).unwrap_or_default();
+            rest = tspan.get(text_end + "</tspan>".len()..).unwrap_or_default();
+
+            (attributes, text)
+        } else {
+            let text_end = rest[1..].find('<').map_or(rest.len(), |end| end + 1);
+            let text = &rest[..text_end];
+            rest = &rest[text_end..];
+
+            ("", text)
+        };
+
+        spans.push(Span {
+            text: unescape_str(text).into_owned(),
+            attributes,
+            font_size: number_attribute(attributes, "font-size").unwrap_or(font_size),
+            fill: attribute(attributes, "fill").unwrap_or(fill),
+        });
+    }
+
+    spans
+}
+
+/// Appends the outline of a glyph to a SVG path.
+struct PathBuilder<'a> {
+    path: &'a mut String,
+    x: f32,
+    y: f32,
+    scale: f32,
+    skew: f32,
+}
+
+impl PathBuilder<'_> {
+    /// Add a point in font units to the path.
+    fn point(&mut self, x: f32, y: f32) {
+        let x = self.x + (x + self.skew * y) * self.scale;
+        let y = self.y - y * self.scale;
+
+        let _ = write!(self.path, "{},{} ", round(x), round(y));
+    }
+}
+
+impl ttf_parser::OutlineBuilder for PathBuilder<'_> {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.path.push('M');
+        self.point(x, y);
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.path.push('L');
+        self.point(x, y);
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        self.path.push('Q');
+        self.point(x1, y1);
+        self.point(x, y);
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        self.path.push('C');
+        self.point(x1, y1);
+        self.point(x2, y2);
+        self.point(x, y);
+    }
+
+    fn close(&mut self) {
+        self.path.push('Z');
+    }
+}
+
+/// Turn a `<text>` element without its closing tag into a group of paths.
+fn outline_text_element(face: &ttf_parser::Face<'static>, element: &str) -> String {
+    let attributes_end = element.find('>').unwrap_or(element.len());
+    let attributes = &element["<text".len()..attributes_end];
+    let content = element.get(attributes_end + 1..).unwrap_or_default();
+
+    let units_per_em = f32::from(face.units_per_em());
+    let letter_spacing = number_attribute(attributes, "letter-spacing").unwrap_or(0.);
+    let advance = |c: char| {
+        face.glyph_index(c)
+            .and_then(|glyph| face.glyph_hor_advance(glyph))
+            .map_or(0., f32::from)
+    };
+
+    let spans = spans(content, attributes);
+    let widths = spans
+        .iter()
+        .map(|span| {
+            span.text
+                .chars()
+                .map(|c| advance(c) * span.font_size / units_per_em + letter_spacing)
+                .sum::<f32>()
+        })
+        .collect::<Vec<_>>();
+    let width = widths.iter().sum::<f32>();
+
+    let mut x = number_attribute(attributes, "x").unwrap_or(0.);
+    let y = number_attribute(attributes, "y").unwrap_or(0.);
+    x -= match attribute(attributes, "text-anchor") {
+        Some("middle") => width / 2.,
+        Some("end") => width,
+        _ => 0.,
+    };
+
+    let mut group = String::from("<g");
+    if let Some(transform) = attribute(attributes, "transform") {
+        let _ = write!(group, r#" transform="{transform}""#);
+    }
+    group.push('>');
+
+    for (span, width) in spans.iter().zip(widths) {
+        let scale = span.font_size / units_per_em;
+        let metrics = TextMetrics::new(face, span.font_size);
+
+        let dominant_baseline = match attribute(attributes, "dominant-baseline") {
+            Some("middle") => DominantBaseline::Middle,
+            Some("central") => DominantBaseline::Central,
+            Some("hanging") | Some("text-before-edge") => DominantBaseline::Hanging,
+            Some("text-after-edge") | Some("ideographic") => DominantBaseline::TextAfterEdge,
+            _ => DominantBaseline::Alphabetic,
+        };
+        let baseline_shift = match attribute(span.attributes, "baseline-shift") {
+            Some("sub") => metrics.baseline_shift(BaselineShift::Subscript),
+            Some("super") => metrics.baseline_shift(BaselineShift::Superscript),
+            _ => 0.,
+        };
+        let baseline = y + metrics.dominant_baseline(dominant_baseline) - baseline_shift;
+
+        let mut path = String::new();
+        let mut builder = PathBuilder {
+            path: &mut path,
+            x,
+            y: baseline,
+            scale,
+            skew: match attribute(span.attributes, "font-style") {
+                Some("italic") => ITALIC_SKEW,
+                _ => 0.,
+            },
+        };
+        for c in span.text.chars() {
+            if let Some(glyph) = face.glyph_index(c) {
+                face.outline_glyph(glyph, &mut builder);
+            }
+            builder.x += advance(c) * scale + letter_spacing;
+        }
+
+        let fill = span.fill;
+        if !path.is_empty() {
+            let _ = write!(group, r#"<path d="{}" fill="{fill}""#, path.trim_end());
+            if attribute(span.attributes, "font-weight") == Some("bold") {
+                let _ = write!(
+                    group,
+                    r#" stroke="{fill}" stroke-width="{}""#,
+                    round(span.font_size * BOLD_STROKE_WIDTH)
+                );
+            }
+            group.push_str("/>");
+        }
+
+        // Text Decorations
+        let decorations = attribute(span.attributes, "text-decoration").unwrap_or_default();
+        for decoration in decorations.split(' ') {
+            let (offset, thickness) = metrics.decoration(match decoration {
+                "underline" => TextDecoration::Underline,
+                "overline" => TextDecoration::Overline,
+                "line-through" => TextDecoration::LineThrough,
+                _ => continue,
+            });
+
+            let _ = write!(
+                group,
+                r#"<rect x="{x}" y="{y}" width="{width}" height="{thickness}" fill="{fill}"/>"#,
+                x = round(x),
+                y = round(baseline + offset),
+                width = round(width),
+                thickness = round(thickness),
+            );
+        }
+
+        x += width;
+    }
+
+    group.push_str("</g>");
+    group
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text_is_replaced_by_paths() {
+        let svg = r##"<svg><g transform="translate(4,0)"><text x="10" y="20" text-anchor="middle" dominant-baseline="middle" font-family="Helvetica" font-size="14" fill="#000" letter-spacing="0"><tspan>A&amp;</tspan><tspan font-weight="bold" fill="#F00">B</tspan></text></g></svg>"##;
+
+        let mut output = Vec::new();
+        write_with_outlined_text(svg.as_bytes(), &mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+
+        assert!(!output.contains("<text"));
+        assert!(output.starts_with(r#"<svg><g transform="translate(4,0)"><g><path d="M"#));
+        assert!(output.ends_with("</g></g></svg>"));
+        assert_eq!(output.matches("<path").count(), 2);
+        assert!(output.contains(r##"fill="#F00" stroke="#F00""##));
+    }
+}
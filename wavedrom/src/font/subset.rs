@@ -0,0 +1,319 @@
+//! Embedding of a subset of the font into SVG documents
+//!
+//! The subset keeps the glyph ids of the original font, but only the outlines of the glyphs that
+//! are used remain. The tables for kerning and glyph substitution are left out, because the text
+//! widths are measured without them.
+
+use std::collections::BTreeSet;
+use std::io;
+
+use super::{Font, HELVETICA_TTF};
+use crate::escape::unescape_str;
+
+/// The tables that are copied into the subset. The `glyf` and `loca` tables are rebuilt.
+const KEPT_TABLES: [&[u8; 4]; 13] = [
+    b"OS/2", b"cmap", b"cvt ", b"fpgm", b"glyf", b"head", b"hhea", b"hmtx", b"loca", b"maxp",
+    b"name", b"post", b"prep",
+];
+
+/// Write a SVG document into the `writer` with a subset of the font with the glyphs that it uses
+/// as an `@font-face` rule.
+pub(super) fn write_with_font_subset(svg: &[u8], writer: &mut impl io::Write) -> io::Result<()> {
+    let font = Font::default();
+    let face = font.get_face();
+
+    let glyphs = text_chars(&String::from_utf8_lossy(svg))
+        .into_iter()
+        .filter_map(|c| face.glyph_index(c))
+        .map(|glyph| glyph.0)
+        .collect::<BTreeSet<_>>();
+    let subset = subset_font(HELVETICA_TTF, &glyphs);
+
+    // The rule is put directly after the opening `<svg>` tag.
+    let split_at = svg
+        .windows(4)
+        .position(|window| window == b"<svg")
+        .and_then(|start| {
+            svg[start..]
+                .iter()
+                .position(|b| *b == b'>')
+                .map(|end| start + end + 1)
+        })
+        .unwrap_or(0);
+    let (head, body) = svg.split_at(split_at);
+
+    writer.write_all(head)?;
+    write!(
+        writer,
+        r#"<style>@font-face{{font-family:"{font_family}";src:url(data:font/ttf;base64,{data}) format("truetype")}}</style>"#,
+        font_family = font
+            .get_font_family_name()
+            .unwrap_or_else(|| "Helvetica".to_string()),
+        data = base64(subset.as_deref().unwrap_or(HELVETICA_TTF)),
+    )?;
+    writer.write_all(body)
+}
+
+/// The characters in the `<text>` elements of a SVG document.
+fn text_chars(svg: &str) -> BTreeSet<char> {
+    let mut chars = BTreeSet::new();
+
+    let mut rest = svg;
+    while let Some(start) = rest.find("<text") {
+        rest = &rest[start..];
+        let end = rest.find("</text>").unwrap_or(rest.len());
+
+        let mut content = String::new();
+        let mut is_in_tag = false;
+        for c in rest[..end].chars() {
+            match c {
+                '<' => is_in_tag = true,
+                '>' => is_in_tag = false,
+                c if !is_in_tag => content.push(c),
+                _ => {}
+            }
+        }
+
+        chars.extend(unescape_str(&content).chars());
+
+        rest = &rest[end..];
+    }
+
+    chars
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_be_bytes(
+        data.get(offset..offset + 2)?.try_into().ok()?,
+    ))
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(
+        data.get(offset..offset + 4)?.try_into().ok()?,
+    ))
+}
+
+fn checksum(data: &[u8]) -> u32 {
+    data.chunks(4).fold(0u32, |sum, chunk| {
+        let mut word = [0; 4];
+        word[..chunk.len()].copy_from_slice(chunk);
+        sum.wrapping_add(u32::from_be_bytes(word))
+    })
+}
+
+/// Create a copy of a TrueType font where only the `glyphs`, the glyphs they are composed of and
+/// the `.notdef` glyph have an outline.
+///
+/// Returns `None` if the font cannot be read.
+fn subset_font(font: &[u8], glyphs: &BTreeSet<u16>) -> Option<Vec<u8>> {
+    let num_tables = usize::from(read_u16(font, 4)?);
+    let tables = (0..num_tables)
+        .map(|i| {
+            let record = 12 + 16 * i;
+            let tag = font.get(record..record + 4)?;
+            let offset = read_u32(font, record + 8)? as usize;
+            let length = read_u32(font, record + 12)? as usize;
+            Some((tag, font.get(offset..offset + length)?))
+        })
+        .collect::<Option<Vec<_>>>()?;
+    let table = |tag: &[u8; 4]| {
+        tables
+            .iter()
+            .find(|(table_tag, _)| table_tag == tag)
+            .map(|(_, data)| *data)
+    };
+
+    let head = table(b"head")?;
+    let glyf = table(b"glyf")?;
+    let loca = table(b"loca")?;
+    let num_glyphs = usize::from(read_u16(table(b"maxp")?, 4)?);
+    let is_long_loca = read_u16(head, 50)? != 0;
+
+    let offsets = (0..=num_glyphs)
+        .map(|i| {
+            if is_long_loca {
+                read_u32(loca, i * 4).map(|offset| offset as usize)
+            } else {
+                read_u16(loca, i * 2).map(|offset| usize::from(offset) * 2)
+            }
+        })
+        .collect::<Option<Vec<_>>>()?;
+    let outline = |glyph: usize| glyf.get(offsets[glyph]..offsets[glyph + 1]);
+
+    // Composite glyphs are made up of other glyphs, which need to be kept as well.
+    let mut kept = vec![false; num_glyphs];
+    let mut pending = glyphs
+        .iter()
+        .map(|glyph| usize::from(*glyph))
+        .chain([0])
+        .collect::<Vec<_>>();
+    while let Some(glyph) = pending.pop() {
+        if glyph >= num_glyphs || kept[glyph] {
+            continue;
+        }
+        kept[glyph] = true;
+
+        let outline = outline(glyph)?;
+        if outline.is_empty() || read_u16(outline, 0)? as i16 >= 0 {
+            continue;
+        }
+
+        let mut offset = 10;
+        loop {
+            let flags = read_u16(outline, offset)?;
+            pending.push(usize::from(read_u16(outline, offset + 2)?));
+
+            offset += 4;
+            offset += if flags & 0x0001 != 0 { 4 } else { 2 };
+            offset += if flags & 0x0008 != 0 {
+                2
+            } else if flags & 0x0040 != 0 {
+                4
+            } else if flags & 0x0080 != 0 {
+                8
+            } else {
+                0
+            };
+
+            if flags & 0x0020 == 0 {
+                break;
+            }
+        }
+    }
+
+    let mut new_glyf = Vec::new();
+    let mut new_offsets = vec![0];
+    for (glyph, is_kept) in kept.iter().enumerate() {
+        if *is_kept {
+            new_glyf.extend_from_slice(outline(glyph)?);
+            new_glyf.resize(new_glyf.len().next_multiple_of(4), 0);
+        }
+        new_offsets.push(new_glyf.len());
+    }
+    let new_loca = new_offsets
+        .into_iter()
+        .flat_map(|offset| {
+            if is_long_loca {
+                (offset as u32).to_be_bytes().to_vec()
+            } else {
+                ((offset / 2) as u16).to_be_bytes().to_vec()
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let mut new_tables = KEPT_TABLES
+        .iter()
+        .filter_map(|tag| {
+            let data = match *tag {
+                b"glyf" => new_glyf.clone(),
+                b"loca" => new_loca.clone(),
+                _ => table(tag)?.to_vec(),
+            };
+            Some((*tag, data))
+        })
+        .collect::<Vec<_>>();
+    new_tables.sort_by_key(|(tag, _)| **tag);
+
+    // The `checkSumAdjustment` of the `head` table is calculated over the whole font.
+    let head_index = new_tables.iter().position(|(tag, _)| *tag == b"head")?;
+    new_tables[head_index].1[8..12].fill(0);
+
+    let num_tables = new_tables.len() as u16;
+    let entry_selector = 15 - num_tables.leading_zeros() as u16;
+    let search_range: u16 = 16 << entry_selector;
+
+    let mut subset = Vec::new();
+    subset.extend_from_slice(&0x0001_0000u32.to_be_bytes());
+    subset.extend_from_slice(&num_tables.to_be_bytes());
+    subset.extend_from_slice(&search_range.to_be_bytes());
+    subset.extend_from_slice(&entry_selector.to_be_bytes());
+    subset.extend_from_slice(&(num_tables * 16 - search_range).to_be_bytes());
+
+    let mut offset = 12 + 16 * new_tables.len();
+    for (tag, data) in &new_tables {
+        subset.extend_from_slice(*tag);
+        subset.extend_from_slice(&checksum(data).to_be_bytes());
+        subset.extend_from_slice(&(offset as u32).to_be_bytes());
+        subset.extend_from_slice(&(data.len() as u32).to_be_bytes());
+        offset += data.len().next_multiple_of(4);
+    }
+
+    let mut head_offset = 0;
+    for (tag, data) in &new_tables {
+        if *tag == b"head" {
+            head_offset = subset.len();
+        }
+        subset.extend_from_slice(data);
+        subset.resize(subset.len().next_multiple_of(4), 0);
+    }
+
+    let adjustment = 0xB1B0_AFBAu32.wrapping_sub(checksum(&subset));
+    subset[head_offset + 8..head_offset + 12].copy_from_slice(&adjustment.to_be_bytes());
+
+    Some(subset)
+}
+
+fn base64(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut encoded = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let bytes = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let bits = u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]);
+
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(char::from(ALPHABET[(bits >> (18 - 6 * i)) as usize & 0x3F]));
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn base64_padding() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(base64(b"foob"), "Zm9vYg==");
+    }
+
+    #[test]
+    fn subset_keeps_used_glyphs() {
+        let face = ttf_parser::Face::parse(HELVETICA_TTF, 0).unwrap();
+        let glyph = face.glyph_index('A').unwrap();
+
+        let subset = subset_font(HELVETICA_TTF, &BTreeSet::from([glyph.0])).unwrap();
+        assert!(subset.len() < HELVETICA_TTF.len());
+
+        let subset_face = ttf_parser::Face::parse(&subset, 0).unwrap();
+        assert_eq!(subset_face.glyph_index('A'), Some(glyph));
+        assert_eq!(
+            subset_face.glyph_bounding_box(glyph),
+            face.glyph_bounding_box(glyph)
+        );
+
+        let unused = face.glyph_index('B').unwrap();
+        assert_eq!(subset_face.glyph_bounding_box(unused), None);
+    }
+
+    #[test]
+    fn text_chars_are_unescaped() {
+        let svg =
+            r#"<svg><text x="0"><tspan>a&amp;b</tspan></text><path d="M0,0"/><text>c</text></svg>"#;
+        assert_eq!(text_chars(svg), BTreeSet::from(['a', '&', 'b', 'c']));
+    }
+}
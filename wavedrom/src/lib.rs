@@ -85,6 +85,17 @@ wavedrom::render_json5(r##"
 //! `serde` feature to deserialize a WaveJson file.
 //! * `serde_json`. Disabled by default. Formal version of JSON that can be used with the `serde`
 //! feature to deserialize a WaveJson file.
+//! * `yaml`. Disabled by default. YAML as an alternative format to deserialize a WaveJson file or
+//! a skin.
+//! * `toml`. Disabled by default. TOML as an alternative format to deserialize a WaveJson file or a
+//! skin.
+//! * `vcd`. Disabled by default. Adds the [`vcd`] module, which reads Value Change Dump files from
+//! simulators and turns them into signal figures, and writes signal figures as Value Change Dump
+//! files.
+//! * `raster`. Disabled by default. Adds the [`raster`] module, which renders figures as PNG
+//! images with [`resvg`][resvg] and the embedded [Helvetica][helvetica] font.
+//! * `pdf`. Disabled by default. Adds the [`pdf`] module, which renders figures as vector PDF
+//! documents with the embedded [Helvetica][helvetica] font. This enables the `embed_font` feature.
 //! * `skins`. Enabled by default. Adds the [`skin`] module, which defines the serialize and
 //! deserialize formats for WaveDrom skins. Also adds logic to merge a skin into an existing set of
 //! options.
@@ -98,7 +109,8 @@ wavedrom::render_json5(r##"
 //! A [`Figure`] can be created in two ways. First, a [`Figure`] can be built programmatically with
 //! the [`Figure::new`] method and the builder pattern methods. Second, a [`Figure`] can be built
 //! by loading a [WaveJson][wavejson] file. This can be done with the [`Figure::from_json5`] or
-//! [`Figure::from_json`] methods.
+//! [`Figure::from_json`] methods, or with the [`Figure::from_yaml`] or [`Figure::from_toml`]
+//! methods for the same structure written in YAML or TOML.
 //!
 //! **2. Assemble [`Figure`] to [`AssembledFigure`]**
 //!
@@ -112,9 +124,13 @@ wavedrom::render_json5(r##"
 //! [`AssembledFigure::write_svg_with_options`] methods. This will write an SVG into an
 //! [`io::Write`][std::io::Write] buffer. If a write to the [`io::Write`][std::io::Write] is
 //! expensive, it is recommended to wrap the [`io::Write`][std::io::Write] in a
-//! [`std::io::BufWriter`].
+//! [`std::io::BufWriter`]. With the `raster` feature, the `write_png` methods in the [`raster`]
+//! module render a PNG image instead. With the `pdf` feature, the `write_pdf` methods in the
+//! [`pdf`] module render a PDF document. The `write_text` methods in the [`terminal`] module
+//! draw the signals as Unicode text for terminals.
 //!
 //! [helvetica]: https://en.wikipedia.org/wiki/Helvetica
+//! [resvg]: https://github.com/RazrFalcon/resvg
 //! [dtd]: https://en.wikipedia.org/wiki/Digital_timing_diagram
 //! [editor]: https://gburghoorn.com/wavedrom
 //! [cli]: https://github.com/coastalwhite/wavedrom-rs/tree/main/wavedrom
@@ -125,6 +141,9 @@ wavedrom::render_json5(r##"
     all(
         feature = "serde_json",
         feature = "json5",
+        feature = "yaml",
+        feature = "toml",
+        feature = "vcd",
         feature = "serde",
         feature = "skins"
     ),
@@ -138,26 +157,87 @@ pub use json5;
 #[cfg(feature = "serde_json")]
 pub use serde_json;
 
+#[cfg(feature = "yaml")]
+pub use serde_yaml;
+
+#[cfg(feature = "toml")]
+pub use toml;
+
 #[cfg(feature = "skins")]
 pub mod skin;
 
 mod color;
 mod font;
 mod shortcuts;
+pub mod assign;
+pub mod register;
 pub mod signal;
+pub mod text;
 mod escape;
  
 pub use font::Font;
 pub use color::Color;
 pub use shortcuts::*;
 
+use std::io;
+
+use self::assign::AssignFigure;
+use self::register::RegisterFigure;
+use self::signal::options::{PathAssembleOptions, RenderOptions};
 use self::signal::SignalFigure;
 
 #[cfg(feature = "serde")]
 pub mod wavejson;
 
+#[cfg(feature = "vcd")]
+pub mod vcd;
+
+pub mod terminal;
+
+#[cfg(feature = "raster")]
+pub mod raster;
+
+#[cfg(feature = "pdf")]
+pub mod pdf;
+
 /// A general wavedrom figure
 pub enum Figure {
     /// A figure containing a set of signals
     Signal(SignalFigure),
+    /// A figure containing a register / bitfield
+    Register(RegisterFigure),
+    /// A figure containing a logic circuit
+    Assign(AssignFigure),
+}
+
+impl Figure {
+    /// Assemble and render a [`Figure`] into a `writer`.
+    #[inline]
+    pub fn write_svg(&self, writer: &mut impl io::Write) -> io::Result<()> {
+        self.write_svg_with_options(
+            writer,
+            PathAssembleOptions::default(),
+            &RenderOptions::default(),
+        )
+    }
+
+    /// Assemble and render a [`Figure`] into a `writer` with a specific set of options.
+    pub fn write_svg_with_options(
+        &self,
+        writer: &mut impl io::Write,
+        assemble_options: PathAssembleOptions,
+        render_options: &RenderOptions,
+    ) -> io::Result<()> {
+        match self {
+            Self::Signal(figure) => figure
+                .assemble_with_options(assemble_options)
+                .write_svg_with_options(writer, render_options),
+            Self::Register(figure) => figure
+                .assemble()
+                .write_svg_with_options(writer, render_options),
+            Self::Assign(figure) => figure
+                .assemble()
+                .write_svg_with_options(writer, render_options),
+        }
+    }
 }
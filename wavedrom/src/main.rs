@@ -3,11 +3,9 @@ use std::io::{stdin, stdout, BufWriter, Read, Write};
 use std::path::PathBuf;
 use std::path::Path;
 
-use usvg::TreeParsing;
-use usvg::TreeTextToPath;
-
 use wavedrom::signal::options::{RenderOptions, PathAssembleOptions};
 use wavedrom::skin::Skin;
+use wavedrom::wavejson::diagnostics::{Diagnostic, StrictError};
 use wavedrom::Figure;
 
 #[derive(Default)]
@@ -16,6 +14,9 @@ struct Flags {
     output: Option<PathBuf>,
     skin: Option<PathBuf>,
     png_scale: Option<String>,
+    cycle_time: Option<String>,
+    format: Option<String>,
+    strict: bool,
 }
 
 enum ParsingError {
@@ -68,11 +69,19 @@ Usage: wavedrom [FLAGS]
 Takes a wavejson file from the STDIN and outputs a SVG to the STDOUT.
 
 Flags:
--i/--input     <path/to/input.json>: specify a path to a input wavejson file.
--o/--output    <path/to/output.svg>: specify a path to a output svg or png file.
--s/--skin      <path/to/skin.json>: specify a path to a skin file.
+-i/--input     <path/to/input.json>: specify a path to a input wavejson file. Files
+               ending in .yaml, .yml or .toml are read as YAML or TOML.
+-o/--output    <path/to/output.svg>: specify a path to a output svg, png, pdf or txt file.
+-f/--format    <format>: the output format (svg, png, pdf, vcd or text). Defaults to the
+               file extension of the output path, or svg when writing to STDOUT.
+-s/--skin      <path/to/skin.json>: specify a path to a skin file or the name of a
+               built-in skin (default, narrow, lowkey or dark).
 -p/--png_scale floating-point value to adjust the resolution of the output png file.
                This flag is ignored when generating svg files. 
+-t/--cycle_time <time>: the duration of a cycle (e.g. 10ns) in output vcd files.
+               Defaults to the config.timescale of the figure or 1ns.
+--strict       treat unknown keys, unknown wave characters, invalid period and phase
+               values and malformed edges as errors. Only supported for JSON5 input.
         "
         .trim()
     }
@@ -120,6 +129,17 @@ Flags:
                         .into(),
                     );
                 }
+                "-t" | "--cycle_time" => {
+                    flags.cycle_time = Some(args.next().ok_or(ParsingError::MissingArgument(arg))?);
+                }
+                "-f" | "--format" => {
+                    flags.format = Some(
+                        args.next()
+                            .ok_or(ParsingError::MissingArgument(arg))?
+                            .to_lowercase(),
+                    );
+                }
+                "--strict" => flags.strict = true,
                 "-h" | "--help" => {
                     Self::print_metadata();
                     println!();
@@ -140,6 +160,43 @@ Flags:
 
 
 
+fn print_diagnostics(input_name: &str, diagnostics: &[Diagnostic]) {
+    for diagnostic in diagnostics {
+        eprintln!("{input_name}:{diagnostic}");
+    }
+}
+
+/// The format of an input or skin file, chosen from its file extension.
+enum InputFormat {
+    Json5,
+    #[cfg(feature = "yaml")]
+    Yaml,
+    #[cfg(feature = "toml")]
+    Toml,
+}
+
+impl InputFormat {
+    fn from_extension(extension: &str) -> Self {
+        match extension.to_lowercase().as_str() {
+            #[cfg(feature = "yaml")]
+            "yaml" | "yml" => Self::Yaml,
+            #[cfg(feature = "toml")]
+            "toml" => Self::Toml,
+            #[cfg(not(feature = "yaml"))]
+            "yaml" | "yml" => {
+                eprintln!("[ERROR]: This binary was built without YAML support. Enable the 'yaml' feature.");
+                std::process::exit(1);
+            }
+            #[cfg(not(feature = "toml"))]
+            "toml" => {
+                eprintln!("[ERROR]: This binary was built without TOML support. Enable the 'toml' feature.");
+                std::process::exit(1);
+            }
+            _ => Self::Json5,
+        }
+    }
+}
+
 fn get_file_extension(file_path: &str) -> Option<&str> {
     if let Some(extension) = Path::new(file_path).extension() {
         extension.to_str()
@@ -148,6 +205,21 @@ fn get_file_extension(file_path: &str) -> Option<&str> {
     }
 }
 
+fn render_svg(
+    figure: &Figure,
+    assemble_options: PathAssembleOptions,
+    render_options: &RenderOptions,
+) -> Vec<u8> {
+    let mut svg_data = Vec::new();
+    match figure.write_svg_with_options(&mut svg_data, assemble_options, render_options) {
+        Ok(r) => r,
+        Err(e) => {
+            eprintln!("[ERROR]: Failed to assemble figure. Reason: {e}");
+        }
+    }
+    svg_data
+}
+
 fn export_svg_file(output_path:&PathBuf, svg_data:&[u8]) -> std::result::Result<(), std::io::Error>{
     let output_file = match std::fs::OpenOptions::new()
                             .write(true)
@@ -162,7 +234,7 @@ fn export_svg_file(output_path:&PathBuf, svg_data:&[u8]) -> std::result::Result<
                             }
                         };
                         let mut writer = BufWriter::new(output_file);
-                        match writer.write(svg_data) {
+                        match writer.write_all(svg_data) {
                             Ok(_s) => {
                                 Ok(())
                             },
@@ -173,37 +245,94 @@ fn export_svg_file(output_path:&PathBuf, svg_data:&[u8]) -> std::result::Result<
                         }
 }
 
-fn export_png_file(flags: &Flags, output_path:&PathBuf, svg_data:&[u8]) -> std::result::Result<(), std::io::Error>{
-    let opt = usvg::Options::default();
-    let mut utree = usvg::Tree::from_data(&svg_data, &opt).unwrap();
-    let mut fontdb = usvg::fontdb::Database::new();
-    fontdb.load_system_fonts();
-    utree.convert_text(&fontdb);
-    let rtree = resvg::Tree::from_usvg(&utree);
-    let size = rtree.size.to_int_size();
-
+#[cfg(feature = "raster")]
+fn export_png_file(
+    flags: &Flags,
+    output_path: &PathBuf,
+    figure: &Figure,
+    assemble_options: PathAssembleOptions,
+    render_options: &RenderOptions,
+) -> std::result::Result<(), std::io::Error> {
     let scale = match &flags.png_scale {
         None => 1.0,
-        Some(scale) => {
-            match scale.parse::<f32>() {
-                Err(e) => {
-                    eprintln!("[ERROR]: Error parsing png_scale: {e:?}.");
-                    std::process::exit(1)
-                }
-                Ok(parsed_scale) => parsed_scale
+        Some(scale) => match scale.parse::<f32>() {
+            Err(e) => {
+                eprintln!("[ERROR]: Error parsing png_scale: {e:?}.");
+                std::process::exit(1)
             }
-        }
+            Ok(parsed_scale) => parsed_scale,
+        },
     };
 
-    let mut pixmap = resvg::tiny_skia::Pixmap::new((size.width() as f32 * scale) as u32, (size.height() as f32 * scale) as u32).unwrap();
-    rtree.render(usvg::Transform::from_scale(scale, scale), &mut pixmap.as_mut());
+    let mut png = Vec::new();
+    figure.write_png_with_options(&mut png, scale, assemble_options, render_options)?;
+    std::fs::write(output_path, png)
+}
 
-    match pixmap.save_png(&output_path) {
-        Err(e) => { 
-            eprintln!("[ERROR]: Error Encoding png: {e:?}.");
-            std::process::exit(1)
+#[cfg(feature = "pdf")]
+fn export_pdf_file(
+    output_path: &PathBuf,
+    figure: &Figure,
+    assemble_options: PathAssembleOptions,
+    render_options: &RenderOptions,
+) -> std::result::Result<(), std::io::Error> {
+    let output_file = std::fs::File::create(output_path)?;
+    let mut writer = BufWriter::new(output_file);
+    figure.write_pdf_with_options(&mut writer, assemble_options, render_options)?;
+    writer.flush()
+}
+
+#[cfg(feature = "vcd")]
+fn export_vcd_file(
+    flags: &Flags,
+    output_path: &PathBuf,
+    figure: &Figure,
+    assemble_options: PathAssembleOptions,
+) -> std::result::Result<(), std::io::Error> {
+    let Figure::Signal(figure) = figure else {
+        eprintln!("[ERROR]: Only signal figures can be written as vcd files.");
+        std::process::exit(1)
+    };
+
+    let mut options = wavedrom::vcd::VcdExportOptions::new();
+    if let Some(cycle_time) = &flags.cycle_time {
+        match cycle_time.parse() {
+            Ok(cycle_time) => options = options.cycle_time(cycle_time),
+            Err(_) => {
+                eprintln!(
+                    "[ERROR]: Invalid cycle_time '{cycle_time}'. Expected a time such as 10ns."
+                );
+                std::process::exit(1)
+            }
         }
-        Ok(()) => Ok(())
+    }
+
+    let output_file = std::fs::File::create(output_path)?;
+    let mut writer = BufWriter::new(output_file);
+    figure
+        .assemble_with_options(assemble_options)
+        .write_vcd(&mut writer, &options)?;
+    writer.flush()
+}
+
+fn export_text_file(
+    output_path: Option<&PathBuf>,
+    figure: &Figure,
+    assemble_options: PathAssembleOptions,
+) -> std::result::Result<(), std::io::Error> {
+    let Figure::Signal(figure) = figure else {
+        eprintln!("[ERROR]: Only signal figures can be written as text.");
+        std::process::exit(1)
+    };
+
+    let mut text = Vec::new();
+    figure
+        .assemble_with_options(assemble_options)
+        .write_text(&mut text)?;
+
+    match output_path {
+        None => stdout().lock().write_all(&text),
+        Some(output_path) => std::fs::write(output_path, text),
     }
 }
 
@@ -244,9 +373,18 @@ fn main() {
         },
     };
 
-    let (assemble_options, render_options) = match flags.skin {
-        None => (PathAssembleOptions::default(), RenderOptions::default()),
-        Some(ref skin_path) => {
+    // A skin that is not an existing file may be the name of a built-in skin.
+    let builtin_skin = flags
+        .skin
+        .as_deref()
+        .filter(|skin_path| !skin_path.exists())
+        .and_then(Path::to_str)
+        .and_then(Skin::named);
+
+    let (assemble_options, render_options) = match (builtin_skin, &flags.skin) {
+        (Some(skin), _) => skin.options(),
+        (None, None) => (PathAssembleOptions::default(), RenderOptions::default()),
+        (None, Some(skin_path)) => {
             let skin = match std::fs::read_to_string(skin_path) {
                 Ok(content) => content,
                 Err(err) => {
@@ -255,7 +393,20 @@ fn main() {
                 }
             };
 
-            match Skin::from_json5(&skin) {
+            let skin_format = skin_path
+                .to_str()
+                .and_then(get_file_extension)
+                .map_or(InputFormat::Json5, InputFormat::from_extension);
+
+            let skin = match skin_format {
+                InputFormat::Json5 => Skin::from_json5(&skin).map_err(|err| err.to_string()),
+                #[cfg(feature = "yaml")]
+                InputFormat::Yaml => Skin::from_yaml(&skin).map_err(|err| err.to_string()),
+                #[cfg(feature = "toml")]
+                InputFormat::Toml => Skin::from_toml(&skin).map_err(|err| err.to_string()),
+            };
+
+            match skin {
                 Ok(skin) => skin.options(),
                 Err(err) => {
                     eprintln!("[ERROR]: Failed to parse skin content. Reason: {err}");
@@ -265,30 +416,104 @@ fn main() {
         }
     };
 
-    let figure = match Figure::from_json5(&content) {
-        Ok(figure) => figure,
-        Err(err) => {
+    let input_name = flags
+        .input
+        .as_ref()
+        .map_or_else(|| "<stdin>".into(), |path| path.display().to_string());
+
+    let input_format = flags
+        .input
+        .as_ref()
+        .and_then(|path| path.to_str())
+        .and_then(get_file_extension)
+        .map_or(InputFormat::Json5, InputFormat::from_extension);
+
+    // The diagnostics need the locations in the source, which are only known for JSON5.
+    if flags.strict && !matches!(input_format, InputFormat::Json5) {
+        eprintln!("[ERROR]: The --strict flag is only supported for JSON5 input.");
+        std::process::exit(1);
+    }
+
+    let result = match input_format {
+        InputFormat::Json5 if flags.strict => {
+            Figure::from_json5_strict(&content).map_err(|err| match err {
+                StrictError::Parse(err) => StrictError::Parse(err.to_string()),
+                StrictError::Invalid(diagnostics) => StrictError::Invalid(diagnostics),
+            })
+        }
+        InputFormat::Json5 => Figure::from_json5_with_diagnostics(&content)
+            .map_err(|err| StrictError::Parse(err.to_string())),
+        #[cfg(feature = "yaml")]
+        InputFormat::Yaml => Figure::from_yaml(&content)
+            .map(|figure| (figure, Vec::new()))
+            .map_err(|err| StrictError::Parse(err.to_string())),
+        #[cfg(feature = "toml")]
+        InputFormat::Toml => Figure::from_toml(&content)
+            .map(|figure| (figure, Vec::new()))
+            .map_err(|err| StrictError::Parse(err.to_string())),
+    };
+
+    let figure = match result {
+        Ok((figure, diagnostics)) => {
+            print_diagnostics(&input_name, &diagnostics);
+            figure
+        }
+        Err(StrictError::Invalid(diagnostics)) => {
+            print_diagnostics(&input_name, &diagnostics);
+            std::process::exit(1);
+        }
+        Err(StrictError::Parse(err)) => {
             eprintln!("[ERROR]: Failed to parse content of file. Reason:");
             eprintln!("{err}");
             std::process::exit(1);
         }
     };
 
-    let Figure::Signal(figure) = figure;
-    let assembled = figure.assemble_with_options(assemble_options);
+    // The format flag takes precedence over the extension of the output path.
+    let output_format = flags.format.clone().or_else(|| {
+        flags
+            .output
+            .as_ref()
+            .and_then(|path| path.to_str())
+            .and_then(get_file_extension)
+            .map(|extension| match extension.to_lowercase().as_str() {
+                "txt" => "text".to_string(),
+                extension => extension.to_string(),
+            })
+    });
 
-    let mut svg_data = BufWriter::new(Vec::new());
-    match assembled.write_svg_with_options(&mut svg_data, &render_options) {
-        Ok(r) => r,
-        Err(e) => {
-            eprintln!("[ERROR]: Failed to assemble figure. Reason: {e}");
+    if output_format.as_deref() == Some("text") {
+        let result = export_text_file(flags.output.as_ref(), &figure, assemble_options);
+        if let Err(err) = result {
+            eprintln!("[ERROR]: Failed to write output. Reason: {err}");
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    #[cfg(feature = "vcd")]
+    if let Some(output_path) = flags
+        .output
+        .as_ref()
+        .filter(|_| output_format.as_deref() == Some("vcd"))
+    {
+        let result = export_vcd_file(&flags, output_path, &figure, assemble_options);
+        if let Err(err) = result {
+            eprintln!("[ERROR]: Failed to write output. Reason: {err}");
+            std::process::exit(1);
         }
+        return;
     }
-    
+
     let result = match flags.output {
+        None if output_format.as_deref().is_some_and(|format| format != "svg") => {
+            eprintln!("[ERROR]: Only svg and text can be written to STDOUT. Specify an output file with -o.");
+            std::process::exit(1)
+        }
         None => {
+            let svg_data = render_svg(&figure, assemble_options, &render_options);
             let mut writer = BufWriter::new(stdout().lock());
-            match writer.write(svg_data.buffer()) {
+            match writer.write_all(&svg_data) {
                 Ok(_s) => {
                     Ok(())
                 },
@@ -299,14 +524,39 @@ fn main() {
             }
         }
         Some(ref output_path) => {
-            if let Some(extension) = get_file_extension(&output_path.as_os_str().to_str().unwrap()) {
-                match extension.to_lowercase().as_str() {
+            if let Some(extension) = output_format {
+                match extension.as_str() {
                     "svg" => {
-                        export_svg_file(output_path, &svg_data.buffer())
+                        let svg_data = render_svg(&figure, assemble_options, &render_options);
+                        export_svg_file(output_path, &svg_data)
                     },
+                    #[cfg(feature = "raster")]
                     "png" => {
-                        export_png_file(&flags, output_path, &svg_data.buffer())
+                        export_png_file(&flags, output_path, &figure, assemble_options, &render_options)
                     },
+                    #[cfg(not(feature = "raster"))]
+                    "png" => {
+                        eprintln!("[ERROR]: This binary was built without PNG support. Enable the 'raster' feature.");
+                        std::process::exit(1)
+                    }
+                    #[cfg(feature = "pdf")]
+                    "pdf" => {
+                        export_pdf_file(output_path, &figure, assemble_options, &render_options)
+                    },
+                    #[cfg(not(feature = "pdf"))]
+                    "pdf" => {
+                        eprintln!("[ERROR]: This binary was built without PDF support. Enable the 'pdf' feature.");
+                        std::process::exit(1)
+                    }
+                    #[cfg(not(feature = "vcd"))]
+                    "vcd" => {
+                        eprintln!("[ERROR]: This binary was built without VCD support. Enable the 'vcd' feature.");
+                        std::process::exit(1)
+                    }
+                    _ if flags.format.is_some() => {
+                        eprintln!("[ERROR]: Unsupported output format '{extension}'.");
+                        std::process::exit(1)
+                    }
                     _ => {
                         eprintln!("[ERROR]: Unsupported file extension in output path.");
                         std::process::exit(1)
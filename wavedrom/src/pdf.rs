@@ -0,0 +1,861 @@
+//! Vector PDF output of figures
+//!
+//! The figures are first rendered to SVG, which is then translated into the drawing operators of a
+//! single page PDF document. The page has the size of the SVG output, where one SVG unit is one
+//! point, so the geometry is the same as in the SVG output. The text is drawn with the Helvetica
+//! font that is embedded in the library, which is also embedded into the document.
+//!
+//! Only the parts of SVG that are used by the figures are translated. These are paths with a color
+//! or pattern fill and stroke, transformations, opacities and text.
+//!
+//! # Examples
+//!
+//! ```
+//! use wavedrom::signal::{Signal, SignalFigure};
+//!
+//! let figure = SignalFigure::new().add_signal(Signal::with_cycle_str("p...").name("clk"));
+//!
+//! let mut pdf = Vec::new();
+//! figure.assemble().write_pdf(&mut pdf)?;
+//! # <Result<(), std::io::Error>>::Ok(())
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt::{self, Display};
+use std::io::{self, Write};
+use std::rc::Rc;
+
+use usvg::tiny_skia_path::{self, PathSegment};
+use usvg::{NodeKind, Paint, Transform, TreeParsing};
+
+use crate::font::metrics::{
+    DominantBaseline, TextDecoration, TextMetrics, BOLD_STROKE_WIDTH, ITALIC_SKEW,
+};
+use crate::font::HELVETICA_TTF;
+use crate::signal::options::{PathAssembleOptions, RenderOptions};
+use crate::signal::AssembledFigure;
+use crate::{Figure, Font};
+
+/// The name of the embedded font in the PDF document.
+const FONT_NAME: &str = "Helvetica";
+
+impl AssembledFigure<'_> {
+    /// Render a [`AssembledFigure`] as a PDF document into a `writer`.
+    #[inline]
+    pub fn write_pdf(&self, writer: &mut impl io::Write) -> io::Result<()> {
+        self.write_pdf_with_options(writer, &RenderOptions::default())
+    }
+
+    /// Render a [`AssembledFigure`] as a PDF document into a `writer` with a set of options.
+    pub fn write_pdf_with_options(
+        &self,
+        writer: &mut impl io::Write,
+        options: &RenderOptions,
+    ) -> io::Result<()> {
+        let mut svg = Vec::new();
+        self.write_svg_with_options(&mut svg, options)?;
+        write_svg_as_pdf(&svg, writer)
+    }
+}
+
+impl Figure {
+    /// Assemble and render a [`Figure`] as a PDF document into a `writer`.
+    pub fn write_pdf_with_options(
+        &self,
+        writer: &mut impl io::Write,
+        assemble_options: PathAssembleOptions,
+        render_options: &RenderOptions,
+    ) -> io::Result<()> {
+        let mut svg = Vec::new();
+        self.write_svg_with_options(&mut svg, assemble_options, render_options)?;
+        write_svg_as_pdf(&svg, writer)
+    }
+}
+
+/// A number that is written with at most three decimals.
+struct Num(f32);
+
+impl Display for Num {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = (self.0 * 1000.).round() / 1000.;
+
+        if value == 0. {
+            write!(f, "0")
+        } else {
+            write!(f, "{value}")
+        }
+    }
+}
+
+/// A transformation matrix in the `a b c d e f` form of PDF.
+struct Matrix(Transform);
+
+impl Display for Matrix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Transform {
+            sx,
+            ky,
+            kx,
+            sy,
+            tx,
+            ty,
+        } = self.0;
+
+        write!(
+            f,
+            "{} {} {} {} {} {}",
+            Num(sx),
+            Num(ky),
+            Num(kx),
+            Num(sy),
+            Num(tx),
+            Num(ty)
+        )
+    }
+}
+
+/// The resources that are used by the content streams of a PDF document.
+struct Resources<'a> {
+    face: &'a ttf_parser::Face<'static>,
+    /// The glyphs that are used with the character they represent
+    glyphs: BTreeMap<u16, char>,
+    /// The patterns that are used with the matrix that maps them onto the page
+    patterns: Vec<(Rc<usvg::Pattern>, Transform)>,
+    /// The fill (`ca`) and stroke (`CA`) opacities that are used
+    opacities: Vec<(&'static str, f32)>,
+}
+
+impl<'a> Resources<'a> {
+    fn new(face: &'a ttf_parser::Face<'static>) -> Self {
+        Self {
+            face,
+            glyphs: BTreeMap::new(),
+            patterns: Vec::new(),
+            opacities: Vec::new(),
+        }
+    }
+
+    fn write_nodes(
+        &mut self,
+        content: &mut Vec<u8>,
+        parent: &usvg::Node,
+        ctm: Transform,
+    ) -> io::Result<()> {
+        for node in parent.children() {
+            match &*node.borrow() {
+                NodeKind::Group(group) => {
+                    if group.transform.is_identity() {
+                        self.write_nodes(content, &node, ctm)?;
+                    } else {
+                        writeln!(content, "q {} cm", Matrix(group.transform))?;
+                        self.write_nodes(content, &node, ctm.pre_concat(group.transform))?;
+                        writeln!(content, "Q")?;
+                    }
+                }
+                NodeKind::Path(path) => self.write_path(content, path, ctm)?,
+                NodeKind::Text(text) => self.write_text(content, text)?,
+                NodeKind::Image(_) => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Set the current fill or stroke paint. Returns `false` if the paint is not supported.
+    fn write_paint(
+        &mut self,
+        content: &mut Vec<u8>,
+        paint: &Paint,
+        opacity: usvg::Opacity,
+        is_stroke: bool,
+        ctm: Transform,
+    ) -> io::Result<bool> {
+        match paint {
+            Paint::Color(color) => writeln!(
+                content,
+                "{} {} {} {}",
+                Num(f32::from(color.red) / 255.),
+                Num(f32::from(color.green) / 255.),
+                Num(f32::from(color.blue) / 255.),
+                if is_stroke { "RG" } else { "rg" },
+            )?,
+            Paint::Pattern(pattern) => {
+                let index = self.patterns.len();
+                self.patterns
+                    .push((pattern.clone(), ctm.pre_concat(pattern.transform)));
+                if is_stroke {
+                    writeln!(content, "/Pattern CS /P{index} SCN")?;
+                } else {
+                    writeln!(content, "/Pattern cs /P{index} scn")?;
+                }
+            }
+            Paint::LinearGradient(_) | Paint::RadialGradient(_) => return Ok(false),
+        }
+
+        let opacity = opacity.get();
+        if opacity < 1. {
+            let key = if is_stroke { "CA" } else { "ca" };
+            let index = self
+                .opacities
+                .iter()
+                .position(|entry| *entry == (key, opacity))
+                .unwrap_or_else(|| {
+                    self.opacities.push((key, opacity));
+                    self.opacities.len() - 1
+                });
+            writeln!(content, "/G{index} gs")?;
+        }
+
+        Ok(true)
+    }
+
+    fn write_path(
+        &mut self,
+        content: &mut Vec<u8>,
+        path: &usvg::Path,
+        ctm: Transform,
+    ) -> io::Result<()> {
+        if path.visibility != usvg::Visibility::Visible {
+            return Ok(());
+        }
+
+        let start = content.len();
+        writeln!(content, "q")?;
+
+        let fill_rule = match &path.fill {
+            Some(fill) if self.write_paint(content, &fill.paint, fill.opacity, false, ctm)? => {
+                Some(fill.rule)
+            }
+            _ => None,
+        };
+
+        let has_stroke = match &path.stroke {
+            Some(stroke)
+                if self.write_paint(content, &stroke.paint, stroke.opacity, true, ctm)? =>
+            {
+                write_stroke_style(content, stroke)?;
+                true
+            }
+            _ => false,
+        };
+
+        let operator = match (fill_rule, has_stroke) {
+            (None, false) => {
+                content.truncate(start);
+                return Ok(());
+            }
+            (None, true) => "S",
+            (Some(usvg::FillRule::NonZero), false) => "f",
+            (Some(usvg::FillRule::EvenOdd), false) => "f*",
+            (Some(usvg::FillRule::NonZero), true) => "B",
+            (Some(usvg::FillRule::EvenOdd), true) => "B*",
+        };
+
+        write_path_data(content, &path.data)?;
+        writeln!(content, "{operator}\nQ")
+    }
+
+    fn glyph(&self, c: char) -> (u16, f32) {
+        let glyph = self.face.glyph_index(c).unwrap_or(ttf_parser::GlyphId(0));
+        let advance = self.face.glyph_hor_advance(glyph).unwrap_or(0);
+
+        (glyph.0, f32::from(advance))
+    }
+
+    /// The width of a piece of text in a span.
+    fn span_width(&self, text: &str, span: &usvg::TextSpan) -> f32 {
+        let scale = span.font_size.get() / f32::from(self.face.units_per_em());
+
+        text.chars()
+            .map(|c| self.glyph(c).1 * scale + span.letter_spacing)
+            .sum()
+    }
+
+    /// The offset of the baseline of a span with regards to the `y` position of its chunk.
+    fn baseline_offset(&self, span: &usvg::TextSpan) -> f32 {
+        let metrics = TextMetrics::new(self.face, span.font_size.get());
+
+        let dominant_baseline = metrics.dominant_baseline(match span.dominant_baseline {
+            usvg::DominantBaseline::Middle => DominantBaseline::Middle,
+            usvg::DominantBaseline::Central => DominantBaseline::Central,
+            usvg::DominantBaseline::Hanging | usvg::DominantBaseline::TextBeforeEdge => {
+                DominantBaseline::Hanging
+            }
+            usvg::DominantBaseline::TextAfterEdge | usvg::DominantBaseline::Ideographic => {
+                DominantBaseline::TextAfterEdge
+            }
+            _ => DominantBaseline::Alphabetic,
+        });
+
+        let baseline_shift = span
+            .baseline_shift
+            .iter()
+            .map(|shift| match shift {
+                usvg::BaselineShift::Baseline => 0.,
+                usvg::BaselineShift::Subscript => {
+                    metrics.baseline_shift(crate::text::BaselineShift::Subscript)
+                }
+                usvg::BaselineShift::Superscript => {
+                    metrics.baseline_shift(crate::text::BaselineShift::Superscript)
+                }
+                usvg::BaselineShift::Number(shift) => *shift,
+            })
+            .sum::<f32>();
+
+        dominant_baseline - baseline_shift
+    }
+
+    fn write_text(&mut self, content: &mut Vec<u8>, text: &usvg::Text) -> io::Result<()> {
+        let (mut x, mut y) = (0., 0.);
+
+        for chunk in &text.chunks {
+            x = chunk.x.unwrap_or(x);
+            y = chunk.y.unwrap_or(y);
+
+            let width = chunk
+                .spans
+                .iter()
+                .map(|span| self.span_width(&chunk.text[span.start..span.end], span))
+                .sum::<f32>();
+            x -= match chunk.anchor {
+                usvg::TextAnchor::Start => 0.,
+                usvg::TextAnchor::Middle => width / 2.,
+                usvg::TextAnchor::End => width,
+            };
+
+            for span in &chunk.spans {
+                let text = &chunk.text[span.start..span.end];
+                let width = self.span_width(text, span);
+                let baseline = y + self.baseline_offset(span);
+
+                self.write_span(content, text, span, (x, baseline), width)?;
+                x += width;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn write_span(
+        &mut self,
+        content: &mut Vec<u8>,
+        text: &str,
+        span: &usvg::TextSpan,
+        (x, y): (f32, f32),
+        width: f32,
+    ) -> io::Result<()> {
+        if span.visibility != usvg::Visibility::Visible || text.is_empty() {
+            return Ok(());
+        }
+
+        let Some(usvg::Fill {
+            paint: Paint::Color(color),
+            ..
+        }) = &span.fill
+        else {
+            return Ok(());
+        };
+
+        let font_size = span.font_size.get();
+        let color = format!(
+            "{} {} {}",
+            Num(f32::from(color.red) / 255.),
+            Num(f32::from(color.green) / 255.),
+            Num(f32::from(color.blue) / 255.),
+        );
+
+        writeln!(content, "q {color} rg")?;
+        writeln!(content, "BT /F0 {} Tf", Num(font_size))?;
+        if span.font.weight >= 600 {
+            writeln!(
+                content,
+                "2 Tr {color} RG {} w",
+                Num(font_size * BOLD_STROKE_WIDTH)
+            )?;
+        }
+        if span.letter_spacing != 0. {
+            writeln!(content, "{} Tc", Num(span.letter_spacing))?;
+        }
+
+        let skew = if span.font.style == usvg::FontStyle::Normal {
+            0.
+        } else {
+            ITALIC_SKEW
+        };
+        // The text matrix flips the glyphs back up, because the y-axis of the page is flipped.
+        write!(content, "1 0 {} -1 {} {} Tm <", Num(skew), Num(x), Num(y))?;
+        for c in text.chars() {
+            let (glyph, _) = self.glyph(c);
+            self.glyphs.entry(glyph).or_insert(c);
+            write!(content, "{glyph:04X}")?;
+        }
+        writeln!(content, "> Tj ET")?;
+
+        // Text Decorations
+        let metrics = TextMetrics::new(self.face, font_size);
+        let decorations = [
+            (&span.decoration.underline, TextDecoration::Underline),
+            (&span.decoration.overline, TextDecoration::Overline),
+            (&span.decoration.line_through, TextDecoration::LineThrough),
+        ];
+        for (decoration, line) in decorations {
+            if decoration.is_some() {
+                let (offset, thickness) = metrics.decoration(line);
+                writeln!(
+                    content,
+                    "{} {} {} {} re f",
+                    Num(x),
+                    Num(y + offset),
+                    Num(width),
+                    Num(thickness)
+                )?;
+            }
+        }
+
+        writeln!(content, "Q")
+    }
+}
+
+fn write_stroke_style(content: &mut Vec<u8>, stroke: &usvg::Stroke) -> io::Result<()> {
+    let line_cap = match stroke.linecap {
+        usvg::LineCap::Butt => 0,
+        usvg::LineCap::Round => 1,
+        usvg::LineCap::Square => 2,
+    };
+    let line_join = match stroke.linejoin {
+        usvg::LineJoin::Miter | usvg::LineJoin::MiterClip => 0,
+        usvg::LineJoin::Round => 1,
+        usvg::LineJoin::Bevel => 2,
+    };
+
+    write!(
+        content,
+        "{} w {line_cap} J {line_join} j {} M",
+        Num(stroke.width.get()),
+        Num(stroke.miterlimit.get()),
+    )?;
+
+    if let Some(dasharray) = &stroke.dasharray {
+        write!(content, " [")?;
+        for (i, dash) in dasharray.iter().enumerate() {
+            if i != 0 {
+                write!(content, " ")?;
+            }
+            write!(content, "{}", Num(*dash))?;
+        }
+        write!(content, "] {} d", Num(stroke.dashoffset))?;
+    }
+
+    writeln!(content)
+}
+
+fn write_path_data(content: &mut Vec<u8>, path: &tiny_skia_path::Path) -> io::Result<()> {
+    let mut start = (0., 0.);
+    let mut last = (0., 0.);
+
+    for segment in path.segments() {
+        match segment {
+            PathSegment::MoveTo(p) => {
+                writeln!(content, "{} {} m", Num(p.x), Num(p.y))?;
+                start = (p.x, p.y);
+                last = start;
+            }
+            PathSegment::LineTo(p) => {
+                writeln!(content, "{} {} l", Num(p.x), Num(p.y))?;
+                last = (p.x, p.y);
+            }
+            PathSegment::QuadTo(c, p) => {
+                // PDF only has cubic curves, so the quadratic curve is elevated.
+                let c1 = (
+                    last.0 + 2. / 3. * (c.x - last.0),
+                    last.1 + 2. / 3. * (c.y - last.1),
+                );
+                let c2 = (p.x + 2. / 3. * (c.x - p.x), p.y + 2. / 3. * (c.y - p.y));
+                writeln!(
+                    content,
+                    "{} {} {} {} {} {} c",
+                    Num(c1.0),
+                    Num(c1.1),
+                    Num(c2.0),
+                    Num(c2.1),
+                    Num(p.x),
+                    Num(p.y)
+                )?;
+                last = (p.x, p.y);
+            }
+            PathSegment::CubicTo(c1, c2, p) => {
+                writeln!(
+                    content,
+                    "{} {} {} {} {} {} c",
+                    Num(c1.x),
+                    Num(c1.y),
+                    Num(c2.x),
+                    Num(c2.y),
+                    Num(p.x),
+                    Num(p.y)
+                )?;
+                last = (p.x, p.y);
+            }
+            PathSegment::Close => {
+                writeln!(content, "h")?;
+                last = start;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// The objects of a PDF document with their byte offsets.
+struct PdfObjects {
+    buffer: Vec<u8>,
+    offsets: Vec<usize>,
+}
+
+impl PdfObjects {
+    fn new() -> Self {
+        let mut buffer = Vec::new();
+        // The binary comment marks the file as binary for transfer programs.
+        buffer.extend_from_slice(b"%PDF-1.7\n%\xE2\xE3\xCF\xD3\n");
+
+        Self {
+            buffer,
+            offsets: Vec::new(),
+        }
+    }
+
+    /// Write the object with the next id, which is one more than the id of the previous object.
+    fn object(&mut self, body: impl Display) -> io::Result<()> {
+        self.offsets.push(self.buffer.len());
+        let id = self.offsets.len();
+        write!(self.buffer, "{id} 0 obj\n{body}\nendobj\n")
+    }
+
+    fn stream(&mut self, dictionary: impl Display, data: &[u8]) -> io::Result<()> {
+        self.offsets.push(self.buffer.len());
+        let id = self.offsets.len();
+        let dictionary = dictionary.to_string();
+        let separator = if dictionary.is_empty() { "" } else { " " };
+        write!(
+            self.buffer,
+            "{id} 0 obj\n<< {dictionary}{separator}/Length {length} >>\nstream\n",
+            length = data.len()
+        )?;
+        self.buffer.extend_from_slice(data);
+        write!(self.buffer, "\nendstream\nendobj\n")
+    }
+
+    fn finish(mut self, writer: &mut impl io::Write) -> io::Result<()> {
+        let xref_offset = self.buffer.len();
+        let size = self.offsets.len() + 1;
+
+        write!(self.buffer, "xref\n0 {size}\n0000000000 65535 f \n")?;
+        for offset in &self.offsets {
+            writeln!(self.buffer, "{offset:010} 00000 n ")?;
+        }
+        write!(
+            self.buffer,
+            "trailer\n<< /Size {size} /Root 1 0 R >>\nstartxref\n{xref_offset}\n%%EOF\n"
+        )?;
+
+        writer.write_all(&self.buffer)
+    }
+}
+
+fn write_svg_as_pdf(svg: &[u8], writer: &mut impl io::Write) -> io::Result<()> {
+    let tree = usvg::Tree::from_data(svg, &usvg::Options::default())
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+
+    let width = tree.size.width();
+    let height = tree.size.height();
+
+    let font = Font::default();
+    let face = font.get_face();
+    let mut resources = Resources::new(face);
+
+    // PDF coordinates start at the bottom left, so the y-axis is flipped.
+    let page_transform = Transform::from_row(1., 0., 0., -1., 0., height);
+    let mut content = Vec::new();
+    writeln!(content, "{} cm", Matrix(page_transform))?;
+    resources.write_nodes(&mut content, &tree.root, page_transform)?;
+
+    // The contents of the patterns can use patterns themselves.
+    let mut pattern_contents = Vec::new();
+    while pattern_contents.len() < resources.patterns.len() {
+        let (pattern, _) = resources.patterns[pattern_contents.len()].clone();
+        let mut pattern_content = Vec::new();
+        resources.write_nodes(&mut pattern_content, &pattern.root, Transform::identity())?;
+        pattern_contents.push(pattern_content);
+    }
+
+    let num_patterns = resources.patterns.len();
+    let font_id = 6 + num_patterns;
+
+    let mut objects = PdfObjects::new();
+
+    // 1: Catalog, 2: Page Tree, 3: Page, 4: Page Content, 5: Resources
+    objects.object("<< /Type /Catalog /Pages 2 0 R >>")?;
+    objects.object("<< /Type /Pages /Kids [3 0 R] /Count 1 >>")?;
+    objects.object(format!(
+        "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] /Resources 5 0 R /Contents 4 0 R >>",
+        Num(width),
+        Num(height)
+    ))?;
+    objects.stream("", &content)?;
+
+    let mut dictionary = String::from("<<");
+    if !resources.glyphs.is_empty() {
+        dictionary.push_str(&format!(" /Font << /F0 {font_id} 0 R >>"));
+    }
+    if num_patterns != 0 {
+        dictionary.push_str(" /Pattern <<");
+        for i in 0..num_patterns {
+            dictionary.push_str(&format!(" /P{i} {} 0 R", 6 + i));
+        }
+        dictionary.push_str(" >>");
+    }
+    if !resources.opacities.is_empty() {
+        dictionary.push_str(" /ExtGState <<");
+        for (i, (key, opacity)) in resources.opacities.iter().enumerate() {
+            dictionary.push_str(&format!(
+                " /G{i} << /Type /ExtGState /{key} {} >>",
+                Num(*opacity)
+            ));
+        }
+        dictionary.push_str(" >>");
+    }
+    dictionary.push_str(" >>");
+    objects.object(dictionary)?;
+
+    // 6..: Patterns
+    for ((pattern, matrix), pattern_content) in resources.patterns.iter().zip(pattern_contents) {
+        let rect = pattern.rect;
+        objects.stream(
+            format!(
+                "/Type /Pattern /PatternType 1 /PaintType 1 /TilingType 1 /BBox [{} {} {} {}] /XStep {} /YStep {} /Matrix [{}] /Resources 5 0 R",
+                Num(rect.x()),
+                Num(rect.y()),
+                Num(rect.right()),
+                Num(rect.bottom()),
+                Num(rect.width()),
+                Num(rect.height()),
+                Matrix(*matrix),
+            ),
+            &pattern_content,
+        )?;
+    }
+
+    if !resources.glyphs.is_empty() {
+        write_font(&mut objects, face, &resources.glyphs, font_id)?;
+    }
+
+    objects.finish(writer)
+}
+
+/// Write the embedded font as a composite font that addresses the glyphs by their id.
+fn write_font(
+    objects: &mut PdfObjects,
+    face: &ttf_parser::Face<'static>,
+    glyphs: &BTreeMap<u16, char>,
+    font_id: usize,
+) -> io::Result<()> {
+    let units_per_em = f32::from(face.units_per_em());
+    let to_pdf_units = |value: f32| Num(value * 1000. / units_per_em);
+
+    objects.object(format!(
+        "<< /Type /Font /Subtype /Type0 /BaseFont /{FONT_NAME} /Encoding /Identity-H /DescendantFonts [{} 0 R] /ToUnicode {} 0 R >>",
+        font_id + 1,
+        font_id + 4,
+    ))?;
+
+    let mut widths = String::new();
+    for &glyph in glyphs.keys() {
+        let advance = face
+            .glyph_hor_advance(ttf_parser::GlyphId(glyph))
+            .unwrap_or(0);
+        widths.push_str(&format!(" {glyph} [{}]", to_pdf_units(f32::from(advance))));
+    }
+    objects.object(format!(
+        "<< /Type /Font /Subtype /CIDFontType2 /BaseFont /{FONT_NAME} /CIDSystemInfo << /Registry (Adobe) /Ordering (Identity) /Supplement 0 >> /FontDescriptor {} 0 R /CIDToGIDMap /Identity /W [{widths} ] >>",
+        font_id + 2,
+    ))?;
+
+    let bbox = face.global_bounding_box();
+    objects.object(format!(
+        "<< /Type /FontDescriptor /FontName /{FONT_NAME} /Flags 32 /FontBBox [{} {} {} {}] /ItalicAngle 0 /Ascent {} /Descent {} /CapHeight {} /StemV 80 /FontFile2 {} 0 R >>",
+        to_pdf_units(f32::from(bbox.x_min)),
+        to_pdf_units(f32::from(bbox.y_min)),
+        to_pdf_units(f32::from(bbox.x_max)),
+        to_pdf_units(f32::from(bbox.y_max)),
+        to_pdf_units(f32::from(face.ascender())),
+        to_pdf_units(f32::from(face.descender())),
+        to_pdf_units(f32::from(face.capital_height().unwrap_or(face.ascender()))),
+        font_id + 3,
+    ))?;
+
+    objects.stream(format!("/Length1 {}", HELVETICA_TTF.len()), HELVETICA_TTF)?;
+
+    // The mapping back to unicode allows text to be searched and copied.
+    let mut cmap = String::from(
+        "/CIDInit /ProcSet findresource begin\n12 dict begin\nbegincmap\n/CIDSystemInfo << /Registry (Adobe) /Ordering (UCS) /Supplement 0 >> def\n/CMapName /Adobe-Identity-UCS def\n/CMapType 2 def\n1 begincodespacerange\n<0000> <FFFF>\nendcodespacerange\n",
+    );
+    let glyphs = glyphs.iter().collect::<Vec<_>>();
+    for block in glyphs.chunks(100) {
+        cmap.push_str(&format!("{} beginbfchar\n", block.len()));
+        for (glyph, c) in block {
+            cmap.push_str(&format!("<{glyph:04X}> <"));
+            for unit in c.encode_utf16(&mut [0; 2]) {
+                cmap.push_str(&format!("{unit:04X}"));
+            }
+            cmap.push_str(">\n");
+        }
+        cmap.push_str("endbfchar\n");
+    }
+    cmap.push_str("endcmap\nCMapName currentdict /CMap defineresource pop\nend\nend");
+
+    objects.stream("", cmap.as_bytes())
+}
+
+#[cfg(all(test, feature = "json5"))]
+mod tests {
+    use super::*;
+
+    fn find(haystack: &[u8], needle: &[u8], from: usize) -> Option<usize> {
+        haystack[from..]
+            .windows(needle.len())
+            .position(|window| window == needle)
+            .map(|i| from + i)
+    }
+
+    fn number_after(pdf: &[u8], at: usize) -> usize {
+        pdf[at..]
+            .iter()
+            .take_while(|c| c.is_ascii_digit())
+            .fold(0, |n, c| n * 10 + usize::from(c - b'0'))
+    }
+
+    /// Render the figure and check the cross-reference table and the stream lengths. Returns the
+    /// objects in the document.
+    fn write_checked(source: &str) -> Vec<String> {
+        let figure = Figure::from_json5(source).unwrap();
+        let mut pdf = Vec::new();
+        figure
+            .write_pdf_with_options(
+                &mut pdf,
+                PathAssembleOptions::default(),
+                &RenderOptions::default(),
+            )
+            .unwrap();
+
+        assert!(pdf.starts_with(b"%PDF-1.7\n"));
+        assert!(pdf.ends_with(b"%%EOF\n"));
+
+        let startxref = find(&pdf, b"startxref\n", pdf.len() - 32).unwrap();
+        let xref = number_after(&pdf, startxref + b"startxref\n".len());
+        assert!(pdf[xref..].starts_with(b"xref\n0 "));
+
+        let size = number_after(&pdf, xref + b"xref\n0 ".len());
+        let trailer = find(&pdf, b"trailer\n", xref).unwrap();
+        assert_eq!(
+            find(
+                &pdf,
+                format!("<< /Size {size} /Root 1 0 R >>").as_bytes(),
+                trailer
+            ),
+            Some(trailer + b"trailer\n".len())
+        );
+
+        let entries = &pdf[find(&pdf, b"\n", xref + 5).unwrap() + 1..trailer];
+        assert_eq!(entries.len(), 20 * size);
+        assert_eq!(&entries[..20], b"0000000000 65535 f \n");
+
+        let offsets = entries
+            .chunks(20)
+            .skip(1)
+            .map(|entry| {
+                assert!(entry.ends_with(b" 00000 n \n"));
+                number_after(entry, 0)
+            })
+            .collect::<Vec<_>>();
+
+        let mut objects = Vec::new();
+        for (i, &offset) in offsets.iter().enumerate() {
+            let header = format!("{} 0 obj\n", i + 1);
+            assert!(pdf[offset..].starts_with(header.as_bytes()));
+
+            let end = offsets.get(i + 1).copied().unwrap_or(xref);
+            let object = &pdf[offset + header.len()..end];
+            assert!(object.ends_with(b"\nendobj\n"));
+
+            if let Some(length) = find(object, b"/Length ", 0) {
+                let length = number_after(object, length + b"/Length ".len());
+                let data = find(object, b">>\nstream\n", 0).unwrap() + b">>\nstream\n".len();
+                assert_eq!(&object[data + length..], b"\nendstream\nendobj\n");
+            }
+
+            objects.push(String::from_utf8_lossy(object).into_owned());
+        }
+
+        objects
+    }
+
+    #[test]
+    fn undefined_hatch_pattern() {
+        let objects = write_checked(r#"{ signal: [{ wave: "x.01" }] }"#);
+
+        // There is no text, so there is no font.
+        assert_eq!(objects.len(), 6);
+        assert!(objects[3].contains("q\n/Pattern cs /P0 scn\n"));
+        assert_eq!(objects[4], "<< /Pattern << /P0 6 0 R >> >>\nendobj\n");
+        assert_eq!(
+            objects[5],
+            "<< /Type /Pattern /PatternType 1 /PaintType 1 /TilingType 1 /BBox [0 0 4 10] \
+             /XStep 4 /YStep 10 /Matrix [0.707 -0.707 -0.707 -0.707 8 40] /Resources 5 0 R \
+             /Length 53 >>\nstream\nq\n0 0 0 rg\n0 0 0 RG\n1 w 0 J 0 j 4 M\n0 0 m\n0 10 l\nB\nQ\n\
+             \nendstream\nendobj\n"
+        );
+    }
+
+    #[test]
+    fn text_styles() {
+        let objects = write_checked(
+            r#"{
+    signal: [{
+        name: ["tspan", ["b", "B"], ["i", "I"], ["u", "U"], ["o", "O"], ["s", "S"]],
+        wave: "01",
+    }],
+}"#,
+        );
+
+        let content = &objects[3];
+        // Bold text is stroked and italic text is slanted.
+        assert!(content.contains("2 Tr 0 0 0 RG 0.42 w\n1 0 0 -1 0 3.661 Tm <0027> Tj ET\n"));
+        assert!(content.contains("1 0 0.2 -1 9.338 3.661 Tm <002E> Tj ET\n"));
+        // The underline, overline and line-through
+        assert!(content.contains("13.228 4.375 10.11 0.69 re f\n"));
+        assert!(content.contains("23.338 -7.465 10.89 0.69 re f\n"));
+        assert!(content.contains("34.228 -0.185 9.338 0.69 re f\n"));
+
+        assert_eq!(objects.len(), 10);
+        assert_eq!(objects[4], "<< /Font << /F0 6 0 R >> >>\nendobj\n");
+        assert!(objects[5].starts_with("<< /Type /Font /Subtype /Type0 /BaseFont /Helvetica "));
+        assert!(objects[8].starts_with(&format!("<< /Length1 {} ", HELVETICA_TTF.len())));
+        assert!(objects[9].contains("5 beginbfchar\n<0027> <0042>\n"));
+    }
+
+    #[test]
+    fn register_figure() {
+        let objects = write_checked(r#"{ reg: [{ bits: 4, name: "A", type: 3 }, { bits: 4 }] }"#);
+
+        let content = &objects[3];
+        assert!(content.contains(">>\nstream\n1 0 0 -1 0 96 cm\n"));
+        // The background of the field with type 3
+        assert!(content.contains(
+            "q\n0.969 0.969 0.631 rg\n328 28 m\n648 28 l\n648 68 l\n328 68 l\nh\nf\nQ\n"
+        ));
+        // The name of the field and the numbers of its first and last bits
+        assert!(content.contains("1 0 0 -1 483.331 51.661 Tm <0026> Tj ET\n"));
+        assert!(content.contains("1 0 0 -1 604.941 23.876 Tm <0015> Tj ET\n"));
+        assert!(content.contains("1 0 0 -1 364.941 23.876 Tm <0018> Tj ET\n"));
+    }
+}
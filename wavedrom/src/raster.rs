@@ -0,0 +1,106 @@
+//! Rasterization of figures into PNG images
+//!
+//! The figures are first rendered to SVG and then rasterized with [`resvg`]. The text is always
+//! drawn with the Helvetica font that is embedded in the library, which is also used to measure
+//! the text. Fonts that are installed on the system are never loaded, so the same figure gives the
+//! same image on every machine.
+//!
+//! # Examples
+//!
+//! ```
+//! use wavedrom::signal::{Signal, SignalFigure};
+//! use wavedrom::signal::options::RenderOptions;
+//!
+//! let figure = SignalFigure::new().add_signal(Signal::with_cycle_str("p...").name("clk"));
+//!
+//! let mut png = Vec::new();
+//! figure.assemble().write_png(&mut png, 2.0, &RenderOptions::default())?;
+//! # <Result<(), std::io::Error>>::Ok(())
+//! ```
+
+use std::io;
+
+use usvg::{TreeParsing, TreeTextToPath};
+
+use crate::font::HELVETICA_TTF;
+use crate::signal::options::{PathAssembleOptions, RenderOptions};
+use crate::signal::AssembledFigure;
+use crate::Figure;
+
+/// The font family that is used for text that does not name a known font.
+const FONT_FAMILY: &str = "Helvetica";
+
+impl AssembledFigure<'_> {
+    /// Render a [`AssembledFigure`] as a PNG image into a `writer`.
+    ///
+    /// The image is `scale` times the size of the SVG output.
+    pub fn write_png(
+        &self,
+        writer: &mut impl io::Write,
+        scale: f32,
+        options: &RenderOptions,
+    ) -> io::Result<()> {
+        let mut svg = Vec::new();
+        self.write_svg_with_options(&mut svg, options)?;
+        write_svg_as_png(&svg, writer, scale)
+    }
+}
+
+impl Figure {
+    /// Assemble and render a [`Figure`] as a PNG image into a `writer`.
+    ///
+    /// The image is `scale` times the size of the SVG output.
+    pub fn write_png_with_options(
+        &self,
+        writer: &mut impl io::Write,
+        scale: f32,
+        assemble_options: PathAssembleOptions,
+        render_options: &RenderOptions,
+    ) -> io::Result<()> {
+        let mut svg = Vec::new();
+        self.write_svg_with_options(&mut svg, assemble_options, render_options)?;
+        write_svg_as_png(&svg, writer, scale)
+    }
+}
+
+/// Rasterize an SVG document as a PNG image that is `scale` times its size.
+pub fn write_svg_as_png(svg: &[u8], writer: &mut impl io::Write, scale: f32) -> io::Result<()> {
+    if !scale.is_finite() || scale <= 0.0 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("scale {scale} is not a positive number"),
+        ));
+    }
+
+    let mut fontdb = usvg::fontdb::Database::new();
+    fontdb.load_font_data(HELVETICA_TTF.to_vec());
+    fontdb.set_sans_serif_family(FONT_FAMILY);
+
+    let options = usvg::Options {
+        font_family: FONT_FAMILY.to_string(),
+        ..usvg::Options::default()
+    };
+
+    let mut tree = usvg::Tree::from_data(svg, &options)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+    tree.convert_text(&fontdb);
+
+    let tree = resvg::Tree::from_usvg(&tree);
+    let size = tree.size.to_int_size();
+
+    let width = (size.width() as f32 * scale).ceil() as u32;
+    let height = (size.height() as f32 * scale).ceil() as u32;
+    let mut pixmap = resvg::tiny_skia::Pixmap::new(width, height).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("image of {width}x{height} pixels cannot be created"),
+        )
+    })?;
+    tree.render(
+        usvg::Transform::from_scale(scale, scale),
+        &mut pixmap.as_mut(),
+    );
+
+    let png = pixmap.encode_png().map_err(io::Error::other)?;
+    writer.write_all(&png)
+}
@@ -0,0 +1,522 @@
+//! The logic to render register / bitfield diagrams
+//!
+//! A register diagram shows a set of fields that together form a register. The fields are placed
+//! from the least significant bit upwards and can be spread over multiple lanes.
+
+mod render;
+
+/// A field of a [`RegisterFigure`].
+///
+/// A field spans a number of bits and can contain a name, a value that is shown per bit, a set of
+/// attributes and a type that determines the background color of the field.
+#[derive(Debug, Clone)]
+pub struct RegisterField {
+    bits: u32,
+    name: Option<String>,
+    value: Option<u64>,
+    attributes: Vec<String>,
+    field_type: Option<u8>,
+}
+
+/// An encapsulation of everything to form a register / bitfield diagram.
+///
+/// # Examples
+///
+/// ```
+/// use wavedrom::register::{RegisterField, RegisterFigure};
+///
+/// let figure = RegisterFigure::new()
+///                  .add_fields([
+///                      RegisterField::new(7).name("opcode").field_type(2),
+///                      RegisterField::new(5).name("rd").field_type(3),
+///                      RegisterField::new(3).name("func3"),
+///                      RegisterField::new(5).name("rs1").field_type(4),
+///                      RegisterField::new(12).name("imm").attribute("signed"),
+///                  ])
+///                  .lanes(2);
+///
+/// let mut svg = Vec::new();
+/// figure.assemble().write_svg(&mut svg)?;
+/// # <Result<(), std::io::Error>>::Ok(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct RegisterFigure {
+    fields: Vec<RegisterField>,
+    bits: Option<u32>,
+    lanes: u32,
+    hspace: Option<u32>,
+    vspace: Option<u32>,
+    compact: bool,
+    offset: u32,
+}
+
+/// A [`RegisterFigure`] that has been assembled with the [`RegisterFigure::assemble`] method.
+///
+/// An assembled figure contains all the information necessary to perform rendering.
+#[derive(Debug)]
+pub struct AssembledRegisterFigure<'a> {
+    bits: u32,
+    lane_bits: u32,
+
+    hspace: Option<u32>,
+    vspace: Option<u32>,
+
+    compact: bool,
+    offset: u32,
+
+    lanes: Vec<RegisterLane<'a>>,
+}
+
+/// A single lane of an [`AssembledRegisterFigure`].
+///
+/// This contains the bits `start..end` of the register.
+#[derive(Debug, Clone)]
+pub struct RegisterLane<'a> {
+    start: u32,
+    end: u32,
+    pieces: Vec<RegisterFieldPiece<'a>>,
+}
+
+/// The part of a [`RegisterField`] that falls within a single [`RegisterLane`].
+///
+/// Bits of the register that are not covered by any field are represented by a piece without a
+/// field.
+#[derive(Debug, Clone)]
+pub struct RegisterFieldPiece<'a> {
+    lsb: u32,
+    msb: u32,
+    field_lsb: u32,
+    field: Option<&'a RegisterField>,
+}
+
+impl RegisterField {
+    /// Create a new [`RegisterField`] that spans `bits` bits.
+    #[inline]
+    pub fn new(bits: u32) -> Self {
+        Self {
+            bits,
+            name: None,
+            value: None,
+            attributes: Vec::new(),
+            field_type: None,
+        }
+    }
+
+    /// Set the name of the [`RegisterField`].
+    #[inline]
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Set a value for the [`RegisterField`]. The value is displayed bit by bit.
+    #[inline]
+    pub fn value(mut self, value: u64) -> Self {
+        self.value = Some(value);
+        self
+    }
+
+    /// Add an attribute to the [`RegisterField`]. Attributes are displayed below the field.
+    #[inline]
+    pub fn attribute(mut self, attribute: impl Into<String>) -> Self {
+        self.attributes.push(attribute.into());
+        self
+    }
+
+    /// Add a set of attributes to the [`RegisterField`].
+    #[inline]
+    pub fn attributes(mut self, attributes: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.attributes
+            .extend(attributes.into_iter().map(Into::into));
+        self
+    }
+
+    /// Set the type of the [`RegisterField`].
+    ///
+    /// The types `2` to `9` correspond to the background colors of the
+    /// [`CycleState::Box2`][crate::signal::CycleState::Box2] to
+    /// [`CycleState::Box9`][crate::signal::CycleState::Box9] states.
+    #[inline]
+    pub fn field_type(mut self, field_type: u8) -> Self {
+        self.field_type = Some(field_type);
+        self
+    }
+
+    /// Get the amount of bits that the [`RegisterField`] spans.
+    #[inline]
+    pub fn get_bits(&self) -> u32 {
+        self.bits
+    }
+
+    /// Get the name of the [`RegisterField`].
+    #[inline]
+    pub fn get_name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Get the value of the [`RegisterField`].
+    #[inline]
+    pub fn get_value(&self) -> Option<u64> {
+        self.value
+    }
+
+    /// Get the attributes of the [`RegisterField`].
+    #[inline]
+    pub fn get_attributes(&self) -> &[String] {
+        &self.attributes
+    }
+
+    /// Get the type of the [`RegisterField`].
+    #[inline]
+    pub fn get_field_type(&self) -> Option<u8> {
+        self.field_type
+    }
+}
+
+impl Default for RegisterFigure {
+    fn default() -> Self {
+        Self {
+            fields: Vec::new(),
+            bits: None,
+            lanes: 1,
+            hspace: None,
+            vspace: None,
+            compact: false,
+            offset: 0,
+        }
+    }
+}
+
+impl RegisterFigure {
+    /// The largest amount of bits that is drawn. Registers with more bits are cut off.
+    pub const MAX_BITS: u32 = 4096;
+
+    /// The largest width and height of a lane.
+    pub const MAX_SPACE: u32 = 1 << 16;
+
+    /// Create a new empty [`RegisterFigure`].
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a [`RegisterField`] to the [`RegisterFigure`].
+    ///
+    /// Fields are placed starting from the least significant bit.
+    #[inline]
+    pub fn add_field(mut self, field: RegisterField) -> Self {
+        self.fields.push(field);
+        self
+    }
+
+    /// Add a set of [`RegisterField`]s to the [`RegisterFigure`].
+    #[inline]
+    pub fn add_fields(mut self, fields: impl IntoIterator<Item = RegisterField>) -> Self {
+        self.fields.extend(fields);
+        self
+    }
+
+    /// Set the total amount of bits of the register.
+    ///
+    /// By default, this is the sum of the bits of all fields.
+    #[inline]
+    pub fn bits(mut self, bits: u32) -> Self {
+        self.bits = Some(bits);
+        self
+    }
+
+    /// Set the amount of lanes that the register is divided over.
+    #[inline]
+    pub fn lanes(mut self, lanes: u32) -> Self {
+        self.lanes = lanes.max(1);
+        self
+    }
+
+    /// Set the width of a single lane. This overrides the
+    /// [`RegisterOptions::lane_width`][crate::signal::options::RegisterOptions::lane_width].
+    #[inline]
+    pub fn horizontal_space(mut self, hspace: u32) -> Self {
+        self.hspace = Some(hspace);
+        self
+    }
+
+    /// Set the height of a single lane. This overrides the
+    /// [`RegisterOptions::lane_height`][crate::signal::options::RegisterOptions::lane_height].
+    #[inline]
+    pub fn vertical_space(mut self, vspace: u32) -> Self {
+        self.vspace = Some(vspace);
+        self
+    }
+
+    /// Set whether the lanes are drawn compactly with a single row of bit numbers.
+    #[inline]
+    pub fn compact(mut self, compact: bool) -> Self {
+        self.compact = compact;
+        self
+    }
+
+    /// Set the number of the least significant bit.
+    #[inline]
+    pub fn offset(mut self, offset: u32) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Get the fields of the [`RegisterFigure`].
+    #[inline]
+    pub fn fields(&self) -> &[RegisterField] {
+        &self.fields
+    }
+
+    /// Returns the total amount of bits of the register.
+    pub fn num_bits(&self) -> u32 {
+        self.bits.unwrap_or_else(|| {
+            self.fields
+                .iter()
+                .fold(0, |sum: u32, field| sum.saturating_add(field.bits))
+        })
+    }
+
+    /// Divide the fields over the lanes and fetch the information needed for rendering.
+    ///
+    /// At most [`RegisterFigure::MAX_BITS`] bits are assembled and the lane width and height are
+    /// limited to [`RegisterFigure::MAX_SPACE`].
+    pub fn assemble(&self) -> AssembledRegisterFigure<'_> {
+        let bits = self.num_bits().min(Self::MAX_BITS);
+        let num_lanes = self.lanes.clamp(1, bits.max(1));
+        let lane_bits = bits.div_ceil(num_lanes);
+
+        let mut lanes = (0..num_lanes)
+            .map(|i| RegisterLane {
+                start: i * lane_bits,
+                end: u32::min((i + 1) * lane_bits, bits),
+                pieces: Vec::new(),
+            })
+            .collect::<Vec<_>>();
+
+        fn place<'a>(
+            lanes: &mut [RegisterLane<'a>],
+            start: u32,
+            end: u32,
+            field: Option<&'a RegisterField>,
+        ) {
+            for lane in lanes.iter_mut() {
+                let lsb = u32::max(start, lane.start);
+                let msb = u32::min(end, lane.end);
+
+                if lsb >= msb {
+                    continue;
+                }
+
+                lane.pieces.push(RegisterFieldPiece {
+                    lsb,
+                    msb: msb - 1,
+                    field_lsb: start,
+                    field,
+                });
+            }
+        }
+
+        let mut position = 0;
+        for field in self.fields.iter() {
+            if position >= bits {
+                break;
+            }
+
+            if field.bits == 0 {
+                continue;
+            }
+
+            let end = u32::min(position.saturating_add(field.bits), bits);
+            place(&mut lanes, position, end, Some(field));
+            position = end;
+        }
+
+        if position < bits {
+            place(&mut lanes, position, bits, None);
+        }
+
+        AssembledRegisterFigure {
+            bits,
+            lane_bits,
+
+            hspace: self.hspace.map(|hspace| hspace.min(Self::MAX_SPACE)),
+            vspace: self.vspace.map(|vspace| vspace.min(Self::MAX_SPACE)),
+
+            compact: self.compact,
+            offset: self.offset,
+
+            lanes,
+        }
+    }
+}
+
+impl<'a> AssembledRegisterFigure<'a> {
+    /// Returns the total amount of bits of the register.
+    #[inline]
+    pub fn num_bits(&self) -> u32 {
+        self.bits
+    }
+
+    /// Returns the maximum amount of bits per lane.
+    #[inline]
+    pub fn lane_bits(&self) -> u32 {
+        self.lane_bits
+    }
+
+    /// Returns whether the register is drawn compactly.
+    #[inline]
+    pub fn is_compact(&self) -> bool {
+        self.compact
+    }
+
+    /// Returns the number of the least significant bit.
+    #[inline]
+    pub fn offset(&self) -> u32 {
+        self.offset
+    }
+
+    /// Returns the lanes of the register starting with the lane with the least significant bits.
+    #[inline]
+    pub fn lanes(&self) -> &[RegisterLane<'a>] {
+        &self.lanes
+    }
+}
+
+impl<'a> RegisterLane<'a> {
+    /// The first bit contained in the lane.
+    #[inline]
+    pub fn start(&self) -> u32 {
+        self.start
+    }
+
+    /// The bit after the last bit contained in the lane.
+    #[inline]
+    pub fn end(&self) -> u32 {
+        self.end
+    }
+
+    /// The field pieces contained in the lane starting with the least significant piece.
+    #[inline]
+    pub fn pieces(&self) -> &[RegisterFieldPiece<'a>] {
+        &self.pieces
+    }
+}
+
+impl<'a> RegisterFieldPiece<'a> {
+    /// The least significant bit of the piece.
+    #[inline]
+    pub fn lsb(&self) -> u32 {
+        self.lsb
+    }
+
+    /// The most significant bit of the piece.
+    #[inline]
+    pub fn msb(&self) -> u32 {
+        self.msb
+    }
+
+    /// The field that the piece belongs to. Bits that are not covered by a field have no field.
+    #[inline]
+    pub fn field(&self) -> Option<&'a RegisterField> {
+        self.field
+    }
+
+    /// The value of bit `bit` within the field's value.
+    pub fn bit_value(&self, bit: u32) -> Option<bool> {
+        let value = self.field?.value?;
+        let shift = bit.checked_sub(self.field_lsb)?;
+
+        Some(value.checked_shr(shift).is_some_and(|v| v & 1 == 1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn assemble_lanes() {
+        let figure = RegisterFigure::new()
+            .add_fields([
+                RegisterField::new(3).name("a"),
+                RegisterField::new(10).name("b"),
+                RegisterField::new(1).name("c"),
+            ])
+            .bits(16)
+            .lanes(2);
+
+        let assembled = figure.assemble();
+        let lanes = assembled.lanes();
+
+        assert_eq!(assembled.lane_bits(), 8);
+        assert_eq!(lanes.len(), 2);
+
+        fn ranges<'a>(lane: &RegisterLane<'a>) -> Vec<(u32, u32, Option<&'a str>)> {
+            lane.pieces()
+                .iter()
+                .map(|p| (p.lsb(), p.msb(), p.field().and_then(|f| f.get_name())))
+                .collect()
+        }
+
+        assert_eq!(ranges(&lanes[0]), [(0, 2, Some("a")), (3, 7, Some("b"))]);
+        assert_eq!(
+            ranges(&lanes[1]),
+            [(8, 12, Some("b")), (13, 13, Some("c")), (14, 15, None)]
+        );
+    }
+
+    #[test]
+    fn bit_values() {
+        let figure = RegisterFigure::new()
+            .add_fields([RegisterField::new(2), RegisterField::new(4).value(0b1010)]);
+        let assembled = figure.assemble();
+        let piece = &assembled.lanes()[0].pieces()[1];
+
+        assert_eq!(piece.bit_value(2), Some(false));
+        assert_eq!(piece.bit_value(3), Some(true));
+        assert_eq!(piece.bit_value(5), Some(true));
+        assert_eq!(assembled.lanes()[0].pieces()[0].bit_value(0), None);
+    }
+
+    fn render(figure: &RegisterFigure) -> String {
+        let mut svg = Vec::new();
+        figure.assemble().write_svg(&mut svg).unwrap();
+        String::from_utf8(svg).unwrap()
+    }
+
+    #[test]
+    fn field_bits_sum_saturates() {
+        // `{reg:[{bits:4294967295},{bits:1}]}`
+        let figure =
+            RegisterFigure::new().add_fields([RegisterField::new(u32::MAX), RegisterField::new(1)]);
+        assert_eq!(figure.num_bits(), u32::MAX);
+        assert_eq!(figure.assemble().num_bits(), RegisterFigure::MAX_BITS);
+        assert!(render(&figure).ends_with("</svg>"));
+    }
+
+    #[test]
+    fn huge_field_is_cut_off() {
+        // `{reg:[{bits:100000000}]}`
+        let figure = RegisterFigure::new().add_field(RegisterField::new(100_000_000));
+        assert_eq!(figure.assemble().num_bits(), RegisterFigure::MAX_BITS);
+        assert!(render(&figure).ends_with("</svg>"));
+    }
+
+    #[test]
+    fn huge_lane_width_is_limited() {
+        // `{reg:[{bits:8}], config:{hspace:4294967295}}`
+        let figure = RegisterFigure::new()
+            .add_field(RegisterField::new(8).name("a"))
+            .horizontal_space(u32::MAX);
+        let svg = render(&figure);
+        assert!(svg.ends_with("</svg>"));
+        assert!(svg.contains(&format!(r#"width="{}""#, RegisterFigure::MAX_SPACE + 16)));
+
+        let figure = figure
+            .vertical_space(u32::MAX)
+            .offset(u32::MAX)
+            .compact(true);
+        assert!(render(&figure).ends_with("</svg>"));
+    }
+}
@@ -0,0 +1,330 @@
+use std::io;
+
+use crate::escape::escape_str;
+use crate::signal::options::{RegisterOptions, RenderOptions};
+use crate::{Color, Font};
+
+use super::{AssembledRegisterFigure, RegisterFieldPiece};
+
+/// The horizontal positions of the bits within a lane.
+struct LaneColumns {
+    x: u32,
+    width: u32,
+    lane_bits: u32,
+}
+
+impl LaneColumns {
+    /// The position of the boundary after `bits` bits counted from the left side of the lane.
+    #[inline]
+    fn boundary(&self, bits: u32) -> u64 {
+        u64::from(self.x) + u64::from(self.width) * u64::from(bits) / u64::from(self.lane_bits)
+    }
+
+    /// The left side of bit `bit` counted from the least significant bit of the lane.
+    #[inline]
+    fn left(&self, bit: u32) -> u64 {
+        self.boundary(self.lane_bits - bit - 1)
+    }
+
+    /// The right side of bit `bit` counted from the least significant bit of the lane.
+    #[inline]
+    fn right(&self, bit: u32) -> u64 {
+        self.boundary(self.lane_bits - bit)
+    }
+
+    /// The middle of bit `bit` counted from the least significant bit of the lane.
+    #[inline]
+    fn middle(&self, bit: u32) -> u64 {
+        (self.left(bit) + self.right(bit)) / 2
+    }
+}
+
+fn field_background(
+    piece: &RegisterFieldPiece,
+    register: &RegisterOptions,
+    backgrounds: &[Color; 8],
+) -> Option<Color> {
+    match piece.field().and_then(|field| field.get_field_type()) {
+        Some(field_type @ 2..=9) => Some(backgrounds[usize::from(field_type - 2)]),
+        _ => register.box_background,
+    }
+}
+
+impl<'a> AssembledRegisterFigure<'a> {
+    /// Render a [`AssembledRegisterFigure`] into a `writer`.
+    #[inline]
+    pub fn write_svg(&self, writer: &mut impl io::Write) -> io::Result<()> {
+        self.write_svg_with_options(writer, &RenderOptions::default())
+    }
+
+    /// Render a [`AssembledRegisterFigure`] into a `writer` with a set of options.
+    pub fn write_svg_with_options(
+        &self,
+        writer: &mut impl io::Write,
+        options: &RenderOptions,
+    ) -> io::Result<()> {
+        #[cfg(feature = "embed_font")]
+        if options.font.embed_subset || options.font.outline_text {
+            return crate::font::write_with_font_options(writer, &options.font, |svg| {
+                self.write_svg_document(svg, options)
+            });
+        }
+
+        self.write_svg_document(writer, options)
+    }
+
+    fn write_svg_document(
+        &self,
+        writer: &mut impl io::Write,
+        options: &RenderOptions,
+    ) -> io::Result<()> {
+        let RenderOptions {
+            background,
+            padding,
+            spacing,
+            signal,
+            register,
+            ..
+        } = options;
+
+        let hspace = self.hspace.unwrap_or(register.lane_width);
+        let vspace = self.vspace.unwrap_or(register.lane_height);
+        let box_height = vspace / 2;
+
+        let font = Font::default();
+        let font_family = font
+            .get_font_family_name()
+            .unwrap_or_else(|| "Helvetica".to_string());
+
+        let num_lanes = self.lanes.len() as u32;
+        let bit_number_font_size = register.bit_number_font_size;
+
+        let label_width = if self.compact {
+            self.lanes
+                .iter()
+                .map(|lane| {
+                    font.get_text_width(
+                        &(u64::from(lane.start()) + u64::from(self.offset)).to_string(),
+                        bit_number_font_size,
+                    )
+                })
+                .max()
+                .unwrap_or_default()
+                + spacing.textbox_to_schema
+        } else {
+            0
+        };
+        let header_height = if self.compact {
+            bit_number_font_size + 4
+        } else {
+            0
+        };
+        let lane_height = if self.compact { box_height } else { vspace };
+
+        let schema_x = padding.figure_left + label_width;
+        let schema_y = padding.figure_top + header_height;
+
+        let figure_width =
+            u64::from(schema_x) + u64::from(hspace) + u64::from(padding.figure_right);
+        let figure_height = u64::from(schema_y)
+            + u64::from(num_lanes) * u64::from(lane_height)
+            + u64::from(padding.figure_bottom);
+
+        write!(
+            writer,
+            r#"<svg version="1.1" xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink" viewport="0 0 {figure_width} {figure_height}" overflow="hidden" width="{figure_width}" height="{figure_height}">"#,
+        )?;
+
+        // Background
+        if let Some(background) = background {
+            write!(
+                writer,
+                r##"<rect width="100%" height="100%" fill="{background}"/>"##
+            )?;
+        }
+
+        if self.lane_bits == 0 {
+            return write!(writer, "</svg>");
+        }
+
+        let columns = LaneColumns {
+            x: schema_x,
+            width: hspace,
+            lane_bits: self.lane_bits,
+        };
+
+        // Compact Bit Numbers
+        if self.compact {
+            write!(writer, "<g>")?;
+            for bit in 0..self.lane_bits {
+                write!(
+                    writer,
+                    r##"<text x="{x}" y="{y}" text-anchor="middle" dominant-baseline="middle" font-family="{font_family}" font-size="{bit_number_font_size}" fill="{color}" letter-spacing="0"><tspan>{bit}</tspan></text>"##,
+                    x = columns.middle(bit),
+                    y = padding.figure_top + header_height / 2,
+                    color = register.bit_number_color,
+                )?;
+            }
+            write!(writer, "</g>")?;
+        }
+
+        for (i, lane) in self.lanes.iter().enumerate() {
+            let row = num_lanes - 1 - i as u32;
+            let lane_bits = lane.end() - lane.start();
+
+            if lane_bits == 0 {
+                continue;
+            }
+
+            let box_top = schema_y
+                + row * lane_height
+                + if self.compact {
+                    0
+                } else {
+                    (vspace - box_height) / 2
+                };
+            let box_bottom = box_top + box_height;
+            let box_middle = box_top + box_height / 2;
+
+            write!(writer, "<g>")?;
+
+            // Field Backgrounds
+            for piece in lane.pieces() {
+                let Some(background) = field_background(piece, register, &signal.backgrounds)
+                else {
+                    continue;
+                };
+
+                let x = columns.left(piece.msb() - lane.start());
+                let width = columns.right(piece.lsb() - lane.start()) - x;
+
+                write!(
+                    writer,
+                    r##"<rect x="{x}" y="{box_top}" width="{width}" height="{box_height}" fill="{background}" stroke="none"/>"##,
+                )?;
+            }
+
+            // Lane Box, Field Separators and Bit Ticks
+            let tick = box_height / 8;
+            write!(
+                writer,
+                r##"<path fill="none" d="M{left},{box_top}H{right}V{box_bottom}H{left}z"##,
+                left = columns.left(lane_bits - 1),
+                right = columns.right(0),
+            )?;
+            for piece in lane.pieces() {
+                let lsb = piece.lsb() - lane.start();
+                let msb = piece.msb() - lane.start();
+
+                if lsb != 0 {
+                    write!(
+                        writer,
+                        "M{x},{box_top}V{box_bottom}",
+                        x = columns.right(lsb)
+                    )?;
+                }
+
+                for bit in lsb + 1..=msb {
+                    write!(
+                        writer,
+                        "M{x},{box_top}v{tick}M{x},{box_bottom}v-{tick}",
+                        x = columns.right(bit),
+                    )?;
+                }
+            }
+            write!(
+                writer,
+                r##"" stroke="{color}" stroke-width="1"/>"##,
+                color = register.box_color,
+            )?;
+
+            // Compact Lane Label
+            if self.compact {
+                write!(
+                    writer,
+                    r##"<text x="{x}" y="{box_middle}" dominant-baseline="middle" font-family="{font_family}" font-size="{bit_number_font_size}" fill="{color}" letter-spacing="0"><tspan>{label}</tspan></text>"##,
+                    x = padding.figure_left,
+                    color = register.bit_number_color,
+                    label = u64::from(lane.start()) + u64::from(self.offset),
+                )?;
+            }
+
+            for piece in lane.pieces() {
+                let lsb = piece.lsb() - lane.start();
+                let msb = piece.msb() - lane.start();
+
+                // Bit Numbers
+                if !self.compact {
+                    let y = box_top - bit_number_font_size / 2 - 2;
+
+                    for bit in [lsb, msb] {
+                        write!(
+                            writer,
+                            r##"<text x="{x}" y="{y}" text-anchor="middle" dominant-baseline="middle" font-family="{font_family}" font-size="{bit_number_font_size}" fill="{color}" letter-spacing="0"><tspan>{number}</tspan></text>"##,
+                            x = columns.middle(bit),
+                            color = register.bit_number_color,
+                            number = u64::from(lane.start() + bit) + u64::from(self.offset),
+                        )?;
+
+                        if lsb == msb {
+                            break;
+                        }
+                    }
+                }
+
+                let Some(field) = piece.field() else {
+                    continue;
+                };
+
+                // Field Value or Name
+                if field.get_value().is_some() {
+                    for bit in lsb..=msb {
+                        let Some(value) = piece.bit_value(lane.start() + bit) else {
+                            continue;
+                        };
+
+                        write!(
+                            writer,
+                            r##"<text x="{x}" y="{box_middle}" text-anchor="middle" dominant-baseline="middle" font-family="{font_family}" font-size="{font_size}" fill="{color}" letter-spacing="0"><tspan>{value}</tspan></text>"##,
+                            x = columns.middle(bit),
+                            font_size = register.name_font_size,
+                            color = register.name_color,
+                            value = u8::from(value),
+                        )?;
+                    }
+                } else if let Some(name) = field.get_name() {
+                    write!(
+                        writer,
+                        r##"<text x="{x}" y="{box_middle}" text-anchor="middle" dominant-baseline="middle" font-family="{font_family}" font-size="{font_size}" fill="{color}" letter-spacing="0"><tspan>{text}</tspan></text>"##,
+                        x = (columns.left(msb) + columns.right(lsb)) / 2,
+                        font_size = register.name_font_size,
+                        color = register.name_color,
+                        text = escape_str(name),
+                    )?;
+                }
+
+                // Field Attributes
+                if !self.compact {
+                    let font_size = register.attribute_font_size;
+
+                    for (j, attribute) in field.get_attributes().iter().enumerate() {
+                        write!(
+                            writer,
+                            r##"<text x="{x}" y="{y}" text-anchor="middle" dominant-baseline="middle" font-family="{font_family}" font-size="{font_size}" fill="{color}" letter-spacing="0"><tspan>{text}</tspan></text>"##,
+                            x = (columns.left(msb) + columns.right(lsb)) / 2,
+                            y = box_bottom + 2 + font_size * j as u32 + font_size / 2,
+                            color = register.attribute_color,
+                            text = escape_str(attribute),
+                        )?;
+                    }
+                }
+            }
+
+            write!(writer, "</g>")?;
+        }
+
+        write!(writer, "</svg>")?;
+
+        Ok(())
+    }
+}
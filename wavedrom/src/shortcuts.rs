@@ -4,6 +4,12 @@ pub use self::json5::*;
 #[cfg(feature = "serde_json")]
 pub use self::serde_json::*;
 
+#[cfg(feature = "yaml")]
+pub use self::yaml::*;
+
+#[cfg(feature = "toml")]
+pub use self::toml::*;
+
 #[cfg(feature = "json5")]
 mod json5 {
     use std::error::Error;
@@ -42,11 +48,7 @@ mod json5 {
         render_options: &RenderOptions,
     ) -> Result<(), RenderJson5Error> {
         let figure = Figure::from_json5(json)?;
-
-        let Figure::Signal(figure) = figure;
-
-        let assembled = figure.assemble_with_options(assemble_options);
-        assembled.write_svg_with_options(writer, render_options)?;
+        figure.write_svg_with_options(writer, assemble_options, render_options)?;
 
         Ok(())
     }
@@ -115,11 +117,7 @@ mod serde_json {
         render_options: &RenderOptions,
     ) -> Result<(), RenderJsonError> {
         let figure = Figure::from_json(json)?;
-
-        let Figure::Signal(figure) = figure;
-
-        let assembled = figure.assemble_with_options(assemble_options);
-        assembled.write_svg_with_options(writer, render_options)?;
+        figure.write_svg_with_options(writer, assemble_options, render_options)?;
 
         Ok(())
     }
@@ -149,3 +147,141 @@ mod serde_json {
 
     impl Error for RenderJsonError {}
 }
+
+#[cfg(feature = "yaml")]
+mod yaml {
+    use std::error::Error;
+    use std::fmt::Display;
+    use std::io;
+
+    use crate::signal::options::{PathAssembleOptions, RenderOptions};
+    use crate::Figure;
+
+    /// An error with the [`render_yaml`][crate::render_yaml] or
+    /// [`render_yaml_with_options`][crate::render_yaml_with_options] functions.
+    #[derive(Debug)]
+    pub enum RenderYamlError {
+        /// An error parsing the YAML
+        Yaml(serde_yaml::Error),
+        /// An error with the IO
+        Io(io::Error),
+    }
+
+    /// Render the contents of a yaml file to a `writer`.
+    #[inline]
+    pub fn render_yaml(yaml: &str, writer: &mut impl io::Write) -> Result<(), RenderYamlError> {
+        render_yaml_with_options(
+            yaml,
+            writer,
+            PathAssembleOptions::default(),
+            &RenderOptions::default(),
+        )
+    }
+
+    /// Render the contents of a yaml file to a `writer` with a specific set of options.
+    pub fn render_yaml_with_options(
+        yaml: &str,
+        writer: &mut impl io::Write,
+        assemble_options: PathAssembleOptions,
+        render_options: &RenderOptions,
+    ) -> Result<(), RenderYamlError> {
+        let figure = Figure::from_yaml(yaml)?;
+        figure.write_svg_with_options(writer, assemble_options, render_options)?;
+
+        Ok(())
+    }
+
+    impl From<serde_yaml::Error> for RenderYamlError {
+        #[inline]
+        fn from(error: serde_yaml::Error) -> Self {
+            Self::Yaml(error)
+        }
+    }
+
+    impl From<io::Error> for RenderYamlError {
+        #[inline]
+        fn from(error: io::Error) -> Self {
+            Self::Io(error)
+        }
+    }
+
+    impl Display for RenderYamlError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Self::Yaml(err) => err.fmt(f),
+                Self::Io(err) => err.fmt(f),
+            }
+        }
+    }
+
+    impl Error for RenderYamlError {}
+}
+
+#[cfg(feature = "toml")]
+mod toml {
+    use std::error::Error;
+    use std::fmt::Display;
+    use std::io;
+
+    use crate::signal::options::{PathAssembleOptions, RenderOptions};
+    use crate::Figure;
+
+    /// An error with the [`render_toml`][crate::render_toml] or
+    /// [`render_toml_with_options`][crate::render_toml_with_options] functions.
+    #[derive(Debug)]
+    pub enum RenderTomlError {
+        /// An error parsing the TOML
+        Toml(toml::de::Error),
+        /// An error with the IO
+        Io(io::Error),
+    }
+
+    /// Render the contents of a toml file to a `writer`.
+    #[inline]
+    pub fn render_toml(toml: &str, writer: &mut impl io::Write) -> Result<(), RenderTomlError> {
+        render_toml_with_options(
+            toml,
+            writer,
+            PathAssembleOptions::default(),
+            &RenderOptions::default(),
+        )
+    }
+
+    /// Render the contents of a toml file to a `writer` with a specific set of options.
+    pub fn render_toml_with_options(
+        toml: &str,
+        writer: &mut impl io::Write,
+        assemble_options: PathAssembleOptions,
+        render_options: &RenderOptions,
+    ) -> Result<(), RenderTomlError> {
+        let figure = Figure::from_toml(toml)?;
+        figure.write_svg_with_options(writer, assemble_options, render_options)?;
+
+        Ok(())
+    }
+
+    impl From<toml::de::Error> for RenderTomlError {
+        #[inline]
+        fn from(error: toml::de::Error) -> Self {
+            Self::Toml(error)
+        }
+    }
+
+    impl From<io::Error> for RenderTomlError {
+        #[inline]
+        fn from(error: io::Error) -> Self {
+            Self::Io(error)
+        }
+    }
+
+    impl Display for RenderTomlError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Self::Toml(err) => err.fmt(f),
+                Self::Io(err) => err.fmt(f),
+            }
+        }
+    }
+
+    impl Error for RenderTomlError {}
+}
@@ -1,88 +1,184 @@
 use std::cmp::Ordering;
-use std::ops::{Add, AddAssign};
+use std::fmt::{self, Display};
+use std::num::NonZeroU64;
+use std::ops::{Add, AddAssign, Rem};
 
-/// The cycle offset within a single-clock cycle.
+use super::Period;
+
+/// An offset in cycles from the start of the signals.
+///
+/// The offset is stored as an exact non-negative fraction of cycles. This means that phases such
+/// as `0.1` and positions within fractional [`Period`]s are placed exactly where they are
+/// specified.
+///
+/// # Examples
+///
+/// ```
+/// use wavedrom::signal::CycleOffset;
 ///
-/// At the moment a cycle in divided into 4 quarters and you 
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
-pub enum InCycleOffset {
-    /// 0/4
-    #[default]
-    Begin,
-    /// 1/4
-    Quarter,
-    /// 2/4
-    Half,
-    /// 3/4
-    ThreeQuarter,
+/// let offset = CycleOffset::from(0.1) + CycleOffset::new_rounded(2);
+/// assert_eq!(Some(offset), CycleOffset::new(21, 10));
+/// assert_eq!(offset.cycle_index(), 2);
+/// assert_eq!(offset.ceil_num_cycles(), 3);
+/// assert_eq!(offset.width_offset(40), 84);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CycleOffset {
+    numerator: u64,
+    denominator: NonZeroU64,
 }
 
-/// The cycle offset within multiple clock cycles. This is precise up until a quarter of a cycle.
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
-pub struct CycleOffset {
-    index: u32,
-    in_offset: InCycleOffset,
+impl CycleOffset {
+    /// The largest denominator that is produced when converting from a floating point number.
+    pub const MAX_APPROXIMATION_DENOMINATOR: u64 = 1000;
+
+    /// Create a new [`CycleOffset`] of `numerator / denominator` cycles.
+    ///
+    /// The fraction is reduced. Returns `None` if the `denominator` is zero.
+    #[inline]
+    pub fn new(numerator: u64, denominator: u64) -> Option<Self> {
+        if denominator == 0 {
+            return None;
+        }
+
+        Some(Self::reduced(numerator.into(), denominator.into()))
+    }
+
+    /// Create a new [`CycleOffset`] at the start of the cycle with index `index`.
+    #[inline]
+    pub fn new_rounded(index: u32) -> Self {
+        Self {
+            numerator: index.into(),
+            denominator: NonZeroU64::MIN,
+        }
+    }
+
+    /// Reduce the fraction `numerator / denominator`. The `denominator` cannot be zero.
+    ///
+    /// If the reduced fraction does not fit, both parts are divided by the same power of two
+    /// until it does. The numerator saturates if the offset itself is too large.
+    fn reduced(numerator: u128, denominator: u128) -> Self {
+        let divisor = gcd(numerator, denominator).max(1);
+        let (mut numerator, mut denominator) = (numerator / divisor, denominator / divisor);
+
+        let excess_bits = (u128::BITS - denominator.leading_zeros()).saturating_sub(u64::BITS);
+        numerator >>= excess_bits;
+        denominator >>= excess_bits;
+
+        Self {
+            numerator: u64::try_from(numerator).unwrap_or(u64::MAX),
+            denominator: u64::try_from(denominator)
+                .ok()
+                .and_then(NonZeroU64::new)
+                .unwrap_or(NonZeroU64::MIN),
+        }
+    }
+
+    /// Get the numerator of the reduced fraction of cycles.
+    #[inline]
+    pub fn numerator(self) -> u64 {
+        self.numerator
+    }
+
+    /// Get the denominator of the reduced fraction of cycles.
+    #[inline]
+    pub fn denominator(self) -> u64 {
+        self.denominator.get()
+    }
+
+    /// Get the a ceiled value of the number of cycles that the [`CycleOffset`] incorperates.
+    #[inline]
+    pub fn ceil_num_cycles(self) -> u32 {
+        u32::try_from(self.numerator.div_ceil(self.denominator())).unwrap_or(u32::MAX)
+    }
+
+    /// Get the index of the cycle that the [`CycleOffset`] falls in.
+    #[inline]
+    pub fn cycle_index(self) -> u32 {
+        u32::try_from(self.numerator / self.denominator()).unwrap_or(u32::MAX)
+    }
+
+    /// Get the part of the [`CycleOffset`] within its cycle as a number in `[0, 1)`.
+    #[inline]
+    pub fn in_cycle_offset(self) -> f64 {
+        (self.numerator % self.denominator()) as f64 / self.denominator() as f64
+    }
+
+    /// Get the [`CycleOffset`] as a floating point number of cycles.
+    #[inline]
+    pub fn as_f64(self) -> f64 {
+        self.numerator as f64 / self.denominator() as f64
+    }
+
+    /// Get the width knowning that a cycle cycle is `width` units wide.
+    ///
+    /// The width is rounded to the nearest unit.
+    #[inline]
+    pub fn width_offset(self, width: u32) -> u32 {
+        let numerator = u128::from(self.numerator) * u128::from(width);
+        let denominator = u128::from(self.denominator());
+
+        u32::try_from((2 * numerator + denominator) / (2 * denominator)).unwrap_or(u32::MAX)
+    }
+
+    /// Half the [`CycleOffset`]
+    #[inline]
+    pub fn half(&self) -> CycleOffset {
+        Self::reduced(self.numerator.into(), 2 * u128::from(self.denominator()))
+    }
+}
+
+impl Default for CycleOffset {
+    #[inline]
+    fn default() -> Self {
+        Self::new_rounded(0)
+    }
 }
 
 impl PartialOrd for CycleOffset {
+    #[inline]
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(match self.index.cmp(&other.index) {
-            Ordering::Equal => self.in_offset.cmp(&other.in_offset),
-            ord => ord,
-        })
+        Some(self.cmp(other))
     }
 }
 
 impl Ord for CycleOffset {
     fn cmp(&self, other: &Self) -> Ordering {
-        match self.index.cmp(&other.index) {
-            Ordering::Equal => self.in_offset.cmp(&other.in_offset),
-            ord => ord,
-        }
+        let lhs = u128::from(self.numerator) * u128::from(other.denominator());
+        let rhs = u128::from(other.numerator) * u128::from(self.denominator());
+
+        lhs.cmp(&rhs)
     }
 }
 
 impl From<f32> for CycleOffset {
+    #[inline]
     fn from(value: f32) -> Self {
-        if value.is_subnormal() {
-            return Self::default();
-        }
-
-        let in_offset = match (value.fract() * 4.).round() as u32 {
-            4 | 0 => InCycleOffset::Begin,
-            1 => InCycleOffset::Quarter,
-            2 => InCycleOffset::Half,
-            _ => InCycleOffset::ThreeQuarter,
-        };
-        let index = if value.fract() > 0.75 && in_offset == InCycleOffset::Begin {
-            value.ceil()
-        } else {
-            value.floor()
-        } as u32;
-
-        Self { index, in_offset }
+        Self::from(f64::from(value))
     }
 }
 
 impl From<f64> for CycleOffset {
+    /// Approximate a [`CycleOffset`] from a floating point number of cycles.
+    ///
+    /// The closest fraction with a denominator of at most
+    /// [`CycleOffset::MAX_APPROXIMATION_DENOMINATOR`] is taken. Values that are negative or not
+    /// finite result in the default [`CycleOffset`]. Values above [`u32::MAX`] are limited to it.
     fn from(value: f64) -> Self {
-        if value.is_subnormal() {
-            return Self::default();
+        if value > f64::from(u32::MAX) {
+            return Self::new_rounded(u32::MAX);
         }
 
-        let in_offset = match ((value * 4.).round() % 4.) as u32 {
-            0 => InCycleOffset::Begin,
-            1 => InCycleOffset::Quarter,
-            2 => InCycleOffset::Half,
-            _ => InCycleOffset::ThreeQuarter,
-        };
-        let index = if value.fract() > 0.75 && in_offset == InCycleOffset::Begin {
-            value.ceil()
-        } else {
-            value.floor()
-        } as u32;
+        approximate_fraction(value, Self::MAX_APPROXIMATION_DENOMINATOR)
+            .and_then(|(numerator, denominator)| Self::new(numerator, denominator))
+            .unwrap_or_default()
+    }
+}
 
-        Self { index, in_offset }
+impl From<Period> for CycleOffset {
+    #[inline]
+    fn from(period: Period) -> Self {
+        Self::new(period.numerator().into(), period.denominator().into()).unwrap_or_default()
     }
 }
 
@@ -90,21 +186,16 @@ impl Add for CycleOffset {
     type Output = Self;
 
     fn add(self, rhs: Self) -> Self::Output {
-        use InCycleOffset::*;
-
-        let (in_offset, carry) = match (self.in_offset, rhs.in_offset) {
-            (x, Begin) | (Begin, x) => (x, 0),
-            (Quarter, Quarter) => (Half, 0),
-            (Quarter, Half) | (Half, Quarter) => (ThreeQuarter, 0),
-            (Half, Half)
-            | (Quarter, ThreeQuarter)
-            | (ThreeQuarter, Quarter) => (Begin, 1),
-            (Half, ThreeQuarter) | (ThreeQuarter, Half) => (Quarter, 1),
-            (ThreeQuarter, ThreeQuarter) => (Half, 1),
-        };
-        let index = self.index + rhs.index + carry;
-
-        Self { index, in_offset }
+        let (lhs_denominator, rhs_denominator) = (
+            u128::from(self.denominator()),
+            u128::from(rhs.denominator()),
+        );
+
+        // The product of two `u64`s always fits, but their sum might not.
+        let numerator = (u128::from(self.numerator) * rhs_denominator)
+            .saturating_add(u128::from(rhs.numerator) * lhs_denominator);
+
+        Self::reduced(numerator, lhs_denominator * rhs_denominator)
     }
 }
 
@@ -115,78 +206,82 @@ impl AddAssign for CycleOffset {
     }
 }
 
-impl CycleOffset {
-    /// Create a new [`CycleOffset`] with a specific cycle cycle `index` and an in cycle cycle
-    /// offset `in_offset`
-    #[inline]
-    pub fn new(index: u32, in_offset: InCycleOffset) -> Self {
-        Self { index, in_offset }
-    }
-
-    /// Create a new [`CycleOffset`] that is rounded to an specific cycle cycle `index`
-    #[inline]
-    pub fn new_rounded(index: u32) -> Self {
-        Self {
-            index,
-            in_offset: InCycleOffset::Begin,
+impl Display for CycleOffset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.denominator() == 1 {
+            write!(f, "{}", self.numerator)
+        } else {
+            write!(f, "{}/{}", self.numerator, self.denominator)
         }
     }
+}
 
-    /// Get the a ceiled value of the number of cycles that the [`CycleOffset`] incorperates.
-    pub fn ceil_num_cycles(self) -> u32 {
-        self.index
-            + match self.in_offset {
-                InCycleOffset::Begin => 0,
-                _ => 1,
-            }
+/// Approximate a non-negative `value` with the closest fraction that has a denominator of at most
+/// `max_denominator`.
+///
+/// This walks the continued fraction expansion of `value` until the convergent is close enough or
+/// its denominator becomes too large. Returns `None` for values that are not finite, negative or
+/// too large.
+pub(crate) fn approximate_fraction(value: f64, max_denominator: u64) -> Option<(u64, u64)> {
+    if !value.is_finite() || value < 0.0 || value > f64::from(u32::MAX) {
+        return None;
     }
 
-    /// Get the `index` of the [`CycleOffset`]
-    #[inline]
-    pub fn cycle_index(self) -> u32 {
-        self.index
-    }
+    let (mut prev_num, mut num) = (0u64, 1u64);
+    let (mut prev_den, mut den) = (1u64, 0u64);
+    let mut remainder = value;
 
-    /// Get the in cycle offset of the [`CycleOffset`]
-    #[inline]
-    pub fn in_cycle_offset(self) -> InCycleOffset {
-        self.in_offset
-    }
+    loop {
+        let whole = remainder.floor();
+        let next_num = whole as u64 * num + prev_num;
+        let next_den = whole as u64 * den + prev_den;
 
-    /// Get the width knowning that a cycle cycle is `width` units wide.
-    #[inline]
-    pub fn width_offset(self, width: u32) -> u32 {
-        width * self.index + self.in_offset.width_offset(width)
-    }
+        if next_den > max_denominator {
+            break;
+        }
 
-    /// Half the [`CycleOffset`]
-    pub fn half(&self) -> CycleOffset {
-        use InCycleOffset::*;
+        (prev_num, num) = (num, next_num);
+        (prev_den, den) = (den, next_den);
 
-        if self.index % 2 == 0 {
-            Self::new(self.index / 2, match self.in_offset {
-                Begin | Quarter => Begin,
-                Half | ThreeQuarter => Quarter,
-            })
-        } else {
-            Self::new(self.index / 2, match self.in_offset {
-                Begin | Quarter => Half,
-                Half | ThreeQuarter => ThreeQuarter,
-            })
+        let fract = remainder - whole;
+        if fract < 1e-9 || (value - num as f64 / den as f64).abs() < 1e-6 {
+            break;
         }
+
+        remainder = 1.0 / fract;
     }
+
+    Some((num, den))
 }
 
-impl InCycleOffset {
-    /// Get the width knowning that a cycle cycle is `width` units wide.
-    pub fn width_offset(self, width: u32) -> u32 {
-        let w = f64::from(width);
+/// The greatest common divisor of `a` and `b`.
+pub(crate) fn gcd<T>(mut a: T, mut b: T) -> T
+where
+    T: Copy + Default + PartialEq + Rem<Output = T>,
+{
+    while b != T::default() {
+        (a, b) = (b, a % b);
+    }
 
-        match self {
-            Self::Begin => 0,
-            Self::Quarter => (w * 0.25).round() as u32,
-            Self::Half => (w * 0.5).round() as u32,
-            Self::ThreeQuarter => (w * 0.75).round() as u32,
-        }
+    a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_large_fractions() {
+        let large = CycleOffset::new(u64::MAX / 2, 3).unwrap();
+        assert_eq!(large + large, CycleOffset::new(u64::MAX - 1, 3).unwrap());
+
+        // A denominator that does not fit is scaled down with the numerator.
+        let a = CycleOffset::new(1, u64::MAX).unwrap();
+        let b = CycleOffset::new(1, u64::MAX - 1).unwrap();
+        assert!((a + b).as_f64() > 0.0);
+        assert!((a + b) >= a);
+
+        let max = CycleOffset::new(u64::MAX, 1).unwrap();
+        assert_eq!((max + max).numerator(), u64::MAX);
     }
 }
@@ -5,6 +5,11 @@
 //! uppercase ASCII character then is is not displayed on the diagram otherwise it is also shown on
 //! the diagram.
 //!
+//! Nodes can also be given a longer name by putting it between braces, e.g. `{req_rise}`. Such a
+//! [named node][EdgeNode::Named] takes up one position in the node string and is referred to with
+//! the same braces in an edge. Names that start with an uppercase ASCII character are not
+//! displayed.
+//!
 //! There are several types of edges, a full overview can be seen in the [wavedrom-rs book][book].
 //! Here they are represented with the [`EdgeVariant`] structure.
 //!
@@ -13,13 +18,27 @@
 //! node><edge identifier><end node> [label]`. The label is text that is put on the middle of the
 //! edge.
 //!
+//! A label can contain the `{dt}` placeholder. It is replaced by the distance between the two
+//! nodes, e.g. `tSU = {dt}` becomes `tSU = 2 cycles`. When the figure has a [`Timescale`], the
+//! distance is given as a time instead (e.g. `tSU = 20ns`).
+//!
+//! Edges are drawn with the [`EdgeOptions`][crate::signal::options::EdgeOptions] of the figure.
+//! Single edges can override the color, stroke width, dash pattern and arrowhead shape with an
+//! [`EdgeStyle`]. In WaveJson, such an edge is given as an object, e.g. `{ def: 'a~>b', color:
+//! '#F00', dash: 'dashed', arrow: 'open' }`.
+//!
 //! [book]: https://coastalwhite.github.io/wavedrom-rs
 
 use std::borrow::Cow;
 use std::collections::{HashMap, HashSet};
+use std::fmt;
 use std::str::FromStr;
 
-use super::{CycleOffset, Signal};
+use super::markers::LineDash;
+use super::path::state_offsets;
+use super::timescale::{display_unit, format_time};
+use super::{CycleOffset, Signal, Timescale};
+use crate::Color;
 
 /// A set of edge markers. Both the edge lines and the text_nodes.
 #[derive(Debug, Clone)]
@@ -32,18 +51,19 @@ pub struct LineEdgeMarkers<'a> {
 #[derive(Debug, Clone)]
 pub struct LineEdge<'a> {
     from: InSignalPosition,
-    from_marker: Option<char>,
+    from_marker: Option<&'a EdgeNode>,
     to: InSignalPosition,
-    to_marker: Option<char>,
+    to_marker: Option<&'a EdgeNode>,
     text: Option<Cow<'a, str>>,
     variant: EdgeVariant,
+    style: EdgeStyle,
 }
 
 /// The text belowing to a node
 #[derive(Debug, Clone)]
 pub struct LineEdgeText {
     at: InSignalPosition,
-    text: char,
+    text: EdgeNode,
 }
 
 /// A position in the signal schema. Containing both a `x` (cycle offset) value and a `y` (signal
@@ -59,9 +79,43 @@ pub struct InSignalPosition {
 #[cfg_attr(test, derive(PartialEq, Eq))]
 pub struct EdgeDefinition {
     variant: EdgeVariant,
-    from: char,
-    to: char,
+    from: EdgeNode,
+    to: EdgeNode,
     label: Option<String>,
+    style: EdgeStyle,
+}
+
+/// The style overrides for a single edge.
+///
+/// Everything that is not set is taken from the
+/// [`EdgeOptions`][crate::signal::options::EdgeOptions] of the figure.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct EdgeStyle {
+    color: Option<Color>,
+    width: Option<u32>,
+    dash: Option<LineDash>,
+    arrow_head: Option<EdgeArrowHead>,
+}
+
+/// The shape of the arrowheads of an edge.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum EdgeArrowHead {
+    /// A filled triangle.
+    #[default]
+    Filled,
+    /// An open chevron.
+    Open,
+    /// A filled circle.
+    Dot,
+}
+
+/// The identifier of a node that an edge starts or ends at
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum EdgeNode {
+    /// A node identified by a single character, e.g. `a`
+    Char(char),
+    /// A node identified by a name that is written between braces, e.g. `{req_rise}`
+    Named(String),
 }
 
 /// A variant of an edge
@@ -106,6 +160,30 @@ pub enum SharpEdgeVariant {
     Cross,
 }
 
+/// An error while parsing an [`EdgeDefinition`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EdgeParseError {
+    kind: EdgeParseErrorKind,
+    position: usize,
+}
+
+/// What went wrong while parsing an [`EdgeDefinition`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EdgeParseErrorKind {
+    /// The edge does not start with a node
+    MissingStartNode,
+    /// A named node has no name, e.g. `{}`
+    EmptyNodeName,
+    /// The edge identifier between the nodes is not known
+    UnknownOperator,
+    /// The `+` edge identifier was given an arrow, e.g. `<+`
+    CrossWithArrow,
+    /// There is no node after the edge identifier
+    MissingEndNode,
+    /// The end node is directly followed by other characters instead of whitespace
+    TrailingCharacters,
+}
+
 /// Structure that defines at which sides of an [`EdgeVariant`] there are arrows.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum EdgeArrowType {
@@ -121,7 +199,7 @@ pub enum EdgeArrowType {
 
 pub(crate) struct LineEdgeMarkersBuilder {
     line_number: u32,
-    node_positions: HashMap<char, InSignalPosition>,
+    node_positions: HashMap<EdgeNode, InSignalPosition>,
     text_nodes: Vec<LineEdgeText>,
 }
 
@@ -147,11 +225,131 @@ fn take_char(s: &str, c: char) -> (&str, bool) {
     }
 }
 
+/// Take a single character node or a named node between braces.
+///
+/// Returns `None` if there is no node and an error if the node has an empty name.
 #[inline]
-fn take(s: &str) -> Option<(&str, char)> {
+fn take_node(s: &str) -> Option<Result<(&str, EdgeNode), EdgeParseErrorKind>> {
+    if let Some((name, rest)) = s.strip_prefix('{').and_then(|s| s.split_once('}')) {
+        return Some(if name.is_empty() {
+            Err(EdgeParseErrorKind::EmptyNodeName)
+        } else {
+            Ok((rest, EdgeNode::from(name)))
+        });
+    }
+
     let mut chars = s.chars();
     let c = chars.next()?;
-    Some((chars.as_str(), c))
+    Some(Ok((chars.as_str(), EdgeNode::Char(c))))
+}
+
+/// Iterate over the positions of a node string. Each position is either empty (`.`), a single
+/// character node or a named node between braces.
+///
+/// An opening brace without a closing brace is read as a single character node.
+pub(crate) fn parse_nodes(nodes: &str) -> impl Iterator<Item = Option<EdgeNode>> + '_ {
+    let mut s = nodes;
+    std::iter::from_fn(move || {
+        if let Some((name, rest)) = s.strip_prefix('{').and_then(|s| s.split_once('}')) {
+            s = rest;
+            return Some((!name.is_empty()).then(|| EdgeNode::from(name)));
+        }
+
+        let mut chars = s.chars();
+        let c = chars.next()?;
+        s = chars.as_str();
+        Some((c != '.').then_some(EdgeNode::Char(c)))
+    })
+}
+
+impl EdgeArrowHead {
+    /// The names of the arrowhead shapes as used in WaveJson.
+    pub const NAMES: &'static [&'static str] = &["filled", "open", "dot"];
+
+    /// Get the arrowhead shape from its name in WaveJson.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "filled" => Self::Filled,
+            "open" => Self::Open,
+            "dot" => Self::Dot,
+            _ => return None,
+        })
+    }
+
+    /// Get the name of the arrowhead shape as used in WaveJson.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Filled => "filled",
+            Self::Open => "open",
+            Self::Dot => "dot",
+        }
+    }
+}
+
+impl EdgeStyle {
+    /// Create a new [`EdgeStyle`] that does not override anything.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the color of the line and the arrowheads.
+    #[inline]
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Set the stroke width of the line.
+    #[inline]
+    pub fn width(mut self, width: u32) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    /// Set the dash pattern of the line.
+    #[inline]
+    pub fn dash(mut self, dash: LineDash) -> Self {
+        self.dash = Some(dash);
+        self
+    }
+
+    /// Set the shape of the arrowheads.
+    #[inline]
+    pub fn arrow_head(mut self, arrow_head: EdgeArrowHead) -> Self {
+        self.arrow_head = Some(arrow_head);
+        self
+    }
+
+    /// Get the color of the line and the arrowheads, if it is overridden.
+    #[inline]
+    pub fn get_color(&self) -> Option<Color> {
+        self.color
+    }
+
+    /// Get the stroke width of the line, if it is overridden.
+    #[inline]
+    pub fn get_width(&self) -> Option<u32> {
+        self.width
+    }
+
+    /// Get the dash pattern of the line, if it is overridden.
+    #[inline]
+    pub fn get_dash(&self) -> Option<LineDash> {
+        self.dash
+    }
+
+    /// Get the shape of the arrowheads, if it is overridden.
+    #[inline]
+    pub fn get_arrow_head(&self) -> Option<EdgeArrowHead> {
+        self.arrow_head
+    }
+
+    /// Returns whether nothing is overridden.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
 }
 
 impl EdgeArrowType {
@@ -206,7 +404,7 @@ impl EdgeVariant {
         }
     }
 
-    fn consume(s: &str) -> Option<(&str, Self)> {
+    fn consume(s: &str) -> Result<(&str, Self), EdgeParseErrorKind> {
         let (s, has_arrow_left) = take_char(s, '<');
 
         match s.as_bytes() {
@@ -214,13 +412,13 @@ impl EdgeVariant {
                 let s = &s[3..];
                 let (s, has_arrow_right) = take_char(s, '>');
                 let arrow_type = EdgeArrowType::new(has_arrow_left, has_arrow_right);
-                Some((s, Self::Sharp(SharpEdgeVariant::BothHorizontal(arrow_type))))
+                Ok((s, Self::Sharp(SharpEdgeVariant::BothHorizontal(arrow_type))))
             }
             [b'-', b'|', ..] => {
                 let s = &s[2..];
                 let (s, has_arrow_right) = take_char(s, '>');
                 let arrow_type = EdgeArrowType::new(has_arrow_left, has_arrow_right);
-                Some((
+                Ok((
                     s,
                     Self::Sharp(SharpEdgeVariant::StartHorizontal(arrow_type)),
                 ))
@@ -229,13 +427,13 @@ impl EdgeVariant {
                 let s = &s[2..];
                 let (s, has_arrow_right) = take_char(s, '>');
                 let arrow_type = EdgeArrowType::new(has_arrow_left, has_arrow_right);
-                Some((s, Self::Sharp(SharpEdgeVariant::EndHorizontal(arrow_type))))
+                Ok((s, Self::Sharp(SharpEdgeVariant::EndHorizontal(arrow_type))))
             }
             [b'-', b'~', ..] => {
                 let s = &s[2..];
                 let (s, has_arrow_right) = take_char(s, '>');
                 let arrow_type = EdgeArrowType::new(has_arrow_left, has_arrow_right);
-                Some((
+                Ok((
                     s,
                     Self::Spline(SplineEdgeVariant::StartHorizontal(arrow_type)),
                 ))
@@ -244,7 +442,7 @@ impl EdgeVariant {
                 let s = &s[2..];
                 let (s, has_arrow_right) = take_char(s, '>');
                 let arrow_type = EdgeArrowType::new(has_arrow_left, has_arrow_right);
-                Some((
+                Ok((
                     s,
                     Self::Spline(SplineEdgeVariant::EndHorizontal(arrow_type)),
                 ))
@@ -253,29 +451,57 @@ impl EdgeVariant {
                 let s = &s[1..];
                 let (s, has_arrow_right) = take_char(s, '>');
                 let arrow_type = EdgeArrowType::new(has_arrow_left, has_arrow_right);
-                Some((s, Self::Sharp(SharpEdgeVariant::Straight(arrow_type))))
+                Ok((s, Self::Sharp(SharpEdgeVariant::Straight(arrow_type))))
             }
             [b'+', ..] => {
                 let s = &s[1..];
                 if has_arrow_left {
-                    return None;
+                    return Err(EdgeParseErrorKind::CrossWithArrow);
                 }
-                Some((s, Self::Sharp(SharpEdgeVariant::Cross)))
+                Ok((s, Self::Sharp(SharpEdgeVariant::Cross)))
             }
             [b'~', ..] => {
                 let s = &s[1..];
                 let (s, has_arrow_right) = take_char(s, '>');
                 let arrow_type = EdgeArrowType::new(has_arrow_left, has_arrow_right);
-                Some((
+                Ok((
                     s,
                     Self::Spline(SplineEdgeVariant::BothHorizontal(arrow_type)),
                 ))
             }
-            _ => None,
+            _ => Err(EdgeParseErrorKind::UnknownOperator),
         }
     }
 }
 
+impl fmt::Display for EdgeVariant {
+    /// Write the edge identifier of the [`EdgeVariant`] including its arrows, e.g. `<-|->`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let identifier = match self {
+            EdgeVariant::Spline(SplineEdgeVariant::BothHorizontal(_)) => "~",
+            EdgeVariant::Spline(SplineEdgeVariant::StartHorizontal(_)) => "-~",
+            EdgeVariant::Spline(SplineEdgeVariant::EndHorizontal(_)) => "~-",
+            EdgeVariant::Sharp(SharpEdgeVariant::Straight(_)) => "-",
+            EdgeVariant::Sharp(SharpEdgeVariant::BothHorizontal(_)) => "-|-",
+            EdgeVariant::Sharp(SharpEdgeVariant::StartHorizontal(_)) => "-|",
+            EdgeVariant::Sharp(SharpEdgeVariant::EndHorizontal(_)) => "|-",
+            EdgeVariant::Sharp(SharpEdgeVariant::Cross) => "+",
+        };
+
+        let arrow_type = self.arrow_type();
+
+        if arrow_type.has_start_arrow() {
+            f.write_str("<")?;
+        }
+        f.write_str(identifier)?;
+        if arrow_type.has_end_arrow() {
+            f.write_str(">")?;
+        }
+
+        Ok(())
+    }
+}
+
 impl LineEdgeMarkersBuilder {
     pub fn new() -> Self {
         Self {
@@ -288,24 +514,27 @@ impl LineEdgeMarkersBuilder {
     pub fn add_signal(&mut self, signal: &Signal) {
         let line_number = self.line_number;
 
-        for (i, c) in signal.get_nodes().chars().enumerate() {
-            if c == '.' {
-                continue;
-            }
+        let offsets = state_offsets(signal.cycles(), signal.get_period(), signal.get_phase());
 
-            let at = InSignalPosition {
-                x: signal.get_phase() + CycleOffset::new_rounded(i as u32),
-                y: line_number,
+        for (node, x) in parse_nodes(signal.get_nodes()).zip(offsets) {
+            let Some(node) = node else {
+                continue;
             };
 
-            self.node_positions.insert(c, at.clone());
-            self.text_nodes.push(LineEdgeText { at, text: c });
+            let at = InSignalPosition { x, y: line_number };
+
+            self.node_positions.insert(node.clone(), at.clone());
+            self.text_nodes.push(LineEdgeText { at, text: node });
         }
 
         self.line_number += 1;
     }
 
-    pub fn build(mut self, edges: &[EdgeDefinition]) -> LineEdgeMarkers {
+    pub fn build(
+        mut self,
+        edges: &[EdgeDefinition],
+        timescale: Option<Timescale>,
+    ) -> LineEdgeMarkers {
         let mut lines = Vec::new();
         let mut used_text_nodes = HashSet::new();
 
@@ -321,17 +550,25 @@ impl LineEdgeMarkersBuilder {
                 continue;
             };
 
-            used_text_nodes.insert(edge.from);
-            used_text_nodes.insert(edge.to);
+            used_text_nodes.insert(&edge.from);
+            used_text_nodes.insert(&edge.to);
 
             let from = from.clone();
             let to = to.clone();
 
-            let text = edge.label.as_ref().map(|text| Cow::Borrowed(&text[..]));
+            let text = edge.label.as_ref().map(|text| {
+                if text.contains(DURATION_PLACEHOLDER) {
+                    let duration = format_duration(from.x, to.x, timescale);
+                    Cow::Owned(text.replace(DURATION_PLACEHOLDER, &duration))
+                } else {
+                    Cow::Borrowed(&text[..])
+                }
+            });
             let variant = edge.variant;
+            let style = edge.style;
 
-            let from_marker = (!edge.from.is_ascii_uppercase()).then_some(edge.from);
-            let to_marker = (!edge.to.is_ascii_uppercase()).then_some(edge.to);
+            let from_marker = (!edge.from.is_hidden()).then_some(&edge.from);
+            let to_marker = (!edge.to.is_hidden()).then_some(&edge.to);
 
             lines.push(LineEdge {
                 from,
@@ -340,11 +577,12 @@ impl LineEdgeMarkersBuilder {
                 to_marker,
                 text,
                 variant,
+                style,
             });
         }
 
         self.text_nodes
-            .retain(|n| !used_text_nodes.contains(&n.text()) && !n.text().is_ascii_uppercase());
+            .retain(|n| !used_text_nodes.contains(n.text()) && !n.text().is_hidden());
 
         LineEdgeMarkers {
             lines,
@@ -353,6 +591,42 @@ impl LineEdgeMarkersBuilder {
     }
 }
 
+/// The placeholder in an edge label that is replaced by the distance between the nodes.
+const DURATION_PLACEHOLDER: &str = "{dt}";
+
+/// Format the distance between two offsets in cycles or, if there is a `timescale`, as a time.
+fn format_duration(from: CycleOffset, to: CycleOffset, timescale: Option<Timescale>) -> String {
+    // The exact distance as the fraction `numerator / denominator` of cycles
+    let lhs = u128::from(from.numerator()) * u128::from(to.denominator());
+    let rhs = u128::from(to.numerator()) * u128::from(from.denominator());
+    let numerator = lhs.abs_diff(rhs);
+    let denominator = u128::from(from.denominator()) * u128::from(to.denominator());
+
+    if let Some(timescale) = timescale {
+        let cycle_time = timescale.as_femtoseconds();
+        let time = (numerator * cycle_time + denominator / 2) / denominator;
+        let unit = display_unit(if time == 0 { cycle_time } else { time });
+        return format_time(time, unit);
+    }
+
+    let integer = numerator / denominator;
+    // The thousandths of a cycle, rounded to the nearest.
+    let fraction = ((numerator % denominator) * 1000 + denominator / 2) / denominator;
+    let (integer, fraction) = if fraction == 1000 {
+        (integer + 1, 0)
+    } else {
+        (integer, fraction)
+    };
+
+    if fraction == 0 {
+        let suffix = if integer == 1 { "cycle" } else { "cycles" };
+        format!("{integer} {suffix}")
+    } else {
+        let fraction = format!("{fraction:03}");
+        format!("{integer}.{} cycles", fraction.trim_end_matches('0'))
+    }
+}
+
 impl LineEdgeMarkers<'_> {
     /// The edge lines for a [`LineEdgeMarkers`]
     pub fn lines(&self) -> &[LineEdge] {
@@ -380,13 +654,13 @@ impl LineEdge<'_> {
 
     /// The marker at the start of the edge line
     #[inline]
-    pub fn from_marker(&self) -> Option<char> {
+    pub fn from_marker(&self) -> Option<&EdgeNode> {
         self.from_marker
     }
 
     /// The marker at the end of the edge line
     #[inline]
-    pub fn to_marker(&self) -> Option<char> {
+    pub fn to_marker(&self) -> Option<&EdgeNode> {
         self.to_marker
     }
 
@@ -401,6 +675,12 @@ impl LineEdge<'_> {
     pub fn label(&self) -> Option<&str> {
         self.text.as_ref().map(|s| &s[..])
     }
+
+    /// The style overrides of the edge line
+    #[inline]
+    pub fn style(&self) -> &EdgeStyle {
+        &self.style
+    }
 }
 
 impl LineEdgeText {
@@ -410,10 +690,67 @@ impl LineEdgeText {
         &self.at
     }
 
-    /// The text content of the node
+    /// The node that is shown
+    #[inline]
+    pub fn text(&self) -> &EdgeNode {
+        &self.text
+    }
+}
+
+impl EdgeNode {
+    /// Returns whether the node is not displayed on the diagram. This is the case for nodes that
+    /// start with an uppercase ASCII character.
+    pub fn is_hidden(&self) -> bool {
+        match self {
+            Self::Char(c) => c.is_ascii_uppercase(),
+            Self::Named(name) => name.starts_with(|c: char| c.is_ascii_uppercase()),
+        }
+    }
+
+    /// The text that is displayed for the node. This is the character or the name without braces.
+    pub fn name(&self) -> Cow<'_, str> {
+        match self {
+            Self::Char(c) => Cow::Owned(c.to_string()),
+            Self::Named(name) => Cow::Borrowed(name),
+        }
+    }
+}
+
+impl From<char> for EdgeNode {
     #[inline]
-    pub fn text(&self) -> char {
-        self.text
+    fn from(c: char) -> Self {
+        Self::Char(c)
+    }
+}
+
+impl From<&str> for EdgeNode {
+    /// Create a [`EdgeNode::Named`] node. A name that consists of one character gives a
+    /// [`EdgeNode::Char`] node.
+    fn from(name: &str) -> Self {
+        let mut chars = name.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Self::Char(c),
+            _ => Self::Named(name.to_string()),
+        }
+    }
+}
+
+impl From<String> for EdgeNode {
+    /// Create a [`EdgeNode::Named`] node. A name that consists of one character gives a
+    /// [`EdgeNode::Char`] node.
+    #[inline]
+    fn from(name: String) -> Self {
+        Self::from(name.as_str())
+    }
+}
+
+impl fmt::Display for EdgeNode {
+    /// Write the node as it is written in a node string or an edge, e.g. `a` or `{req_rise}`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Char(c) => write!(f, "{c}"),
+            Self::Named(name) => write!(f, "{{{name}}}"),
+        }
     }
 }
 
@@ -431,44 +768,170 @@ impl InSignalPosition {
 
 impl EdgeDefinition {
     /// Create a new [`EdgeDefinition`] from a set of parameters
-    pub fn new(variant: EdgeVariant, from: char, to: char, label: Option<String>) -> Self {
-        Self { variant, from, to, label }
+    ///
+    /// The nodes can be given as a `char` or as the name of a [named node][EdgeNode::Named].
+    pub fn new(
+        variant: EdgeVariant,
+        from: impl Into<EdgeNode>,
+        to: impl Into<EdgeNode>,
+        label: Option<String>,
+    ) -> Self {
+        Self {
+            variant,
+            from: from.into(),
+            to: to.into(),
+            label,
+            style: EdgeStyle::default(),
+        }
     }
-}
 
-impl FromStr for EdgeDefinition {
-    type Err = usize;
+    /// Set the style overrides of the edge.
+    #[inline]
+    pub fn with_style(mut self, style: EdgeStyle) -> Self {
+        self.style = style;
+        self
+    }
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let start_len = s.len();
-        let s = s.trim_start();
-        let start_idx = start_len - s.len();
+    /// The variant of the edge
+    #[inline]
+    pub fn variant(&self) -> &EdgeVariant {
+        &self.variant
+    }
 
-        let (s, from) = take(s).ok_or(start_idx)?;
+    /// The node that the edge starts at
+    #[inline]
+    pub fn from_node(&self) -> &EdgeNode {
+        &self.from
+    }
 
-        let start_len = s.len();
-        let s = s.trim_start();
-        let start_idx = start_idx + start_len - s.len();
+    /// The node that the edge ends at
+    #[inline]
+    pub fn to_node(&self) -> &EdgeNode {
+        &self.to
+    }
+
+    /// The label of the edge
+    #[inline]
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
+    /// The style overrides of the edge
+    #[inline]
+    pub fn style(&self) -> &EdgeStyle {
+        &self.style
+    }
+
+    /// Parse an [`EdgeDefinition`] and also return the byte indices of the `from` and `to` nodes.
+    pub(crate) fn parse_with_positions(
+        input: &str,
+    ) -> Result<(Self, usize, usize), EdgeParseError> {
+        let offset = |rest: &str| input.len() - rest.len();
+        let error = |kind, rest: &str| EdgeParseError {
+            kind,
+            position: offset(rest),
+        };
+
+        let s = input.trim_start();
+        let from_idx = offset(s);
+        let (s, from) = take_node(s)
+            .ok_or(EdgeParseErrorKind::MissingStartNode)
+            .and_then(|node| node)
+            .map_err(|kind| error(kind, s))?;
 
-        let (s, variant) = EdgeVariant::consume(s).ok_or(start_idx)?;
+        let s = s.trim_start();
+        let (s, variant) = EdgeVariant::consume(s).map_err(|kind| error(kind, s))?;
 
-        let start_len = s.len();
         let s = s.trim_start();
-        let start_idx = start_idx + start_len - s.len();
+        let to_idx = offset(s);
+        let (s, to) = take_node(s)
+            .ok_or(EdgeParseErrorKind::MissingEndNode)
+            .and_then(|node| node)
+            .map_err(|kind| error(kind, s))?;
+
+        if s.starts_with(|c: char| !c.is_whitespace()) {
+            return Err(error(EdgeParseErrorKind::TrailingCharacters, s));
+        }
+
+        let label = (!s.is_empty()).then_some(s.trim_start().to_string());
+
+        Ok((
+            Self {
+                variant,
+                from,
+                to,
+                label,
+                style: EdgeStyle::default(),
+            },
+            from_idx,
+            to_idx,
+        ))
+    }
+}
 
-        let (s, to) = take(s).ok_or(start_idx)?;
+impl fmt::Display for EdgeDefinition {
+    /// Write the [`EdgeDefinition`] in the WaveJson edge syntax, e.g. `a<-|->b label`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.from, self.variant)?;
 
-        let text = (!s.is_empty()).then_some(s.trim_start().to_string());
+        // Separate the end node if it would otherwise be read as part of the edge identifier
+        if matches!(self.to, EdgeNode::Char('<' | '>' | '-' | '|' | '~' | '+')) {
+            f.write_str(" ")?;
+        }
+        write!(f, "{}", self.to)?;
 
-        Ok(Self {
-            variant,
-            from,
-            to,
-            label: text,
+        if let Some(label) = &self.label {
+            write!(f, " {label}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl EdgeParseError {
+    /// What went wrong
+    #[inline]
+    pub fn kind(&self) -> EdgeParseErrorKind {
+        self.kind
+    }
+
+    /// The byte index in the input where the problem was found
+    #[inline]
+    pub fn position(&self) -> usize {
+        self.position
+    }
+}
+
+impl fmt::Display for EdgeParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::MissingStartNode => "missing start node",
+            Self::EmptyNodeName => "named node without a name",
+            Self::UnknownOperator => "unknown edge operator",
+            Self::CrossWithArrow => "the '+' edge operator cannot have arrows",
+            Self::MissingEndNode => "missing end node",
+            Self::TrailingCharacters => "the end node must be followed by whitespace",
         })
     }
 }
 
+impl fmt::Display for EdgeParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at byte {}", self.kind, self.position)
+    }
+}
+
+impl std::error::Error for EdgeParseError {}
+
+impl FromStr for EdgeDefinition {
+    type Err = EdgeParseError;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with_positions(s).map(|(definition, _, _)| definition)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -484,7 +947,7 @@ mod tests {
 
                 let out = EdgeVariant::consume($input);
 
-                assert!(out.is_some());
+                assert!(out.is_ok());
 
                 let (out_str, out) = out.unwrap();
 
@@ -492,7 +955,7 @@ mod tests {
                 assert_eq!(out, $output);
             };
             ($input:literal) => {
-                assert!(EdgeVariant::consume($input).is_none());
+                assert!(EdgeVariant::consume($input).is_err());
             };
         }
 
@@ -519,8 +982,8 @@ mod tests {
 
                 let out = out.unwrap();
 
-                assert_eq!(out.from, $from);
-                assert_eq!(out.to, $to);
+                assert_eq!(out.from, EdgeNode::from($from));
+                assert_eq!(out.to, EdgeNode::from($to));
                 assert_eq!(out.variant, $edge_variant);
                 assert_eq!(out.label, Some($text).map(Into::into));
             };
@@ -534,8 +997,8 @@ mod tests {
 
                 let out = out.unwrap();
 
-                assert_eq!(out.from, $from);
-                assert_eq!(out.to, $to);
+                assert_eq!(out.from, EdgeNode::from($from));
+                assert_eq!(out.to, EdgeNode::from($to));
                 assert_eq!(out.variant, $edge_variant);
                 assert!(out.label.is_none());
             };
@@ -547,5 +1010,69 @@ mod tests {
         assert_edge_def!("I+J abc" => 'I', 'J', Sharp(SharpEdgeVariant::Cross), "abc");
         assert_edge_def!("I<+J abc");
         assert_edge_def!("<+J" => '<', 'J', Sharp(SharpEdgeVariant::Cross));
+        assert_edge_def!("{req_rise}~>{ack} t1" => "req_rise", "ack", Spline(SplineEdgeVariant::BothHorizontal(EdgeArrowType::End)), "t1");
+        assert_edge_def!("a-{Ack}" => 'a', "Ack", Sharp(SharpEdgeVariant::Straight(EdgeArrowType::None)));
+        assert_edge_def!("{}-a");
+    }
+
+    #[test]
+    fn edge_definition_errors() {
+        let kind = |input: &str| {
+            let error = input.parse::<EdgeDefinition>().unwrap_err();
+            (error.kind(), error.position())
+        };
+
+        use EdgeParseErrorKind::*;
+        assert_eq!(kind("  "), (MissingStartNode, 2));
+        assert_eq!(kind("{}~>a"), (EmptyNodeName, 0));
+        assert_eq!(kind("a?b"), (UnknownOperator, 1));
+        assert_eq!(kind("a <+ b"), (CrossWithArrow, 2));
+        assert_eq!(kind("a~> "), (MissingEndNode, 4));
+        assert_eq!(kind("a~>b)"), (TrailingCharacters, 4));
+        assert_eq!(kind("a~>{ack}label"), (TrailingCharacters, 8));
+    }
+
+    #[test]
+    fn named_nodes() {
+        let nodes: Vec<_> = parse_nodes(".{req_rise}a.{B}{x").collect();
+        assert_eq!(
+            nodes,
+            [
+                None,
+                Some(EdgeNode::Named("req_rise".into())),
+                Some(EdgeNode::Char('a')),
+                None,
+                Some(EdgeNode::Char('B')),
+                Some(EdgeNode::Char('{')),
+                Some(EdgeNode::Char('x')),
+            ]
+        );
+
+        assert!(EdgeNode::from("Ack").is_hidden());
+        assert!(!EdgeNode::from("ack").is_hidden());
+
+        let edge: EdgeDefinition = "{req_rise}<->{ack} 2 cycles".parse().unwrap();
+        assert_edge_def_round_trip(&edge);
+    }
+
+    #[test]
+    fn duration_labels() {
+        let dt = |from: f64, to: f64, timescale: Option<Timescale>| {
+            format_duration(CycleOffset::from(from), CycleOffset::from(to), timescale)
+        };
+
+        assert_eq!(dt(1.0, 3.0, None), "2 cycles");
+        assert_eq!(dt(3.0, 2.0, None), "1 cycle");
+        assert_eq!(dt(0.5, 2.0, None), "1.5 cycles");
+        assert_eq!(dt(0.0, 1.0 / 3.0, None), "0.333 cycles");
+
+        let timescale = "10ns".parse().ok();
+        assert_eq!(dt(1.0, 3.0, timescale), "20ns");
+        assert_eq!(dt(1.0, 1.25, timescale), "2.5ns");
+        assert_eq!(dt(0.0, 200.0, timescale), "2us");
+    }
+
+    fn assert_edge_def_round_trip(edge: &EdgeDefinition) {
+        assert_eq!(&edge.to_string().parse::<EdgeDefinition>().unwrap(), edge);
     }
 }
@@ -1,7 +1,15 @@
-use super::edges::{EdgeDefinition, EdgeVariant, LineEdgeMarkersBuilder};
-use super::markers::{CycleEnumerationMarker, GroupMarker};
+use super::edges::{EdgeDefinition, EdgeNode, EdgeVariant, LineEdgeMarkersBuilder};
+use super::markers::{
+    CycleEnumerationMarker, GroupMarker, HighlightRegion, TimeRulerPosition, VerticalMarker,
+};
 use super::options::PathAssembleOptions;
-use super::{AssembledFigure, AssembledLine, CycleState, DefinitionTracker, Signal, SignalPath};
+use super::{
+    AssembledFigure, AssembledLine, CycleState, DefinitionTracker, Signal, SignalPath, Timescale,
+};
+use crate::text::RichText;
+
+#[cfg(all(feature = "skins", feature = "json5"))]
+use crate::skin::Skin;
 
 impl Default for SignalFigure {
     fn default() -> Self {
@@ -11,6 +19,11 @@ impl Default for SignalFigure {
             top_cycle_marker: None,
             bottom_cycle_marker: None,
             hscale: 1,
+            skin: None,
+            timescale: None,
+            time_ruler: TimeRulerPosition::default(),
+            markers: Vec::new(),
+            highlights: Vec::new(),
             edges: Vec::new(),
             sections: Vec::new(),
         }
@@ -56,14 +69,20 @@ impl Default for SignalFigure {
 /// [dtd]: https://en.wikipedia.org/wiki/Digital_timing_diagram
 #[derive(Debug, Clone)]
 pub struct SignalFigure {
-    header_text: Option<String>,
-    footer_text: Option<String>,
+    header_text: Option<RichText>,
+    footer_text: Option<RichText>,
 
     top_cycle_marker: Option<CycleEnumerationMarker>,
     bottom_cycle_marker: Option<CycleEnumerationMarker>,
 
     hscale: u16,
+    skin: Option<String>,
+
+    timescale: Option<Timescale>,
+    time_ruler: TimeRulerPosition,
 
+    markers: Vec<VerticalMarker>,
+    highlights: Vec<HighlightRegion>,
     edges: Vec<EdgeDefinition>,
 
     sections: Vec<SignalFigureSection>,
@@ -87,13 +106,25 @@ impl SignalFigureSectionGroup {
     pub fn new(label: Option<String>, items: Vec<SignalFigureSection>) -> SignalFigureSectionGroup {
         Self(label, items)
     }
+
+    /// Get the label of the group.
+    #[inline]
+    pub fn label(&self) -> Option<&str> {
+        self.0.as_deref()
+    }
+
+    /// Get the sections within the group.
+    #[inline]
+    pub fn items(&self) -> &[SignalFigureSection] {
+        &self.1
+    }
 }
 
 impl SignalFigure {
     /// Create a new [`Figure`] with a set of parameters.
     pub fn with(
-        title: Option<String>,
-        footer: Option<String>,
+        title: Option<RichText>,
+        footer: Option<RichText>,
 
         top_cycle_marker: Option<CycleEnumerationMarker>,
         bottom_cycle_marker: Option<CycleEnumerationMarker>,
@@ -113,6 +144,11 @@ impl SignalFigure {
             edges,
 
             hscale,
+            skin: None,
+            timescale: None,
+            time_ruler: TimeRulerPosition::default(),
+            markers: Vec::new(),
+            highlights: Vec::new(),
             sections,
         }
     }
@@ -125,14 +161,14 @@ impl SignalFigure {
 
     /// Give a header text to the [`Figure`].
     #[inline]
-    pub fn header_text(mut self, header_text: impl Into<String>) -> Self {
+    pub fn header_text(mut self, header_text: impl Into<RichText>) -> Self {
         self.header_text = Some(header_text.into());
         self
     }
 
     /// Give a footer text to the [`Figure`].
     #[inline]
-    pub fn footer_text(mut self, footer_text: impl Into<String>) -> Self {
+    pub fn footer_text(mut self, footer_text: impl Into<RichText>) -> Self {
         self.footer_text = Some(footer_text.into());
         self
     }
@@ -160,12 +196,69 @@ impl SignalFigure {
         self
     }
 
+    /// Select one of the built-in skins for the [`Figure`] by its name.
+    ///
+    /// The skin is applied on top of the options given when assembling and rendering the
+    /// [`Figure`]. Unknown skin names are ignored. The available skins are listed in
+    /// [`Skin::NAMES`][crate::skin::Skin::NAMES].
+    ///
+    /// The skin is only applied when both the `skins` and `json5` features are enabled, because
+    /// the built-in skins are stored as JSON5. Otherwise, it is ignored.
+    #[inline]
+    pub fn skin(mut self, skin: impl Into<String>) -> Self {
+        self.skin = Some(skin.into());
+        self
+    }
+
+    /// Give the duration of one cycle to the [`SignalFigure`].
+    ///
+    /// With a time base, a time ruler with the absolute times is drawn along the signals. Where
+    /// the ruler is drawn is set with [`SignalFigure::time_ruler`].
+    #[inline]
+    pub fn timescale(mut self, timescale: Timescale) -> Self {
+        self.timescale = Some(timescale);
+        self
+    }
+
+    /// Set where the time ruler of the [`SignalFigure`] is drawn.
+    ///
+    /// The time ruler is only drawn when the figure has a [`timescale`][SignalFigure::timescale].
+    #[inline]
+    pub fn time_ruler(mut self, position: TimeRulerPosition) -> Self {
+        self.time_ruler = position;
+        self
+    }
+
+    /// Add a [`VerticalMarker`] spanning all signals to the [`SignalFigure`].
+    pub fn add_marker(mut self, marker: VerticalMarker) -> Self {
+        self.markers.push(marker);
+        self
+    }
+
+    /// Add a set of [`VerticalMarker`]s spanning all signals to the [`SignalFigure`].
+    pub fn add_markers(mut self, markers: impl IntoIterator<Item = VerticalMarker>) -> Self {
+        self.markers.extend(markers);
+        self
+    }
+
+    /// Add a [`HighlightRegion`] to the [`SignalFigure`].
+    pub fn add_highlight(mut self, highlight: HighlightRegion) -> Self {
+        self.highlights.push(highlight);
+        self
+    }
+
+    /// Add a set of [`HighlightRegion`]s to the [`SignalFigure`].
+    pub fn add_highlights(mut self, highlights: impl IntoIterator<Item = HighlightRegion>) -> Self {
+        self.highlights.extend(highlights);
+        self
+    }
+
     /// Add a labeled arrow / edge with a set of parameters to the [`Figure`].
     pub fn add_labeled_edge_with(
         mut self,
         variant: EdgeVariant,
-        from: char,
-        to: char,
+        from: impl Into<EdgeNode>,
+        to: impl Into<EdgeNode>,
         label: impl Into<String>,
     ) -> Self {
         self.edges
@@ -174,7 +267,12 @@ impl SignalFigure {
     }
 
     /// Add a unlabeled arrow / edge with a set of parameters to the [`Figure`].
-    pub fn add_edge_with(mut self, variant: EdgeVariant, from: char, to: char) -> Self {
+    pub fn add_edge_with(
+        mut self,
+        variant: EdgeVariant,
+        from: impl Into<EdgeNode>,
+        to: impl Into<EdgeNode>,
+    ) -> Self {
         self.edges
             .push(EdgeDefinition::new(variant, from, to, None));
         self
@@ -233,6 +331,78 @@ impl SignalFigure {
         self
     }
 
+    /// Get the header text of the [`SignalFigure`].
+    #[inline]
+    pub fn get_header_text(&self) -> Option<&RichText> {
+        self.header_text.as_ref()
+    }
+
+    /// Get the footer text of the [`SignalFigure`].
+    #[inline]
+    pub fn get_footer_text(&self) -> Option<&RichText> {
+        self.footer_text.as_ref()
+    }
+
+    /// Get the top cycle enumeration marker of the [`SignalFigure`].
+    #[inline]
+    pub fn get_top_cycle_marker(&self) -> Option<CycleEnumerationMarker> {
+        self.top_cycle_marker
+    }
+
+    /// Get the bottom cycle enumeration marker of the [`SignalFigure`].
+    #[inline]
+    pub fn get_bottom_cycle_marker(&self) -> Option<CycleEnumerationMarker> {
+        self.bottom_cycle_marker
+    }
+
+    /// Get the horizontal scale of the [`SignalFigure`].
+    #[inline]
+    pub fn get_horizontal_scale(&self) -> u16 {
+        self.hscale
+    }
+
+    /// Get the name of the built-in skin of the [`SignalFigure`].
+    #[inline]
+    pub fn get_skin(&self) -> Option<&str> {
+        self.skin.as_deref()
+    }
+
+    /// Get the duration of one cycle of the [`SignalFigure`].
+    #[inline]
+    pub fn get_timescale(&self) -> Option<Timescale> {
+        self.timescale
+    }
+
+    /// Get where the time ruler of the [`SignalFigure`] is drawn.
+    #[inline]
+    pub fn get_time_ruler(&self) -> TimeRulerPosition {
+        self.time_ruler
+    }
+
+    /// Get the vertical markers of the [`SignalFigure`].
+    #[inline]
+    pub fn get_markers(&self) -> &[VerticalMarker] {
+        &self.markers
+    }
+
+    /// Get the highlight regions of the [`SignalFigure`].
+    #[inline]
+    pub fn get_highlights(&self) -> &[HighlightRegion] {
+        &self.highlights
+    }
+
+    /// Get the arrows / edges of the [`SignalFigure`].
+    #[inline]
+    pub fn get_edges(&self) -> &[EdgeDefinition] {
+        &self.edges
+    }
+
+    /// Get the sections of the [`SignalFigure`].
+    #[inline]
+    pub fn get_sections(&self) -> &[SignalFigureSection] {
+        &self.sections
+    }
+
     /// Form the signal paths and fetch information needed for rendering with a certain set of
     /// [`PathAssembleOptions`].
     ///
@@ -247,9 +417,17 @@ impl SignalFigure {
         let top_cycle_marker = self.top_cycle_marker;
         let bottom_cycle_marker = self.bottom_cycle_marker;
         let hscale = self.hscale;
+        let timescale = self.timescale;
+        let time_ruler = self.time_ruler;
 
-        let header_text = self.header_text.as_ref().map(|s| &s[..]);
-        let footer_text = self.footer_text.as_ref().map(|s| &s[..]);
+        let header_text = self.header_text.as_ref();
+        let footer_text = self.footer_text.as_ref();
+        let skin = self.skin.as_deref();
+
+        #[cfg(all(feature = "skins", feature = "json5"))]
+        if let Some(assemble) = skin.and_then(Skin::named).and_then(|skin| skin.assemble) {
+            options.merge_in(assemble);
+        }
 
         options.cycle_width *= hscale;
 
@@ -303,10 +481,11 @@ impl SignalFigure {
                         path: SignalPath::new(
                             signal.cycles(),
                             signal.get_data_fields(),
-                            signal.get_period_internal(),
+                            signal.get_period(),
                             signal.get_phase(),
                         )
                         .assemble_with_options(options),
+                        signal,
                     });
                 }
                 SectionItem::GroupStart(depth, group) => {
@@ -332,7 +511,7 @@ impl SignalFigure {
             }
         }
 
-        let line_edge_markers = line_edge_markers.build(&self.edges);
+        let line_edge_markers = line_edge_markers.build(&self.edges, self.timescale);
 
         let num_cycles = lines
             .iter()
@@ -356,6 +535,14 @@ impl SignalFigure {
             top_cycle_marker,
             bottom_cycle_marker,
 
+            timescale,
+            time_ruler,
+
+            vertical_markers: &self.markers,
+            highlights: &self.highlights,
+
+            skin,
+
             path_assemble_options: options,
 
             lines,
@@ -1,6 +1,7 @@
 //! A collection of markers that get overlayed onto the signal diagram.
 
 use super::CycleOffset;
+use crate::Color;
 
 /// A marker for a group of [`AssembledLine`][crate::AssembledLine]s.
 ///
@@ -41,6 +42,44 @@ pub struct ClockEdgeMarker {
     edge: ClockEdge,
 }
 
+/// The dash pattern of a line.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum LineDash {
+    /// A continuous line.
+    Solid,
+    /// A line of long dashes.
+    #[default]
+    Dashed,
+    /// A line of dots.
+    Dotted,
+}
+
+/// A named vertical line spanning all the signals at a point in time.
+///
+/// This is usually used to mark an instant such as `T0` or a sample point. The label is shown above
+/// the signals.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerticalMarker {
+    at: CycleOffset,
+    label: String,
+    color: Option<Color>,
+    dash: LineDash,
+}
+
+/// A shaded rectangle that highlights the cycles `from..to` of some or all signals.
+///
+/// This is usually used to emphasize a part of a diagram such as a burst or a wait state. The
+/// label is shown above the signals.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HighlightRegion {
+    from: CycleOffset,
+    to: CycleOffset,
+    lanes: Vec<u32>,
+    label: String,
+    color: Option<Color>,
+    opacity: Option<f32>,
+}
+
 impl<'a> GroupMarker<'a> {
     /// Create a new [`GroupMarker`] capturing lines `start..end`.
     #[inline]
@@ -149,3 +188,194 @@ impl ClockEdgeMarker {
         self.edge
     }
 }
+
+impl LineDash {
+    /// The names of the dash patterns as used in WaveJson.
+    pub const NAMES: &'static [&'static str] = &["solid", "dashed", "dotted"];
+
+    /// Get the dash pattern from its name in WaveJson.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "solid" => Self::Solid,
+            "dashed" => Self::Dashed,
+            "dotted" => Self::Dotted,
+            _ => return None,
+        })
+    }
+
+    /// Get the name of the dash pattern as used in WaveJson.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Solid => "solid",
+            Self::Dashed => "dashed",
+            Self::Dotted => "dotted",
+        }
+    }
+
+    /// Get the value of the SVG `stroke-dasharray` attribute, if the line is not solid.
+    pub fn dasharray(self) -> Option<&'static str> {
+        match self {
+            Self::Solid => None,
+            Self::Dashed => Some("6,3"),
+            Self::Dotted => Some("1,2"),
+        }
+    }
+}
+
+impl VerticalMarker {
+    /// Create a new [`VerticalMarker`] at `at` with a `label` and the default look.
+    ///
+    /// An empty `label` shows no label.
+    #[inline]
+    pub fn new(at: CycleOffset, label: impl Into<String>) -> Self {
+        Self::with(at, label, None, LineDash::default())
+    }
+
+    /// Create a new [`VerticalMarker`] with a set of parameters.
+    ///
+    /// Without a `color`, the color of the render options is used.
+    #[inline]
+    pub fn with(
+        at: CycleOffset,
+        label: impl Into<String>,
+        color: Option<Color>,
+        dash: LineDash,
+    ) -> Self {
+        Self {
+            at,
+            label: label.into(),
+            color,
+            dash,
+        }
+    }
+
+    /// Returns where in the signals the [`VerticalMarker`] is positioned.
+    #[inline]
+    pub fn at(&self) -> CycleOffset {
+        self.at
+    }
+
+    /// Returns the label of the [`VerticalMarker`].
+    #[inline]
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// Returns the color of the [`VerticalMarker`], if it overrides the render options.
+    #[inline]
+    pub fn color(&self) -> Option<Color> {
+        self.color
+    }
+
+    /// Returns the dash pattern of the [`VerticalMarker`].
+    #[inline]
+    pub fn dash(&self) -> LineDash {
+        self.dash
+    }
+}
+
+impl HighlightRegion {
+    /// Create a new [`HighlightRegion`] over the cycles `from..to` of all signals with the default
+    /// look.
+    #[inline]
+    pub fn new(from: CycleOffset, to: CycleOffset) -> Self {
+        Self::with(from, to, Vec::new(), String::new(), None, None)
+    }
+
+    /// Create a new [`HighlightRegion`] with a set of parameters.
+    ///
+    /// The `lanes` are the indices of the highlighted signal lines, starting from `0`. With no
+    /// `lanes`, all signals are highlighted. An empty `label` shows no label. Without a `color` or
+    /// an `opacity`, the ones of the render options are used.
+    #[inline]
+    pub fn with(
+        from: CycleOffset,
+        to: CycleOffset,
+        lanes: Vec<u32>,
+        label: impl Into<String>,
+        color: Option<Color>,
+        opacity: Option<f32>,
+    ) -> Self {
+        Self {
+            from,
+            to,
+            lanes,
+            label: label.into(),
+            color,
+            opacity,
+        }
+    }
+
+    /// Returns where the [`HighlightRegion`] starts.
+    #[inline]
+    pub fn start(&self) -> CycleOffset {
+        self.from
+    }
+
+    /// Returns where the [`HighlightRegion`] ends.
+    #[inline]
+    pub fn end(&self) -> CycleOffset {
+        self.to
+    }
+
+    /// Returns the indices of the highlighted signal lines. This is empty if all signals are
+    /// highlighted.
+    #[inline]
+    pub fn lanes(&self) -> &[u32] {
+        &self.lanes
+    }
+
+    /// Returns the label of the [`HighlightRegion`].
+    #[inline]
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// Returns the fill color of the [`HighlightRegion`], if it overrides the render options.
+    #[inline]
+    pub fn color(&self) -> Option<Color> {
+        self.color
+    }
+
+    /// Returns the fill opacity of the [`HighlightRegion`], if it overrides the render options.
+    #[inline]
+    pub fn opacity(&self) -> Option<f32> {
+        self.opacity
+    }
+}
+
+/// Where the time ruler of a figure with a [`Timescale`][super::Timescale] is drawn.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum TimeRulerPosition {
+    /// Above the signals, below the header.
+    Top,
+    /// Below the signals, above the footer.
+    #[default]
+    Bottom,
+    /// The time ruler is not drawn. The time base is still used for the durations.
+    Hidden,
+}
+
+impl TimeRulerPosition {
+    /// The names of the positions as used in WaveJson.
+    pub const NAMES: &'static [&'static str] = &["top", "bottom", "none"];
+
+    /// Get the position from its name in WaveJson.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "top" => Self::Top,
+            "bottom" => Self::Bottom,
+            "none" => Self::Hidden,
+            _ => return None,
+        })
+    }
+
+    /// Get the name of the position as used in WaveJson.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Top => "top",
+            Self::Bottom => "bottom",
+            Self::Hidden => "none",
+        }
+    }
+}
@@ -6,27 +6,34 @@ mod figure;
 pub mod markers;
 pub mod options;
 mod path;
+mod period;
 mod render;
+mod timescale;
 
-pub use cycle_offset::{CycleOffset, InCycleOffset};
+#[cfg(feature = "vcd")]
+pub(crate) use cycle_offset::gcd;
+pub use cycle_offset::CycleOffset;
 pub use figure::{SignalFigure, SignalFigureSection, SignalFigureSectionGroup};
 pub use path::*;
-
-use std::num::NonZeroU16;
+pub use period::Period;
+pub use timescale::{TimeUnit, Timescale};
 
 use edges::LineEdgeMarkers;
-use markers::{CycleEnumerationMarker, GroupMarker};
+use markers::{
+    CycleEnumerationMarker, GroupMarker, HighlightRegion, TimeRulerPosition, VerticalMarker,
+};
 
 use self::options::PathAssembleOptions;
+use crate::text::RichText;
 
 /// A diagram signal line with a set of cycles.
 #[derive(Debug, Clone)]
 pub struct Signal {
-    name: String,
+    name: RichText,
     cycles: Vec<CycleState>,
-    data: Vec<String>,
+    data: Vec<RichText>,
     node: String,
-    period: NonZeroU16,
+    period: Period,
     phase: CycleOffset,
 }
 
@@ -35,8 +42,9 @@ pub struct Signal {
 /// This contains the shaped signal path, the group nesting depth and the name of the signal line.
 #[derive(Debug, Clone)]
 pub struct AssembledLine<'a> {
-    text: &'a str,
+    text: &'a RichText,
     path: AssembledSignalPath,
+    signal: &'a Signal,
 }
 
 #[derive(Default, Debug)]
@@ -62,12 +70,20 @@ pub struct AssembledFigure<'a> {
     group_label_at_depth: Vec<bool>,
     max_group_depth: u32,
 
-    header_text: Option<&'a str>,
-    footer_text: Option<&'a str>,
+    header_text: Option<&'a RichText>,
+    footer_text: Option<&'a RichText>,
 
     top_cycle_marker: Option<CycleEnumerationMarker>,
     bottom_cycle_marker: Option<CycleEnumerationMarker>,
 
+    timescale: Option<Timescale>,
+    time_ruler: TimeRulerPosition,
+
+    vertical_markers: &'a [VerticalMarker],
+    highlights: &'a [HighlightRegion],
+
+    skin: Option<&'a str>,
+
     path_assemble_options: PathAssembleOptions,
 
     lines: Vec<AssembledLine<'a>>,
@@ -98,6 +114,12 @@ impl<'a> AssembledFigure<'a> {
         self.hscale
     }
 
+    /// Returns the name of the built-in skin that was selected for the figure.
+    #[inline]
+    pub fn skin(&self) -> Option<&'a str> {
+        self.skin
+    }
+
     /// Returns whether the [`AssembledFigure`] contains any [`CycleState::X`]
     #[inline]
     pub fn has_undefined(&self) -> bool {
@@ -155,13 +177,13 @@ impl<'a> AssembledFigure<'a> {
 
     /// Returns a potential header text of the [`AssembledFigure`]
     #[inline]
-    pub fn header_text(&self) -> Option<&'a str> {
+    pub fn header_text(&self) -> Option<&'a RichText> {
         self.header_text
     }
 
     /// Returns a potential footer text of the [`AssembledFigure`]
     #[inline]
-    pub fn footer_text(&self) -> Option<&'a str> {
+    pub fn footer_text(&self) -> Option<&'a RichText> {
         self.footer_text
     }
 
@@ -176,22 +198,57 @@ impl<'a> AssembledFigure<'a> {
     pub fn bottom_cycle_marker(&self) -> Option<CycleEnumerationMarker> {
         self.bottom_cycle_marker
     }
+
+    /// Returns the duration of one cycle of the [`AssembledFigure`]
+    #[inline]
+    pub fn timescale(&self) -> Option<Timescale> {
+        self.timescale
+    }
+
+    /// Returns the [`VerticalMarker`]s spanning the signals of the [`AssembledFigure`]
+    #[inline]
+    pub fn vertical_markers(&self) -> &'a [VerticalMarker] {
+        self.vertical_markers
+    }
+
+    /// Returns the [`HighlightRegion`]s of the [`AssembledFigure`]
+    #[inline]
+    pub fn highlights(&self) -> &'a [HighlightRegion] {
+        self.highlights
+    }
+
+    /// Returns where the time ruler is drawn if the [`AssembledFigure`] has a time base
+    ///
+    /// This is `None` when the figure has no [`Timescale`] or when the ruler is hidden.
+    #[inline]
+    pub fn time_ruler(&self) -> Option<TimeRulerPosition> {
+        match (self.timescale, self.time_ruler) {
+            (Some(_), TimeRulerPosition::Top | TimeRulerPosition::Bottom) => Some(self.time_ruler),
+            _ => None,
+        }
+    }
 }
 
-impl AssembledLine<'_> {
+impl<'a> AssembledLine<'a> {
     fn is_empty(&self) -> bool {
         self.path.is_empty() && self.text.is_empty()
     }
+
+    /// The [`Signal`] that the line was assembled from.
+    #[cfg_attr(not(feature = "vcd"), allow(dead_code))]
+    pub(crate) fn signal(&self) -> &'a Signal {
+        self.signal
+    }
 }
 
 impl Default for Signal {
     fn default() -> Self {
         Self {
-            name: String::new(),
+            name: RichText::new(),
             cycles: Vec::new(),
             data: Vec::new(),
             node: String::new(),
-            period: NonZeroU16::MIN,
+            period: Period::ONE,
             phase: CycleOffset::default(),
         }
     }
@@ -200,15 +257,13 @@ impl Default for Signal {
 impl Signal {
     /// Create a new [`Signal`] with a set of parameters.
     pub fn with(
-        name: String,
+        name: RichText,
         cycles: Vec<CycleState>,
-        data: Vec<String>,
+        data: Vec<RichText>,
         node: String,
-        period: u16,
+        period: Period,
         phase: CycleOffset,
     ) -> Self {
-        let period = NonZeroU16::new(period).unwrap_or(NonZeroU16::MIN);
-
         Self {
             name,
             cycles,
@@ -285,12 +340,27 @@ impl Signal {
         self
     }
 
+    /// Add a named node to the [`Signal`].
+    ///
+    /// The node takes up one position and is written as `{name}` in the node string. For more
+    /// information look at the [`edges`] documentation.
+    #[inline]
+    pub fn add_named_node(mut self, name: impl AsRef<str>) -> Self {
+        self.node.push('{');
+        self.node.push_str(name.as_ref());
+        self.node.push('}');
+        self
+    }
+
     /// Add a set of nodes to the [`Signal`].
     ///
     /// This is used for putting markers and arrows / edges on the diagram. For more information
     /// look at the [`edges`][crate::edges] documentation.
     #[inline]
-    pub fn add_data_fields(mut self, fields: impl IntoIterator<Item = impl Into<String>>) -> Self {
+    pub fn add_data_fields(
+        mut self,
+        fields: impl IntoIterator<Item = impl Into<RichText>>,
+    ) -> Self {
         self.data.extend(fields.into_iter().map(Into::into));
         self
     }
@@ -300,7 +370,7 @@ impl Signal {
     /// This is used to fill up a container state, such as the [`CycleState::Data`] or
     /// [`CycleState::Box2`], with text.
     #[inline]
-    pub fn add_data_field(mut self, data: impl Into<String>) -> Self {
+    pub fn add_data_field(mut self, data: impl Into<RichText>) -> Self {
         self.data.push(data.into());
         self
     }
@@ -315,11 +385,11 @@ impl Signal {
     }
 
     /// Set the period for a signal. This is mostly important for clock signals.
+    ///
+    /// The period can be a whole number of cycles or a fraction of cycles given as a [`Period`].
     #[inline]
-    pub fn period(mut self, period: u16) -> Self {
-        let period = NonZeroU16::new(period).unwrap_or(NonZeroU16::MIN);
-
-        self.period = period;
+    pub fn period(mut self, period: impl Into<Period>) -> Self {
+        self.period = period.into();
         self
     }
 
@@ -332,14 +402,14 @@ impl Signal {
 
     /// Set the name for a [`Signal`]
     #[inline]
-    pub fn name(mut self, name: impl Into<String>) -> Self {
+    pub fn name(mut self, name: impl Into<RichText>) -> Self {
         self.name = name.into();
         self
     }
 
     /// Get the name of the [`Signal`].
     #[inline]
-    pub fn get_name(&self) -> &str {
+    pub fn get_name(&self) -> &RichText {
         &self.name
     }
 
@@ -351,7 +421,7 @@ impl Signal {
 
     /// Get the data of the [`Signal`].
     #[inline]
-    pub fn get_data_fields(&self) -> &[String] {
+    pub fn get_data_fields(&self) -> &[RichText] {
         &self.data
     }
 
@@ -363,13 +433,7 @@ impl Signal {
 
     /// Get the period of the [`Signal`].
     #[inline]
-    pub fn get_period(&self) -> u16 {
-        self.period.get()
-    }
-
-    /// Get the period of the [`Signal`].
-    #[inline]
-    pub(crate) fn get_period_internal(&self) -> NonZeroU16 {
+    pub fn get_period(&self) -> Period {
         self.period
     }
 
@@ -125,12 +125,24 @@ define_options! {
         header: HeaderOptions[PartialHeaderOptions],
         /// The figure's footer options
         footer: FooterOptions[PartialFooterOptions],
+        /// The time ruler options
+        time_ruler: TimeRulerOptions[PartialTimeRulerOptions],
+        /// The vertical marker options
+        marker: MarkerOptions[PartialMarkerOptions],
+        /// The highlight region options
+        highlight: HighlightOptions[PartialHighlightOptions],
         /// The signal options
         signal: SignalOptions[PartialSignalOptions],
         /// The group indicator
         group_indicator: GroupIndicatorOptions[PartialGroupIndicatorOptions],
         /// The arrow / edge options
         edge: EdgeOptions[PartialEdgeOptions],
+        /// The register / bitfield options
+        register: RegisterOptions[PartialRegisterOptions],
+        /// The assign / logic circuit options
+        assign: AssignOptions[PartialAssignOptions],
+        /// The font options
+        font: FontOptions[PartialFontOptions],
     }
 }
 
@@ -215,6 +227,59 @@ define_options! {
     }
 }
 
+define_options! {
+    /// The time ruler options for figures with a time base
+    TimeRulerOptions,
+
+    /// A subset of [`TimeRulerOptions`]
+    PartialTimeRulerOptions {
+        /// The time ruler height
+        height: u32 => 24,
+        /// The length of the ticks on the ruler
+        tick_length: u32 => 4,
+        /// The time label font size
+        font_size: u32 => 11,
+        /// The minimum horizontal space between two time labels
+        label_spacing: u32 => 8,
+        /// The ruler and time label color
+        color: Color => Color::BLACK,
+    }
+}
+
+define_options! {
+    /// The options for the vertical markers spanning all signals
+    MarkerOptions,
+
+    /// A subset of [`MarkerOptions`]
+    PartialMarkerOptions {
+        /// The height of the band above the signals that holds the marker and highlight labels
+        label_height: u32 => 16,
+        /// The marker label font size
+        font_size: u32 => 12,
+        /// The marker line width
+        line_width: u32 => 1,
+        /// The line and label color of markers that do not give a color
+        color: Color => Color { red: 255, green: 0, blue: 0 },
+    }
+}
+
+define_options! {
+    /// The options for the shaded highlight regions
+    HighlightOptions,
+
+    /// A subset of [`HighlightOptions`]
+    PartialHighlightOptions {
+        /// The fill color of regions that do not give a color
+        color: Color => Color { red: 255, green: 221, blue: 87 },
+        /// The fill opacity of regions that do not give an opacity
+        opacity: f32 => 0.4,
+        /// The region label font size
+        font_size: u32 => 12,
+        /// The region label color
+        label_color: Color => Color::BLACK,
+    }
+}
+
 define_options! {
     /// The signal options for the figure
     SignalOptions,
@@ -312,6 +377,85 @@ define_options! {
     }
 }
 
+define_options! {
+    /// The register / bitfield options for the figure
+    RegisterOptions,
+
+    /// A subset of the [`RegisterOptions`]
+    PartialRegisterOptions {
+        /// The default width of a single lane if the figure does not define a `hspace`
+        lane_width: u32 => 640,
+        /// The default height of a single lane if the figure does not define a `vspace`
+        lane_height: u32 => 80,
+
+        /// The line color of the register boxes
+        box_color: Color => Color::BLACK,
+        /// The background color of fields without a type
+        box_background: Option<Color> => None,
+
+        /// The font size of the field names
+        name_font_size: u32 => 14,
+        /// The text color of the field names
+        name_color: Color => Color::BLACK,
+
+        /// The font size of the bit numbers
+        bit_number_font_size: u32 => 11,
+        /// The text color of the bit numbers
+        bit_number_color: Color => Color::BLACK,
+
+        /// The font size of the field attributes
+        attribute_font_size: u32 => 11,
+        /// The text color of the field attributes
+        attribute_color: Color => Color::BLACK,
+    }
+}
+
+define_options! {
+    /// The assign / logic circuit options for the figure
+    AssignOptions,
+
+    /// A subset of the [`AssignOptions`]
+    PartialAssignOptions {
+        /// The height of a single row. Every input occupies one row.
+        row_height: u32 => 36,
+        /// The width of a single gate
+        gate_width: u32 => 40,
+        /// The minimal height of a single gate
+        gate_height: u32 => 28,
+        /// The horizontal space between two columns of gates
+        column_spacing: u32 => 32,
+
+        /// The line color of the gates
+        gate_color: Color => Color::BLACK,
+        /// The background color of the gates
+        gate_background: Option<Color> => None,
+        /// The color of the wires between the gates
+        wire_color: Color => Color::BLACK,
+
+        /// The font size of the input and output names
+        font_size: u32 => 14,
+        /// The text color of the input and output names
+        text_color: Color => Color::BLACK,
+    }
+}
+
+define_options! {
+    /// The options for the font that the text of a figure is shown with
+    FontOptions,
+
+    /// A subset of the [`FontOptions`]
+    PartialFontOptions {
+        /// Embed the glyphs of the font that are used by the figure into the SVG as an
+        /// `@font-face` rule. The text is then shown with the same font that its width is measured
+        /// with, even on machines where that font is not installed. This requires the `embed_font`
+        /// feature.
+        embed_subset: bool => false,
+        /// Draw all text as outline paths of the glyphs of the font. The figure then does not
+        /// depend on any font when it is shown. This requires the `embed_font` feature.
+        outline_text: bool => false,
+    }
+}
+
 impl GroupIndicatorOptions {
     /// The label spacing added to the label font size
     pub fn label_height(&self) -> u32 {
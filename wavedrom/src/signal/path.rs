@@ -1,16 +1,15 @@
-use std::num::NonZeroU16;
-
 use super::options::PathAssembleOptions;
 use super::markers::{ClockEdge, ClockEdgeMarker};
-use super::CycleOffset;
+use super::{CycleOffset, Period};
+use crate::text::RichText;
 
 /// The path given for a [`Signal`][crate::signal::Signal]
 #[derive(Debug, Clone)]
 pub struct SignalPath<'a> {
     states: Vec<CycleState>,
-    period: NonZeroU16,
+    period: Period,
     phase: CycleOffset,
-    data: &'a [String],
+    data: &'a [RichText],
 }
 
 /// A state that a signal can be at any cycle
@@ -68,9 +67,12 @@ pub enum CycleState {
     LowMarked,
 }
 
-impl From<char> for CycleState {
-    fn from(value: char) -> Self {
-        match value {
+impl CycleState {
+    /// Get the [`CycleState`] for a WaveJson wave character.
+    ///
+    /// Returns `None` if `c` is not a known wave character.
+    pub fn from_char(c: char) -> Option<Self> {
+        Some(match c {
             '1' => CycleState::Top,
             '0' => CycleState::Bottom,
             'z' => CycleState::Middle,
@@ -96,11 +98,51 @@ impl From<char> for CycleState {
             'H' => CycleState::HighMarked,
             'l' => CycleState::LowUnmarked,
             'L' => CycleState::LowMarked,
-            _ => CycleState::X,
+            _ => return None,
+        })
+    }
+
+    /// Get the WaveJson wave character of the [`CycleState`].
+    ///
+    /// This is the inverse of [`CycleState::from_char`].
+    pub fn to_char(self) -> char {
+        match self {
+            CycleState::Top => '1',
+            CycleState::Bottom => '0',
+            CycleState::Middle => 'z',
+            CycleState::X => 'x',
+            CycleState::PosedgeClockUnmarked => 'p',
+            CycleState::PosedgeClockMarked => 'P',
+            CycleState::NegedgeClockUnmarked => 'n',
+            CycleState::NegedgeClockMarked => 'N',
+            CycleState::Box2 => '2',
+            CycleState::Box3 => '3',
+            CycleState::Box4 => '4',
+            CycleState::Box5 => '5',
+            CycleState::Box6 => '6',
+            CycleState::Box7 => '7',
+            CycleState::Box8 => '8',
+            CycleState::Box9 => '9',
+            CycleState::Continue => '.',
+            CycleState::Gap => '|',
+            CycleState::Data => '=',
+            CycleState::Up => 'u',
+            CycleState::Down => 'd',
+            CycleState::HighUnmarked => 'h',
+            CycleState::HighMarked => 'H',
+            CycleState::LowUnmarked => 'l',
+            CycleState::LowMarked => 'L',
         }
     }
 }
 
+impl From<char> for CycleState {
+    #[inline]
+    fn from(value: char) -> Self {
+        Self::from_char(value).unwrap_or(CycleState::X)
+    }
+}
+
 /// A command for a signal path to take with relative coordinates.
 #[derive(Debug, Clone)]
 pub enum PathCommand {
@@ -153,7 +195,7 @@ pub struct SignalPathSegment {
 
     actions: Vec<PathCommand>,
 
-    text: Option<String>,
+    text: Option<RichText>,
     gaps: Vec<CycleOffset>,
     clock_edge_markers: Vec<ClockEdgeMarker>,
 }
@@ -222,8 +264,8 @@ impl SignalPathSegment {
     }
 
     /// Returns the possible marker text that a segment contains.
-    pub fn marker_text(&self) -> Option<&str> {
-        self.text.as_ref().map(|s| &s[..])
+    pub fn marker_text(&self) -> Option<&RichText> {
+        self.text.as_ref()
     }
 
     /// The starting `x` value
@@ -248,7 +290,7 @@ pub struct SignalSegmentIter<'a> {
 
     cycle_offset: CycleOffset,
 
-    period: NonZeroU16,
+    period: Period,
 
     prev: Option<CycleState>,
 
@@ -256,7 +298,7 @@ pub struct SignalSegmentIter<'a> {
     backward: PathData,
 
     box_index: usize,
-    box_content: &'a [String],
+    box_content: &'a [RichText],
 
     clock_edge_markers: Vec<ClockEdgeMarker>,
     gaps: Vec<CycleOffset>,
@@ -334,6 +376,22 @@ impl<'a> SignalSegmentIter<'a> {
             .push(self.cycle_offset + self.cycle_length(state).half())
     }
 
+    /// The widths of the first and second half of a clock period that starts at the current
+    /// cycle offset.
+    ///
+    /// The widths are determined from the exact offsets so that rounding errors of fractional
+    /// periods do not accumulate.
+    fn clock_half_widths(&self) -> (i32, i32) {
+        let w = u32::from(self.options.cycle_width);
+        let period = CycleOffset::from(self.period);
+
+        let start = self.cycle_offset.width_offset(w) as i32;
+        let middle = (self.cycle_offset + period.half()).width_offset(w) as i32;
+        let end = (self.cycle_offset + period).width_offset(w) as i32;
+
+        (middle - start, end - middle)
+    }
+
     fn begin(&mut self, state: CycleState) {
         let t = i32::from(self.options.transition_offset);
         let h = i32::from(self.options.signal_height);
@@ -373,7 +431,6 @@ impl<'a> SignalSegmentIter<'a> {
         let t = i32::from(self.options.transition_offset);
         let h = i32::from(self.options.signal_height);
         let w = i32::from(self.options.cycle_width);
-        let p = i32::from(self.period.get());
 
         use CycleState::*;
 
@@ -394,20 +451,24 @@ impl<'a> SignalSegmentIter<'a> {
                     self.posedge_marker();
                 }
 
+                let (first_half, second_half) = self.clock_half_widths();
+
                 self.forward.vertical_line(-h);
-                self.forward.horizontal_line(w * p / 2);
+                self.forward.horizontal_line(first_half);
                 self.forward.vertical_line(h);
-                self.forward.horizontal_line(w * p / 2);
+                self.forward.horizontal_line(second_half);
             }
             NegedgeClockMarked | NegedgeClockUnmarked => {
                 if state == NegedgeClockMarked {
                     self.negedge_marker();
                 }
 
+                let (first_half, second_half) = self.clock_half_widths();
+
                 self.forward.vertical_line(h);
-                self.forward.horizontal_line(w * p / 2);
+                self.forward.horizontal_line(first_half);
                 self.forward.vertical_line(-h);
-                self.forward.horizontal_line(w * p / 2);
+                self.forward.horizontal_line(second_half);
             }
             HighUnmarked | HighMarked | LowUnmarked | LowMarked => {
                 self.forward.horizontal_line(w - t);
@@ -1045,29 +1106,79 @@ impl<'a> SignalSegmentIter<'a> {
     }
 
     fn cycle_length(&self, mut state: CycleState) -> CycleOffset {
-        use CycleState::*;
-
-        if matches!(state, Continue | Gap) {
-            state = self.prev.unwrap_or(X);
+        if matches!(state, CycleState::Continue | CycleState::Gap) {
+            state = self.prev.unwrap_or(CycleState::X);
         }
 
-        CycleOffset::new_rounded(match state {
-            Top | Bottom | Middle | Box2 | Box3 | Box4 | Box5 | Box6 | Box7 | Box8 | Box9
-            | Data | X | Down | Up | HighUnmarked | HighMarked | LowUnmarked | LowMarked => 1,
-            PosedgeClockUnmarked | PosedgeClockMarked | NegedgeClockUnmarked
-            | NegedgeClockMarked => self.period.get().into(),
-            Continue | Gap => unreachable!(),
-        })
+        state.cycle_length(self.period)
     }
 }
 
+/// Get the offsets at which each of the `states` of a signal start.
+///
+/// After the last state, the offsets continue with the length of the last state. This is used to
+/// place the nodes of a signal.
+pub(crate) fn state_offsets(
+    states: &[CycleState],
+    period: Period,
+    phase: CycleOffset,
+) -> impl Iterator<Item = CycleOffset> + '_ {
+    let mut offset = phase;
+    let mut prev = CycleState::X;
+
+    states
+        .iter()
+        .copied()
+        .chain(std::iter::repeat(CycleState::Continue))
+        .map(move |state| {
+            let at = offset;
+
+            if !matches!(state, CycleState::Continue | CycleState::Gap) {
+                prev = state;
+            }
+            offset += prev.cycle_length(period);
+
+            at
+        })
+}
+
+/// Get the state that each of the `states` of a signal has after resolving continued states.
+///
+/// Clocks keep running over a continued state, so it yields the previous clock again. All other
+/// states are extended and yield `None`. A signal that starts with a continued state starts
+/// undefined.
+pub(crate) fn resolved_states(
+    states: &[CycleState],
+) -> impl Iterator<Item = Option<CycleState>> + '_ {
+    let mut prev = None;
+
+    states.iter().map(move |&state| {
+        use CycleState::*;
+
+        let state = match state {
+            Continue | Gap => match prev {
+                Some(
+                    state @ (PosedgeClockUnmarked | PosedgeClockMarked | NegedgeClockUnmarked
+                    | NegedgeClockMarked),
+                ) => state,
+                Some(_) => return None,
+                None => X,
+            },
+            state => state,
+        };
+        prev = Some(state);
+
+        Some(state)
+    })
+}
+
 impl<'a> SignalPath<'a> {
     /// Create a new [`SignalPath`] with a set of parameters.
     #[inline]
     pub fn new(
         states: &[CycleState],
-        data: &'a [String],
-        period: NonZeroU16,
+        data: &'a [RichText],
+        period: Period,
         phase: CycleOffset,
     ) -> Self {
         let states = states.to_vec();
@@ -1296,6 +1407,22 @@ impl PathData {
 }
 
 impl CycleState {
+    /// The length of a state. Clock states last a period and all other states last a single
+    /// cycle.
+    fn cycle_length(self, period: Period) -> CycleOffset {
+        use CycleState::*;
+
+        match self {
+            Top | Bottom | Middle | Box2 | Box3 | Box4 | Box5 | Box6 | Box7 | Box8 | Box9
+            | Data | X | Down | Up | HighUnmarked | HighMarked | LowUnmarked | LowMarked => {
+                CycleOffset::new_rounded(1)
+            }
+            PosedgeClockUnmarked | PosedgeClockMarked | NegedgeClockUnmarked
+            | NegedgeClockMarked => period.into(),
+            Continue | Gap => unreachable!(),
+        }
+    }
+
     fn background(self) -> Option<PathSegmentBackground> {
         use CycleState::*;
 
@@ -1330,27 +1457,82 @@ mod tests {
     #[test]
     fn calculate_cycle_length() {
         macro_rules! assert_cycle_length {
-            ([$($item:ident),* $(,)?], $period:literal, ($phase_index:literal, $phase_in_offset:ident) => $result:literal) => {
-                let period = NonZeroU16::new($period).unwrap();
+            ([$($item:ident),* $(,)?], $period:literal, $phase:literal => $result:literal) => {
+                let period = Period::from($period);
                 let options = PathAssembleOptions::default();
                 let num_cycles = SignalPath::new(
                     &[$(CycleState::$item),*],
                     &[],
                     period,
-                    $crate::CycleOffset::new($phase_index, $crate::InCycleOffset::$phase_in_offset),
+                    CycleOffset::from($phase),
                 ).iter(options).last().map_or(0, |i| i.end_cycle.ceil_num_cycles());
                 assert_eq!(num_cycles, $result);
             };
         }
 
-        assert_cycle_length!([], 1, (0, Begin) => 0);
-        assert_cycle_length!([], 2, (0, Begin) => 0);
-        assert_cycle_length!([Box2], 1, (0, Begin) => 1);
-        assert_cycle_length!([Box2], 2, (0, Begin) => 1);
-        assert_cycle_length!([PosedgeClockMarked], 1, (0, Begin) => 1);
-        assert_cycle_length!([PosedgeClockMarked], 2, (0, Begin) => 2);
-        assert_cycle_length!([Box2, PosedgeClockMarked], 3, (0, Begin) => 4);
-        assert_cycle_length!([PosedgeClockMarked, NegedgeClockMarked], 3, (0, Begin) => 6);
-        assert_cycle_length!([PosedgeClockMarked, Continue, NegedgeClockMarked], 3, (0, Begin) => 9);
+        assert_cycle_length!([], 1, 0.0 => 0);
+        assert_cycle_length!([], 2, 0.0 => 0);
+        assert_cycle_length!([Box2], 1, 0.0 => 1);
+        assert_cycle_length!([Box2], 2, 0.0 => 1);
+        assert_cycle_length!([PosedgeClockMarked], 1, 0.0 => 1);
+        assert_cycle_length!([PosedgeClockMarked], 2, 0.0 => 2);
+        assert_cycle_length!([Box2, PosedgeClockMarked], 3, 0.0 => 4);
+        assert_cycle_length!([PosedgeClockMarked, NegedgeClockMarked], 3, 0.0 => 6);
+        assert_cycle_length!([PosedgeClockMarked, Continue, NegedgeClockMarked], 3, 0.0 => 9);
+        assert_cycle_length!([Box2], 1, 0.1 => 2);
+        assert_cycle_length!([PosedgeClockMarked, Continue], 2, 0.5 => 5);
+    }
+
+    #[test]
+    fn fractional_period() {
+        let options = PathAssembleOptions::default();
+        let states = [CycleState::PosedgeClockMarked; 3];
+
+        let period = Period::new(2, 3).unwrap();
+        let path = SignalPath::new(&states, &[], period, CycleOffset::default());
+        assert_eq!(path.assemble_with_options(options).num_cycles(), 2);
+
+        let markers = path
+            .iter(options)
+            .flat_map(|item| item.segment.clock_edge_markers().to_vec())
+            .map(|marker| marker.at())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            markers,
+            [
+                CycleOffset::new(0, 1).unwrap(),
+                CycleOffset::new(2, 3).unwrap(),
+                CycleOffset::new(4, 3).unwrap(),
+            ]
+        );
+
+        let offsets = state_offsets(&states, Period::new(1, 2).unwrap(), CycleOffset::default())
+            .take(4)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            offsets,
+            [
+                CycleOffset::new(0, 2).unwrap(),
+                CycleOffset::new(1, 2).unwrap(),
+                CycleOffset::new(2, 2).unwrap(),
+                CycleOffset::new(3, 2).unwrap(),
+            ]
+        );
+    }
+
+    #[test]
+    fn precise_phase() {
+        let options = PathAssembleOptions::default();
+        let phase = CycleOffset::from(0.1);
+        let path = SignalPath::new(
+            &[CycleState::PosedgeClockMarked],
+            &[],
+            Period::ONE,
+            phase,
+        );
+
+        let segment = path.iter(options).next().unwrap().segment;
+        assert_eq!(segment.x(), 5);
+        assert_eq!(segment.clock_edge_markers()[0].at(), phase);
     }
 }
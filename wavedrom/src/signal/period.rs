@@ -0,0 +1,154 @@
+use std::cmp::Ordering;
+use std::fmt::{self, Display};
+use std::num::{NonZeroU16, NonZeroU32};
+
+use super::cycle_offset::{approximate_fraction, gcd};
+
+/// The period of a [`Signal`][super::Signal] in cycles.
+///
+/// A period is a positive rational number. This allows for clocks that run at a multiple of the
+/// diagram's cycle rate (e.g. a period of `1/2` for a double data rate clock) and for clock
+/// domains with a ratio (e.g. a period of `3/2` or `2/3`).
+///
+/// # Examples
+///
+/// ```
+/// use wavedrom::signal::Period;
+///
+/// let period = Period::new(3, 6).unwrap();
+/// assert_eq!(period.numerator(), 1);
+/// assert_eq!(period.denominator(), 2);
+///
+/// assert_eq!(Period::from_f64(1.5), Period::new(3, 2));
+/// assert_eq!(Some(Period::from(2)), Period::new(2, 1));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Period {
+    numerator: NonZeroU32,
+    denominator: NonZeroU32,
+}
+
+impl Period {
+    /// A period of exactly one cycle.
+    pub const ONE: Self = Self {
+        numerator: NonZeroU32::MIN,
+        denominator: NonZeroU32::MIN,
+    };
+
+    /// The largest denominator that [`Period::from_f64`] produces.
+    pub const MAX_APPROXIMATION_DENOMINATOR: u32 = 1000;
+
+    /// Create a new [`Period`] of `numerator / denominator` cycles.
+    ///
+    /// The fraction is reduced. Returns `None` if either the `numerator` or the `denominator` is
+    /// zero.
+    pub fn new(numerator: u32, denominator: u32) -> Option<Self> {
+        if numerator == 0 || denominator == 0 {
+            return None;
+        }
+
+        let divisor = gcd(numerator, denominator);
+
+        Some(Self {
+            numerator: NonZeroU32::new(numerator / divisor)?,
+            denominator: NonZeroU32::new(denominator / divisor)?,
+        })
+    }
+
+    /// Approximate a [`Period`] from a floating point number of cycles.
+    ///
+    /// The closest fraction with a denominator of at most
+    /// [`Period::MAX_APPROXIMATION_DENOMINATOR`] is taken. This means that values such as `0.5`,
+    /// `1.5` and `0.6666667` result in `1/2`, `3/2` and `2/3`, respectively. Returns `None` for
+    /// values that are not finite or that are not positive.
+    pub fn from_f64(value: f64) -> Option<Self> {
+        if value <= 0.0 {
+            return None;
+        }
+
+        let max_denominator = u64::from(Self::MAX_APPROXIMATION_DENOMINATOR);
+        match approximate_fraction(value, max_denominator)? {
+            // The value is smaller than the smallest representable fraction.
+            (0, _) => Self::new(1, Self::MAX_APPROXIMATION_DENOMINATOR),
+            (numerator, denominator) => Self::new(
+                u32::try_from(numerator).ok()?,
+                u32::try_from(denominator).ok()?,
+            ),
+        }
+    }
+
+    /// Get the numerator of the reduced fraction.
+    #[inline]
+    pub fn numerator(self) -> u32 {
+        self.numerator.get()
+    }
+
+    /// Get the denominator of the reduced fraction.
+    #[inline]
+    pub fn denominator(self) -> u32 {
+        self.denominator.get()
+    }
+
+    /// Returns whether the [`Period`] is a whole number of cycles.
+    #[inline]
+    pub fn is_integer(self) -> bool {
+        self.denominator() == 1
+    }
+
+    /// Get the [`Period`] as a floating point number of cycles.
+    #[inline]
+    pub fn as_f64(self) -> f64 {
+        f64::from(self.numerator()) / f64::from(self.denominator())
+    }
+}
+
+impl Default for Period {
+    #[inline]
+    fn default() -> Self {
+        Self::ONE
+    }
+}
+
+impl From<NonZeroU16> for Period {
+    #[inline]
+    fn from(value: NonZeroU16) -> Self {
+        Self {
+            numerator: value.into(),
+            denominator: NonZeroU32::MIN,
+        }
+    }
+}
+
+impl From<u16> for Period {
+    /// Create a whole number [`Period`]. A period of `0` is turned into a period of `1`.
+    #[inline]
+    fn from(value: u16) -> Self {
+        NonZeroU16::new(value).map_or(Self::ONE, Self::from)
+    }
+}
+
+impl PartialOrd for Period {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Period {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let lhs = u64::from(self.numerator()) * u64::from(other.denominator());
+        let rhs = u64::from(other.numerator()) * u64::from(self.denominator());
+
+        lhs.cmp(&rhs)
+    }
+}
+
+impl Display for Period {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_integer() {
+            write!(f, "{}", self.numerator)
+        } else {
+            write!(f, "{}/{}", self.numerator, self.denominator)
+        }
+    }
+}
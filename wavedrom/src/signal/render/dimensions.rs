@@ -1,4 +1,5 @@
 use crate::signal::{
+    markers::TimeRulerPosition,
     options::{PathAssembleOptions, RenderOptions},
     AssembledFigure,
 };
@@ -23,7 +24,7 @@ impl<'a> SvgDimensions<'a> {
             figure
                 .lines
                 .iter()
-                .map(|line| font.get_text_width(line.text, options.signal.name_font_size))
+                .map(|line| font.get_rich_text_width(line.text, options.signal.name_font_size))
                 .max()
                 .unwrap_or_default()
         });
@@ -93,7 +94,47 @@ impl<'a> SvgDimensions<'a> {
             height += header.cycle_marker_height;
         }
 
-        height
+        height + self.label_band_height() + self.top_ruler_height()
+    }
+
+    #[inline]
+    pub fn label_band_height(&self) -> u32 {
+        let has_labels = self
+            .figure
+            .vertical_markers
+            .iter()
+            .any(|marker| !marker.label().is_empty())
+            || self
+                .figure
+                .highlights
+                .iter()
+                .any(|highlight| !highlight.label().is_empty());
+
+        if has_labels {
+            self.options.marker.label_height
+        } else {
+            0
+        }
+    }
+
+    /// The middle of the band above the cycle enumeration markers that holds the labels of the
+    /// vertical markers and highlight regions.
+    pub fn label_band_y(&self) -> u32 {
+        let mut y = self.schema_y() - self.top_ruler_height() - self.label_band_height() / 2;
+
+        if self.figure.top_cycle_marker.is_some() {
+            y -= self.options.header.cycle_marker_height;
+        }
+
+        y
+    }
+
+    #[inline]
+    pub fn top_ruler_height(&self) -> u32 {
+        match self.figure.time_ruler() {
+            Some(TimeRulerPosition::Top) => self.options.time_ruler.height,
+            _ => 0,
+        }
     }
 
     #[inline]
@@ -125,7 +166,15 @@ impl<'a> SvgDimensions<'a> {
             height += footer.cycle_marker_height;
         }
 
-        height
+        height + self.bottom_ruler_height()
+    }
+
+    #[inline]
+    pub fn bottom_ruler_height(&self) -> u32 {
+        match self.figure.time_ruler() {
+            Some(TimeRulerPosition::Bottom) => self.options.time_ruler.height,
+            _ => 0,
+        }
     }
 
     // #[inline]
@@ -264,6 +313,40 @@ impl<'a> SvgDimensions<'a> {
             + self.wave_height() * num_lines
     }
 
+    /// The vertical spans `(y, height)` that cover the signal lines `lanes`.
+    ///
+    /// Consecutive lines are covered by one span. No `lanes` covers the whole schema.
+    pub fn lane_spans(&self, lanes: &[u32]) -> Vec<(u32, u32)> {
+        if lanes.is_empty() {
+            return vec![(self.schema_y(), self.schema_height())];
+        }
+
+        let mut lanes: Vec<u32> = lanes
+            .iter()
+            .copied()
+            .filter(|lane| *lane < self.num_lines())
+            .collect();
+        lanes.sort_unstable();
+        lanes.dedup();
+
+        let half_spacing = self.options.spacing.line_to_line / 2;
+
+        let mut spans = Vec::new();
+        let mut lanes = lanes.into_iter().peekable();
+        while let Some(first) = lanes.next() {
+            let mut last = first;
+            while let Some(lane) = lanes.next_if(|lane| *lane == last + 1) {
+                last = lane;
+            }
+
+            let y = self.signal_top(first) - half_spacing;
+            let height = self.signal_top(last) + self.wave_height() + half_spacing - y;
+            spans.push((y, height));
+        }
+
+        spans
+    }
+
     #[inline]
     pub fn cycle_width(&self) -> u32 {
         (self.figure.hscale * self.assemble_options.cycle_width).into()
@@ -5,8 +5,11 @@ use super::dimensions::SvgDimensions;
 
 use crate::escape::escape_str;
 use crate::signal::{
-    edges::{EdgeArrowType, EdgeVariant, LineEdge, SharpEdgeVariant, SplineEdgeVariant},
-    options::{PathAssembleOptions, RenderOptions},
+    edges::{
+        EdgeArrowHead, EdgeArrowType, EdgeStyle, EdgeVariant, InSignalPosition, LineEdge,
+        SharpEdgeVariant, SplineEdgeVariant,
+    },
+    options::{EdgeOptions, PathAssembleOptions, RenderOptions},
 };
 use crate::{Color, Font};
 
@@ -34,9 +37,11 @@ impl PlacedVec2D {
     }
 }
 
-struct BBox {
-    middle_x: u32,
-    middle_y: u32,
+/// The bounding box of a text that is centered on a point
+#[derive(Debug, Clone, Copy)]
+pub struct BBox {
+    middle_x: f64,
+    middle_y: f64,
     width: u32,
     height: u32,
 }
@@ -54,23 +59,22 @@ pub fn write_line_edge(
     let from = edge.from();
     let to = edge.to();
 
-    let from_x = dims.schema_x() + from.x().width_offset(assemble_options.cycle_width.into());
-    let from_y = dims.signal_top(from.y()) + u32::from(assemble_options.signal_height / 2);
+    let (from_x, from_y) = node_position(from, dims, assemble_options);
 
     if from == to {
         return Ok((f64::from(from_x), f64::from(from_y)));
     }
 
-    let to_x = dims.schema_x() + to.x().width_offset(assemble_options.cycle_width.into());
-    let to_y = dims.signal_top(to.y()) + u32::from(assemble_options.signal_height / 2);
+    let (to_x, to_y) = node_position(to, dims, assemble_options);
 
+    let node_font_size = edge_options.node_font_size;
     let from_bbox = edge
         .from_marker()
-        .map(|c| get_text_bbox(&c.to_string(), from_x, from_y, font, 14))
+        .map(|node| get_text_bbox(&node.name(), from_x, from_y, font, node_font_size))
         .unwrap_or(BBox::at(from_x, from_y));
     let to_bbox = edge
         .to_marker()
-        .map(|c| get_text_bbox(&c.to_string(), to_x, to_y, font, 14))
+        .map(|node| get_text_bbox(&node.name(), to_x, to_y, font, node_font_size))
         .unwrap_or(BBox::at(to_x, to_y));
 
     let (start, end) = if from_x == to_x {
@@ -396,36 +400,36 @@ pub fn write_line_edge(
         }
     }
 
+    let style = edge.style();
+    let stroke_width = style.get_width().unwrap_or(1);
+
     write!(
         writer,
-        r##"" fill="none" stroke="{color}" stroke-width="1"/>"##,
-        color = edge_options.edge_color,
+        r##"" fill="none" stroke="{color}" stroke-width="{stroke_width}""##,
+        color = style.get_color().unwrap_or(edge_options.edge_color),
     )?;
+    if let Some(dasharray) = style.get_dash().and_then(|dash| dash.dasharray()) {
+        write!(writer, r#" stroke-dasharray="{dasharray}""#)?;
+    }
+    write!(writer, "/>")?;
 
-    write_edge_arrow_heads(
-        writer,
-        arrow_type,
-        start,
-        end,
-        edge_options.edge_arrow_size,
-        edge_options.edge_arrow_color,
-    )?;
+    write_edge_arrow_heads(writer, arrow_type, style, start, end, edge_options)?;
 
     write!(writer, "</g>")?;
 
     Ok((middle_x, middle_y))
 }
 
+/// Write the node texts and the label of an edge. The label is centered on `label_at`.
 pub fn write_line_edge_markers(
     writer: &mut impl io::Write,
     edge: LineEdge,
-    middle: (f64, f64),
+    label_at: (f64, f64),
     dims: &SvgDimensions,
     assemble_options: PathAssembleOptions,
     render_options: &RenderOptions,
     font: &Font,
 ) -> io::Result<()> {
-    let assemble_options = assemble_options;
     let edge_options = &render_options.edge;
 
     let from = edge.from();
@@ -435,20 +439,14 @@ pub fn write_line_edge_markers(
         return Ok(());
     }
 
-    let from_x = dims.schema_x() + from.x().width_offset(assemble_options.cycle_width.into());
-    let from_y = dims.signal_top(from.y()) + u32::from(assemble_options.signal_height / 2);
-
-    let to_x = dims.schema_x() + to.x().width_offset(assemble_options.cycle_width.into());
-    let to_y = dims.signal_top(to.y()) + u32::from(assemble_options.signal_height / 2);
-
-    let middle_x = middle.0;
-    let middle_y = middle.1;
+    let (from_x, from_y) = node_position(from, dims, assemble_options);
+    let (to_x, to_y) = node_position(to, dims, assemble_options);
 
-    if let Some(c) = edge.from_marker() {
+    if let Some(node) = edge.from_marker() {
         write_edge_text(
             writer,
             (f64::from(from_x), f64::from(from_y)),
-            &c.to_string(),
+            &node.name(),
             edge_options.node_font_size,
             edge_options.node_text_color,
             edge_options.node_background_color,
@@ -456,11 +454,11 @@ pub fn write_line_edge_markers(
         )?;
     }
 
-    if let Some(c) = edge.to_marker() {
+    if let Some(node) = edge.to_marker() {
         write_edge_text(
             writer,
             (f64::from(to_x), f64::from(to_y)),
-            &c.to_string(),
+            &node.name(),
             edge_options.node_font_size,
             edge_options.node_text_color,
             edge_options.node_background_color,
@@ -471,7 +469,7 @@ pub fn write_line_edge_markers(
     if let Some(text) = edge.label() {
         write_edge_text(
             writer,
-            (middle_x, middle_y),
+            label_at,
             text,
             edge_options.edge_text_font_size,
             edge_options.edge_text_color,
@@ -483,39 +481,85 @@ pub fn write_line_edge_markers(
     Ok(())
 }
 
+/// Get the position of the center of a node in the figure.
+pub fn node_position(
+    at: &InSignalPosition,
+    dims: &SvgDimensions,
+    assemble_options: PathAssembleOptions,
+) -> (u32, u32) {
+    let x = dims.schema_x() + at.x().width_offset(assemble_options.cycle_width.into());
+    let y = dims.signal_top(at.y()) + u32::from(assemble_options.signal_height / 2);
+
+    (x, y)
+}
+
 impl BBox {
-    fn at(x: u32, y: u32) -> Self {
+    fn at(x: impl Into<f64>, y: impl Into<f64>) -> Self {
+        Self::new(x, y, 0, 0)
+    }
+
+    /// Create a new [`BBox`] of `width` by `height` centered on `(middle_x, middle_y)`.
+    pub fn new(
+        middle_x: impl Into<f64>,
+        middle_y: impl Into<f64>,
+        width: u32,
+        height: u32,
+    ) -> Self {
+        Self {
+            middle_x: middle_x.into(),
+            middle_y: middle_y.into(),
+            width,
+            height,
+        }
+    }
+
+    /// The center of the [`BBox`]
+    pub fn middle(&self) -> (f64, f64) {
+        (self.middle_x, self.middle_y)
+    }
+
+    /// The height of the [`BBox`]
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// The same [`BBox`] centered on `at`
+    pub fn moved_to(self, at: (f64, f64)) -> Self {
         Self {
-            middle_x: x,
-            middle_y: y,
-            width: 0,
-            height: 0,
+            middle_x: at.0,
+            middle_y: at.1,
+            ..self
         }
     }
 
+    /// Returns whether two [`BBox`]es overlap when they are kept at least `margin` apart.
+    pub fn overlaps(&self, other: &Self, margin: f64) -> bool {
+        self.x_min() < other.x_max() + margin
+            && other.x_min() < self.x_max() + margin
+            && self.y_min() < other.y_max() + margin
+            && other.y_min() < self.y_max() + margin
+    }
+
     fn x_min(&self) -> f64 {
-        f64::from(self.middle_x) - f64::from(self.width) / 2.
+        self.middle_x - f64::from(self.width) / 2.
     }
     fn x_max(&self) -> f64 {
-        f64::from(self.middle_x) + f64::from(self.width) / 2.
+        self.middle_x + f64::from(self.width) / 2.
     }
 
     fn y_min(&self) -> f64 {
-        f64::from(self.middle_y) - f64::from(self.height) / 2.
+        self.middle_y - f64::from(self.height) / 2.
     }
     fn y_max(&self) -> f64 {
-        f64::from(self.middle_y) + f64::from(self.height) / 2.
+        self.middle_y + f64::from(self.height) / 2.
     }
 
     fn intersection_bb(&self, to_x: impl Into<f64>, to_y: impl Into<f64>) -> (f64, f64) {
         if self.width == 0 || self.height == 0 {
-            return (self.middle_x.into(), self.middle_y.into());
+            return (self.middle_x, self.middle_y);
         }
 
-        let dir = (
-            to_x.into() - f64::from(self.middle_x),
-            to_y.into() - f64::from(self.middle_y),
-        );
+        let dir = (to_x.into() - self.middle_x, to_y.into() - self.middle_y);
 
         let bbox_x = dir.0.signum() * f64::from(self.width) / 2.;
         let bbox_y = dir.1.signum() * f64::from(self.height) / 2.;
@@ -528,13 +572,13 @@ impl BBox {
 
         if bbox_x_dis < bbox_y_dis {
             (
-                f64::from(self.middle_x) + bbox_x,
-                f64::from(self.middle_y) + bbox_x_intersection_y,
+                self.middle_x + bbox_x,
+                self.middle_y + bbox_x_intersection_y,
             )
         } else {
             (
-                f64::from(self.middle_x) + bbox_y_intersection_x,
-                f64::from(self.middle_y) + bbox_y,
+                self.middle_x + bbox_y_intersection_x,
+                self.middle_y + bbox_y,
             )
         }
     }
@@ -574,15 +618,17 @@ impl PlacedVec2D {
     }
 }
 
-fn get_text_bbox(text: &str, middle_x: u32, middle_y: u32, font: &Font, font_size: u32) -> BBox {
-    let width = font.get_text_width(text, 14);
+/// Get the [`BBox`] of a text that is centered on `(middle_x, middle_y)`.
+pub fn get_text_bbox(
+    text: &str,
+    middle_x: impl Into<f64>,
+    middle_y: impl Into<f64>,
+    font: &Font,
+    font_size: u32,
+) -> BBox {
+    let width = font.get_text_width(text, font_size);
 
-    BBox {
-        middle_x,
-        middle_y,
-        width,
-        height: font_size,
-    }
+    BBox::new(middle_x, middle_y, width, font_size)
 }
 
 pub fn write_edge_text(
@@ -616,8 +662,25 @@ pub fn write_edge_text(
 fn write_edge_arrow_head_path(
     writer: &mut impl io::Write,
     v2d: PlacedVec2D,
+    head: EdgeArrowHead,
     size: u32,
 ) -> io::Result<()> {
+    if head == EdgeArrowHead::Dot {
+        let radius = f64::from(size) / 2.;
+        let Some(center) = offset_in_dir(v2d.origin, v2d.dir, radius) else {
+            return Ok(());
+        };
+
+        return write!(
+            writer,
+            "M{x},{y}a{radius},{radius} 0 1,0 {diameter},0a{radius},{radius} 0 1,0 -{diameter},0z",
+            x = SVGF64(center.0 - radius),
+            y = SVGF64(center.1),
+            radius = SVGF64(radius),
+            diameter = SVGF64(2. * radius),
+        );
+    }
+
     let Some(end) = offset_in_dir(v2d.origin, v2d.dir, size) else {
         return Ok(());
     };
@@ -639,28 +702,44 @@ fn write_edge_arrow_head_path(
     let v2x = SVGF64(v2.0);
     let v2y = SVGF64(v2.1);
 
-    write!(writer, "M{at_x},{at_y}L{v1x},{v1y}L{v2x},{v2y}z")
+    if head == EdgeArrowHead::Open {
+        write!(writer, "M{v1x},{v1y}L{at_x},{at_y}L{v2x},{v2y}")
+    } else {
+        write!(writer, "M{at_x},{at_y}L{v1x},{v1y}L{v2x},{v2y}z")
+    }
 }
 
 fn write_edge_arrow_heads(
     writer: &mut impl io::Write,
     arrow_type: EdgeArrowType,
+    style: &EdgeStyle,
     begin: PlacedVec2D,
     end: PlacedVec2D,
-    arrow_size: u32,
-    arrow_color: Color,
+    edge_options: &EdgeOptions,
 ) -> io::Result<()> {
+    let head = style.get_arrow_head().unwrap_or_default();
+    let arrow_size = edge_options.edge_arrow_size;
+    let arrow_color = style.get_color().unwrap_or(edge_options.edge_arrow_color);
+
     write!(writer, r#"<path d=""#)?;
 
     if matches!(arrow_type, EdgeArrowType::Start | EdgeArrowType::Both) {
-        write_edge_arrow_head_path(writer, begin, arrow_size)?;
+        write_edge_arrow_head_path(writer, begin, head, arrow_size)?;
     }
 
     if matches!(arrow_type, EdgeArrowType::End | EdgeArrowType::Both) {
-        write_edge_arrow_head_path(writer, end.invert(), arrow_size)?;
+        write_edge_arrow_head_path(writer, end.invert(), head, arrow_size)?;
     }
 
-    write!(writer, r##"" fill="{arrow_color}" stroke="none"/>"##)?;
+    if head == EdgeArrowHead::Open {
+        write!(
+            writer,
+            r##"" fill="none" stroke="{arrow_color}" stroke-width="{stroke_width}"/>"##,
+            stroke_width = style.get_width().unwrap_or(1),
+        )?;
+    } else {
+        write!(writer, r##"" fill="{arrow_color}" stroke="none"/>"##)?;
+    }
 
     Ok(())
 }
@@ -5,13 +5,22 @@ use super::path::{PathCommand, PathSegmentBackground};
 use crate::escape::escape_str;
 use crate::{Color, Font};
 
-use self::edges::{write_edge_text, write_line_edge, write_line_edge_markers};
+#[cfg(all(feature = "skins", feature = "json5"))]
+use crate::skin::Skin;
+
+use self::edges::{
+    get_text_bbox, node_position, write_edge_text, write_line_edge, write_line_edge_markers, BBox,
+};
+use self::placement::LabelPlacer;
+use self::ruler::write_time_ruler;
 
 use super::path::AssembledSignalPath;
-use super::AssembledFigure;
+use super::{AssembledFigure, CycleOffset};
 
 mod dimensions;
 mod edges;
+mod placement;
+mod ruler;
 
 use super::options::{PathAssembleOptions, RenderOptions, SignalOptions};
 use dimensions::SvgDimensions;
@@ -113,6 +122,34 @@ impl<'a> AssembledFigure<'a> {
         &self,
         writer: &mut impl io::Write,
         options: &RenderOptions,
+    ) -> io::Result<()> {
+        #[cfg(all(feature = "skins", feature = "json5"))]
+        let skinned_options = self
+            .skin
+            .and_then(Skin::named)
+            .and_then(|skin| skin.render)
+            .map(|render| {
+                let mut options = options.clone();
+                options.merge_in(render);
+                options
+            });
+        #[cfg(all(feature = "skins", feature = "json5"))]
+        let options = skinned_options.as_ref().unwrap_or(options);
+
+        #[cfg(feature = "embed_font")]
+        if options.font.embed_subset || options.font.outline_text {
+            return crate::font::write_with_font_options(writer, &options.font, |svg| {
+                self.write_svg_document(svg, options)
+            });
+        }
+
+        self.write_svg_document(writer, options)
+    }
+
+    fn write_svg_document(
+        &self,
+        writer: &mut impl io::Write,
+        options: &RenderOptions,
     ) -> io::Result<()> {
         let RenderOptions {
             background,
@@ -122,7 +159,13 @@ impl<'a> AssembledFigure<'a> {
             group_indicator,
             header,
             footer,
+            time_ruler,
+            marker,
+            highlight,
             edge,
+            register: _,
+            assign: _,
+            font: _,
         } = options;
 
         let PathAssembleOptions {
@@ -193,12 +236,18 @@ impl<'a> AssembledFigure<'a> {
             write!(writer, r##"</g>"##)?;
         }
 
+        // A vertical line over the full schema height. The cycle hint lines and the vertical
+        // markers give it a stroke.
         write!(
             writer,
-            r##"<g id="cl"><path fill="none" d="M0,0v{schema_height}" stroke-width="1" stroke-dasharray="2" stroke="{color}"/></g>"##,
-            color = signal.hint_line_color,
+            r##"<path id="vl" fill="none" d="M0,0v{schema_height}"/>"##,
             schema_height = dims.schema_height(),
         )?;
+        write!(
+            writer,
+            r##"<g id="cl" stroke-width="1" stroke-dasharray="2" stroke="{color}"><use xlink:href="#vl"/></g>"##,
+            color = signal.hint_line_color,
+        )?;
         write!(writer, "</defs>")?;
 
         // Background
@@ -216,10 +265,12 @@ impl<'a> AssembledFigure<'a> {
 
             write!(
                 writer,
-                r##"<text x="{x}" y="{y}" text-anchor="middle" dominant-baseline="middle" font-family="{font_family}" font-size="{title_font_size}" fill="{title_color}" letter-spacing="0"><tspan>{text}</tspan></text>"##,
+                r##"<text x="{x}" y="{y}" text-anchor="middle" dominant-baseline="middle" font-family="{font_family}" font-size="{title_font_size}" fill="{title_color}" letter-spacing="0">{text}</text>"##,
                 x = dims.header_x() + dims.header_width() / 2,
-                y = dims.header_y() + dims.header_height() / 2,
-                text = escape_str(title),
+                y = dims.header_y()
+                    + (dims.header_height() - dims.label_band_height() - dims.top_ruler_height())
+                        / 2,
+                text = title.svg_tspans(title_font_size),
             )?;
         }
 
@@ -241,7 +292,7 @@ impl<'a> AssembledFigure<'a> {
                         x = dims.schema_x()
                             + dims.cycle_width() * (offset - start)
                             + dims.cycle_width() / 2,
-                        y = dims.header_y() + dims.header_height(),
+                        y = dims.schema_y() - dims.top_ruler_height(),
                     )?;
                 }
                 write!(writer, "</g>")?;
@@ -260,6 +311,50 @@ impl<'a> AssembledFigure<'a> {
         }
         write!(writer, "</g>")?;
 
+        // Highlight Regions
+        if !self.highlights.is_empty() {
+            let label_font_size = highlight.font_size;
+            let label_color = highlight.label_color;
+
+            write!(writer, "<g>")?;
+            for region in self.highlights {
+                // Regions are cut off at the end of the figure.
+                let figure_end = CycleOffset::new_rounded(self.num_cycles);
+                let x = dims.schema_x() + region.start().min(figure_end).width_offset(cycle_width);
+                let end_x =
+                    dims.schema_x() + region.end().min(figure_end).width_offset(cycle_width);
+
+                if end_x <= x {
+                    continue;
+                }
+
+                let color = region.color().unwrap_or(highlight.color);
+                let opacity = region.opacity().unwrap_or(highlight.opacity);
+
+                for (y, height) in dims.lane_spans(region.lanes()) {
+                    write!(
+                        writer,
+                        r##"<rect x="{x}" y="{y}" width="{width}" height="{height}" fill="{color}" fill-opacity="{opacity}"/>"##,
+                        width = end_x - x,
+                    )?;
+                }
+
+                if !region.label().is_empty() {
+                    write!(
+                        writer,
+                        r##"<text x="{x}" y="{y}" text-anchor="middle" dominant-baseline="middle" font-family="{font_family}" font-size="{label_font_size}" fill="{label_color}" letter-spacing="0"><tspan>{text}</tspan></text>"##,
+                        x = (x + end_x) / 2,
+                        y = dims.label_band_y(),
+                        text = escape_str(region.label()),
+                    )?;
+                }
+            }
+            write!(writer, "</g>")?;
+        }
+
+        // Time Ruler
+        write_time_ruler(writer, self, &dims, time_ruler, &font, &font_family)?;
+
         // Group Indicators
         if !self.group_markers.is_empty() {
             let label_font_size = group_indicator.label_fontsize;
@@ -341,9 +436,9 @@ impl<'a> AssembledFigure<'a> {
 
                 write!(
                     writer,
-                    r##"<g transform="translate(0,{y})"><text dominant-baseline="middle" font-family="{font_family}" font-size="{name_font_size}" fill="{name_color}" letter-spacing="0"><tspan>{text}</tspan></text></g>"##,
+                    r##"<g transform="translate(0,{y})"><text dominant-baseline="middle" font-family="{font_family}" font-size="{name_font_size}" fill="{name_color}" letter-spacing="0">{text}</text></g>"##,
                     y = signal_height / 2,
-                    text = escape_str(line.text),
+                    text = line.text.svg_tspans(name_font_size),
                 )?;
             }
 
@@ -363,6 +458,43 @@ impl<'a> AssembledFigure<'a> {
         }
         write!(writer, "</g>")?;
 
+        // Vertical Markers
+        if !self.vertical_markers.is_empty() {
+            let label_font_size = marker.font_size;
+
+            write!(writer, "<g>")?;
+            for vertical_marker in self.vertical_markers {
+                // Markers past the end of the figure are not drawn.
+                if vertical_marker.at() > CycleOffset::new_rounded(self.num_cycles) {
+                    continue;
+                }
+
+                let x = dims.schema_x() + vertical_marker.at().width_offset(cycle_width);
+                let color = vertical_marker.color().unwrap_or(marker.color);
+
+                write!(
+                    writer,
+                    r##"<use transform="translate({x},{y})" xlink:href="#vl" stroke-width="{line_width}" stroke="{color}""##,
+                    y = dims.schema_y(),
+                    line_width = marker.line_width,
+                )?;
+                if let Some(dasharray) = vertical_marker.dash().dasharray() {
+                    write!(writer, r##" stroke-dasharray="{dasharray}""##)?;
+                }
+                write!(writer, "/>")?;
+
+                if !vertical_marker.label().is_empty() {
+                    write!(
+                        writer,
+                        r##"<text x="{x}" y="{y}" text-anchor="middle" dominant-baseline="middle" font-family="{font_family}" font-size="{label_font_size}" fill="{color}" letter-spacing="0"><tspan>{text}</tspan></text>"##,
+                        y = dims.label_band_y(),
+                        text = escape_str(vertical_marker.label()),
+                    )?;
+                }
+            }
+            write!(writer, "</g>")?;
+        }
+
         // Footer Text
         if let Some(footer_text) = self.footer_text {
             let footer_font_size = footer.font_size;
@@ -370,10 +502,12 @@ impl<'a> AssembledFigure<'a> {
 
             write!(
                 writer,
-                r##"<text x="{x}" y="{y}" text-anchor="middle" dominant-baseline="middle" font-family="{font_family}" font-size="{footer_font_size}" fill="{footer_color}" letter-spacing="0"><tspan>{text}</tspan></text>"##,
+                r##"<text x="{x}" y="{y}" text-anchor="middle" dominant-baseline="middle" font-family="{font_family}" font-size="{footer_font_size}" fill="{footer_color}" letter-spacing="0">{text}</text>"##,
                 x = dims.footer_width() / 2,
-                y = dims.footer_y() + dims.footer_height() / 2,
-                text = escape_str(footer_text),
+                y = dims.footer_y()
+                    + dims.bottom_ruler_height()
+                    + (dims.footer_height() - dims.bottom_ruler_height()) / 2,
+                text = footer_text.svg_tspans(footer_font_size),
             )?;
         }
 
@@ -396,13 +530,67 @@ impl<'a> AssembledFigure<'a> {
                         x = dims.schema_x()
                             + dims.cycle_width() * (offset - start)
                             + dims.cycle_width() / 2,
-                        y = dims.footer_y()
+                        y = dims.footer_y() + dims.bottom_ruler_height(),
                     )?;
                 }
                 write!(writer, "</g>")?;
             }
         }
 
+        // The edge labels and the node texts are placed such that they avoid the data box texts,
+        // the node texts of the edges and each other.
+        let mut placer = LabelPlacer::new();
+        for (i, line) in self.lines.iter().enumerate() {
+            let Ok(i) = u32::try_from(i) else {
+                break;
+            };
+
+            for segment in line.path.segments() {
+                let Some(text) = segment.marker_text() else {
+                    continue;
+                };
+
+                placer.occupy(BBox::new(
+                    dims.schema_x() as i32 + segment.x() + segment.width() / 2,
+                    dims.signal_top(i) + signal_height / 2,
+                    font.get_rich_text_width(text, signal.marker_font_size),
+                    signal.marker_font_size,
+                ));
+            }
+        }
+        for line_edge in self.line_edge_markers.lines() {
+            if line_edge.from() == line_edge.to() {
+                continue;
+            }
+
+            for (at, node) in [
+                (line_edge.from(), line_edge.from_marker()),
+                (line_edge.to(), line_edge.to_marker()),
+            ] {
+                if let Some(node) = node {
+                    let (x, y) = node_position(at, &dims, self.path_assemble_options);
+                    placer.occupy(get_text_bbox(
+                        &node.name(),
+                        x,
+                        y,
+                        &font,
+                        edge.node_font_size,
+                    ));
+                }
+            }
+        }
+        let text_node_positions = self
+            .line_edge_markers
+            .text_nodes()
+            .iter()
+            .map(|text_node| {
+                let (x, y) = node_position(text_node.at(), &dims, self.path_assemble_options);
+                let bbox =
+                    get_text_bbox(&text_node.text().name(), x, y, &font, edge.node_font_size);
+                placer.place_node_text(bbox)
+            })
+            .collect::<Vec<_>>();
+
         // Edge markers
         if !self.line_edge_markers.lines().is_empty() {
             let mut middles = Vec::with_capacity(self.line_edge_markers.lines().len());
@@ -425,10 +613,33 @@ impl<'a> AssembledFigure<'a> {
                 .iter()
                 .zip(middles.into_iter())
             {
+                let label_at = match line_edge.label() {
+                    Some(label) if line_edge.from() != line_edge.to() => {
+                        let (from_x, from_y) =
+                            node_position(line_edge.from(), &dims, self.path_assemble_options);
+                        let (to_x, to_y) =
+                            node_position(line_edge.to(), &dims, self.path_assemble_options);
+                        let bbox = get_text_bbox(
+                            label,
+                            middle.0,
+                            middle.1,
+                            &font,
+                            edge.edge_text_font_size,
+                        );
+
+                        placer.place_edge_label(
+                            bbox,
+                            (from_x.into(), from_y.into()),
+                            (to_x.into(), to_y.into()),
+                        )
+                    }
+                    _ => middle,
+                };
+
                 write_line_edge_markers(
                     writer,
                     line_edge.clone(),
-                    middle,
+                    label_at,
                     &dims,
                     self.path_assemble_options,
                     options,
@@ -441,15 +652,16 @@ impl<'a> AssembledFigure<'a> {
         // Edge separate text markers
         if !self.line_edge_markers.text_nodes().is_empty() {
             write!(writer, "<g>")?;
-            for text_node in self.line_edge_markers.text_nodes() {
-                let text = text_node.text().to_string();
-                let x = dims.schema_x() + text_node.at().x().width_offset(cycle_width);
-                let y = dims.signal_top(text_node.at().y()) + signal_height / 2;
-
+            for (text_node, at) in self
+                .line_edge_markers
+                .text_nodes()
+                .iter()
+                .zip(text_node_positions)
+            {
                 write_edge_text(
                     writer,
-                    (x.into(), y.into()),
-                    &text,
+                    at,
+                    &text_node.text().name(),
                     edge.node_font_size,
                     edge.node_text_color,
                     edge.node_background_color,
@@ -569,14 +781,14 @@ fn write_signal(
         if let Some(marker_text) = segment.marker_text() {
             write!(
                 writer,
-                r##"<g transform="translate({x},{y})"><text text-anchor="middle" dominant-baseline="middle" font-family="{font_family}" font-size="{font_size}" fill="{color}" letter-spacing="0"><tspan>{text}</tspan></text></g>"##,
+                r##"<g transform="translate({x},{y})"><text text-anchor="middle" dominant-baseline="middle" font-family="{font_family}" font-size="{font_size}" fill="{color}" letter-spacing="0">{text}</text></g>"##,
                 font_family = Font::default()
                     .get_font_family_name()
                     .as_ref()
                     .map(|s| &s[..])
                     .unwrap_or("Helvetica"),
                 font_size = options.marker_font_size,
-                text = marker_text,
+                text = marker_text.svg_tspans(options.marker_font_size),
                 color = options.marker_color,
                 x = segment.x() + segment.width() / 2,
                 y = signal_height / 2,
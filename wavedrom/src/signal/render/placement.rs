@@ -0,0 +1,136 @@
+use std::iter;
+
+use super::edges::BBox;
+
+/// The space that is kept free between two labels
+const LABEL_MARGIN: f64 = 2.;
+
+/// The number of steps that a label is nudged in each direction before giving up
+const NUDGE_STEPS: u32 = 3;
+
+/// Places the edge labels and node texts such that they do not overlap.
+///
+/// Labels are placed one after the other. Each label is put at the first of a list of candidate
+/// positions where it does not overlap with the areas that are already occupied. The result only
+/// depends on the order in which the labels are placed, so the placement is deterministic.
+pub struct LabelPlacer {
+    occupied: Vec<BBox>,
+}
+
+impl LabelPlacer {
+    pub fn new() -> Self {
+        Self {
+            occupied: Vec::new(),
+        }
+    }
+
+    /// Mark an area that cannot be moved (e.g. a data box text) as occupied.
+    pub fn occupy(&mut self, bbox: BBox) {
+        self.occupied.push(bbox);
+    }
+
+    fn is_free(&self, bbox: &BBox) -> bool {
+        !self
+            .occupied
+            .iter()
+            .any(|other| other.overlaps(bbox, LABEL_MARGIN))
+    }
+
+    /// Put `bbox` at the first candidate position where it is free. If there is no such position,
+    /// the first candidate is used.
+    fn place(
+        &mut self,
+        bbox: BBox,
+        candidates: impl IntoIterator<Item = (f64, f64)>,
+    ) -> (f64, f64) {
+        let mut candidates = candidates.into_iter();
+        let first = candidates.next().unwrap_or(bbox.middle());
+
+        let at = iter::once(first)
+            .chain(candidates)
+            .find(|at| self.is_free(&bbox.moved_to(*at)))
+            .unwrap_or(first);
+
+        self.occupied.push(bbox.moved_to(at));
+        at
+    }
+
+    /// Place the label of an edge from `from` to `to`. The `bbox` is centered on the middle of the
+    /// edge.
+    ///
+    /// The label is first nudged along the edge and then moved up or down.
+    pub fn place_edge_label(&mut self, bbox: BBox, from: (f64, f64), to: (f64, f64)) -> (f64, f64) {
+        let (middle_x, middle_y) = bbox.middle();
+        let step = f64::from(bbox.height()) + LABEL_MARGIN;
+
+        let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+        let length = (dx * dx + dy * dy).sqrt();
+
+        let along = (1..=NUDGE_STEPS)
+            .map(move |i| f64::from(i) * step)
+            .take_while(move |offset| 2. * offset < length)
+            .flat_map(move |offset| {
+                let (offset_x, offset_y) = (dx / length * offset, dy / length * offset);
+                [
+                    (middle_x + offset_x, middle_y + offset_y),
+                    (middle_x - offset_x, middle_y - offset_y),
+                ]
+            });
+
+        self.place(
+            bbox,
+            iter::once(bbox.middle())
+                .chain(along)
+                .chain(vertical_offsets(bbox)),
+        )
+    }
+
+    /// Place the text of a node that is not part of an edge. The `bbox` is centered on the node.
+    ///
+    /// The text is moved up or down if its place is taken.
+    pub fn place_node_text(&mut self, bbox: BBox) -> (f64, f64) {
+        self.place(
+            bbox,
+            iter::once(bbox.middle()).chain(vertical_offsets(bbox)),
+        )
+    }
+}
+
+/// The positions above and below `bbox` ordered by their distance.
+fn vertical_offsets(bbox: BBox) -> impl Iterator<Item = (f64, f64)> {
+    let (middle_x, middle_y) = bbox.middle();
+    let step = f64::from(bbox.height()) + LABEL_MARGIN;
+
+    (1..=NUDGE_STEPS).flat_map(move |i| {
+        let offset = f64::from(i) * step;
+        [(middle_x, middle_y - offset), (middle_x, middle_y + offset)]
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn place_labels() -> Vec<(f64, f64)> {
+        let mut placer = LabelPlacer::new();
+
+        // A data box text in the middle of both edges
+        placer.occupy(BBox::new(100, 50, 20, 10));
+
+        let label = BBox::new(100, 50, 20, 10);
+        vec![
+            placer.place_edge_label(label, (0., 50.), (200., 50.)),
+            placer.place_edge_label(label, (0., 50.), (200., 50.)),
+        ]
+    }
+
+    #[test]
+    fn overlapping_edge_labels() {
+        let positions = place_labels();
+
+        // The first label is nudged along the edge past the data box text. The second label ends
+        // up on the other side.
+        assert_eq!(positions, [(124., 50.), (76., 50.)]);
+        assert_eq!(place_labels(), positions);
+    }
+}
@@ -0,0 +1,104 @@
+use std::io;
+
+use super::dimensions::SvgDimensions;
+
+use crate::signal::markers::TimeRulerPosition;
+use crate::signal::options::TimeRulerOptions;
+use crate::signal::timescale::{display_unit, format_time};
+use crate::signal::AssembledFigure;
+use crate::Font;
+
+/// The steps between two labeled cycles that are tried in order: 1, 2, 5, 10, 20, 50, ...
+fn label_steps() -> impl Iterator<Item = u64> {
+    (0u32..).map(|i| [1, 2, 5][(i % 3) as usize] * 10u64.saturating_pow(i / 3))
+}
+
+pub fn write_time_ruler(
+    writer: &mut impl io::Write,
+    figure: &AssembledFigure,
+    dims: &SvgDimensions,
+    options: &TimeRulerOptions,
+    font: &Font,
+    font_family: &str,
+) -> io::Result<()> {
+    let (Some(timescale), Some(position)) = (figure.timescale(), figure.time_ruler()) else {
+        return Ok(());
+    };
+
+    let num_cycles = figure.num_cycles();
+    if num_cycles == 0 {
+        return Ok(());
+    }
+
+    let TimeRulerOptions {
+        height: _,
+        tick_length,
+        font_size,
+        label_spacing,
+        color,
+    } = *options;
+
+    let cycle_time = timescale.as_femtoseconds();
+    let unit = display_unit(cycle_time);
+    let cycle_width = dims.cycle_width();
+
+    // Only label every so many cycles so that the widest label fits between two labels.
+    let widest_label = format_time(cycle_time * u128::from(num_cycles), unit);
+    let label_width = u64::from(font.get_text_width(&widest_label, font_size) + label_spacing);
+    let every = label_steps()
+        .find(|every| {
+            every * u64::from(cycle_width) >= label_width || *every >= u64::from(num_cycles)
+        })
+        .unwrap_or(1);
+
+    let schema_x = dims.schema_x();
+    let (axis_y, direction) = match position {
+        TimeRulerPosition::Top => (dims.schema_y(), -1),
+        _ => (dims.footer_y(), 1),
+    };
+
+    write!(writer, "<g>")?;
+
+    write!(
+        writer,
+        r##"<path fill="none" d="M{schema_x},{axis_y}h{schema_width}"##,
+        schema_width = dims.schema_width(),
+    )?;
+    for i in 0..=num_cycles {
+        let length = if u64::from(i) % every == 0 {
+            tick_length
+        } else {
+            tick_length / 2
+        };
+
+        write!(
+            writer,
+            "M{x},{axis_y}v{length}",
+            x = schema_x + i * cycle_width,
+            length = direction * length as i32,
+        )?;
+    }
+    write!(writer, r##"" stroke-width="1" stroke="{color}"/>"##)?;
+
+    let label_y = axis_y as i32 + direction * (tick_length + 2 + font_size / 2) as i32;
+    for i in (0..=num_cycles).step_by(every as usize) {
+        let anchor = if i == 0 {
+            "start"
+        } else if i == num_cycles {
+            "end"
+        } else {
+            "middle"
+        };
+
+        write!(
+            writer,
+            r##"<text x="{x}" y="{label_y}" text-anchor="{anchor}" dominant-baseline="middle" font-family="{font_family}" font-size="{font_size}" fill="{color}" letter-spacing="0"><tspan>{text}</tspan></text>"##,
+            x = schema_x + i * cycle_width,
+            text = format_time(cycle_time * u128::from(i), unit),
+        )?;
+    }
+
+    write!(writer, "</g>")?;
+
+    Ok(())
+}
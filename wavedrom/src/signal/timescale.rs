@@ -0,0 +1,228 @@
+use std::fmt::{self, Display};
+use std::num::NonZeroU32;
+use std::str::FromStr;
+
+/// A unit of time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TimeUnit {
+    /// Femtoseconds
+    Femtoseconds,
+    /// Picoseconds
+    Picoseconds,
+    /// Nanoseconds
+    Nanoseconds,
+    /// Microseconds
+    Microseconds,
+    /// Milliseconds
+    Milliseconds,
+    /// Seconds
+    Seconds,
+}
+
+impl TimeUnit {
+    /// All the units from the smallest to the largest.
+    pub const ALL: [Self; 6] = [
+        Self::Femtoseconds,
+        Self::Picoseconds,
+        Self::Nanoseconds,
+        Self::Microseconds,
+        Self::Milliseconds,
+        Self::Seconds,
+    ];
+
+    /// Get the number of femtoseconds in one of this unit.
+    pub fn as_femtoseconds(self) -> u128 {
+        match self {
+            Self::Femtoseconds => 1,
+            Self::Picoseconds => 1_000,
+            Self::Nanoseconds => 1_000_000,
+            Self::Microseconds => 1_000_000_000,
+            Self::Milliseconds => 1_000_000_000_000,
+            Self::Seconds => 1_000_000_000_000_000,
+        }
+    }
+
+    /// Get the symbol of the unit as used in VCD files and WaveJson (e.g. `ns`).
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Self::Femtoseconds => "fs",
+            Self::Picoseconds => "ps",
+            Self::Nanoseconds => "ns",
+            Self::Microseconds => "us",
+            Self::Milliseconds => "ms",
+            Self::Seconds => "s",
+        }
+    }
+
+    /// Get the unit for a symbol. Both `us` and `µs` are accepted for microseconds.
+    pub fn from_symbol(symbol: &str) -> Option<Self> {
+        Some(match symbol {
+            "fs" => Self::Femtoseconds,
+            "ps" => Self::Picoseconds,
+            "ns" => Self::Nanoseconds,
+            "us" | "µs" => Self::Microseconds,
+            "ms" => Self::Milliseconds,
+            "s" => Self::Seconds,
+            _ => return None,
+        })
+    }
+}
+
+/// Get the largest unit that is not larger than `femtoseconds`.
+///
+/// Times that are multiples of `femtoseconds` can then be shown in this unit with at most three
+/// decimals.
+pub(crate) fn display_unit(femtoseconds: u128) -> TimeUnit {
+    TimeUnit::ALL
+        .into_iter()
+        .rev()
+        .find(|unit| unit.as_femtoseconds() <= femtoseconds)
+        .unwrap_or(TimeUnit::Femtoseconds)
+}
+
+/// Format a time in `unit` with at most three decimals (e.g. `2.5ns`).
+pub(crate) fn format_time(femtoseconds: u128, unit: TimeUnit) -> String {
+    let unit_fs = unit.as_femtoseconds();
+    let integer = femtoseconds / unit_fs;
+    // The thousandths of the unit, rounded to the nearest.
+    let fraction = ((femtoseconds % unit_fs) * 1000 + unit_fs / 2) / unit_fs;
+
+    let (integer, fraction) = if fraction == 1000 {
+        (integer + 1, 0)
+    } else {
+        (integer, fraction)
+    };
+
+    if fraction == 0 {
+        format!("{integer}{unit}")
+    } else {
+        let fraction = format!("{fraction:03}");
+        format!("{integer}.{}{unit}", fraction.trim_end_matches('0'))
+    }
+}
+
+impl Display for TimeUnit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.symbol())
+    }
+}
+
+/// An amount of time given as a whole number of a [`TimeUnit`].
+///
+/// This is used to give a real duration to the cycles of a
+/// [`SignalFigure`][super::SignalFigure] and for the time unit of VCD files.
+///
+/// # Examples
+///
+/// ```
+/// use wavedrom::signal::{TimeUnit, Timescale};
+///
+/// let timescale: Timescale = "10ns".parse().unwrap();
+/// assert_eq!(timescale.value(), 10);
+/// assert_eq!(timescale.unit(), TimeUnit::Nanoseconds);
+///
+/// // Fractions are expressed in a smaller unit.
+/// assert_eq!("2.5 ns".parse(), Ok(Timescale::new(2500, TimeUnit::Picoseconds).unwrap()));
+/// assert_eq!("1000ps".parse::<Timescale>().unwrap().to_string(), "1ns");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Timescale {
+    value: NonZeroU32,
+    unit: TimeUnit,
+}
+
+impl Timescale {
+    /// Create a new [`Timescale`] of `value` times `unit`.
+    ///
+    /// Returns `None` if the `value` is zero.
+    #[inline]
+    pub fn new(value: u32, unit: TimeUnit) -> Option<Self> {
+        Some(Self {
+            value: NonZeroU32::new(value)?,
+            unit,
+        })
+    }
+
+    /// Create a [`Timescale`] from a number of femtoseconds.
+    ///
+    /// The largest unit that represents the time exactly is chosen. Returns `None` if the time is
+    /// zero or is too large to be represented.
+    pub fn from_femtoseconds(femtoseconds: u128) -> Option<Self> {
+        let unit = TimeUnit::ALL
+            .into_iter()
+            .rev()
+            .find(|unit| femtoseconds % unit.as_femtoseconds() == 0)?;
+
+        let value = femtoseconds / unit.as_femtoseconds();
+        Self::new(u32::try_from(value).ok()?, unit)
+    }
+
+    /// Get the number of units.
+    #[inline]
+    pub fn value(self) -> u32 {
+        self.value.get()
+    }
+
+    /// Get the unit.
+    #[inline]
+    pub fn unit(self) -> TimeUnit {
+        self.unit
+    }
+
+    /// Get the time in femtoseconds.
+    #[inline]
+    pub fn as_femtoseconds(self) -> u128 {
+        u128::from(self.value()) * self.unit.as_femtoseconds()
+    }
+}
+
+impl Display for Timescale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.value, self.unit)
+    }
+}
+
+impl FromStr for Timescale {
+    type Err = usize;
+
+    /// Parse a time such as `10ns`, `1 us` or `2.5ns`.
+    ///
+    /// On failure, the byte index where the time is malformed is returned.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let number_end = s
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .unwrap_or(s.len());
+        let (number, unit) = s.split_at(number_end);
+
+        let unit_start = number_end + (unit.len() - unit.trim_start().len());
+        let unit = TimeUnit::from_symbol(unit.trim()).ok_or(unit_start)?;
+
+        let (integer, fraction) = number.split_once('.').unwrap_or((number, ""));
+        if integer.is_empty() && fraction.is_empty() {
+            return Err(0);
+        }
+        if let Some(idx) = fraction.find('.') {
+            return Err(integer.len() + 1 + idx);
+        }
+
+        let mut femtoseconds = 0u128;
+        for c in integer.chars().chain(fraction.chars()) {
+            femtoseconds = femtoseconds
+                .checked_mul(10)
+                .and_then(|fs| fs.checked_add(u128::from(c as u8 - b'0')))
+                .ok_or(0usize)?;
+        }
+
+        femtoseconds = femtoseconds
+            .checked_mul(unit.as_femtoseconds())
+            .ok_or(0usize)?;
+
+        let divisor = 10u128.checked_pow(fraction.len() as u32).ok_or(0usize)?;
+        if femtoseconds % divisor != 0 {
+            // The time is more precise than a femtosecond.
+            return Err(number_end);
+        }
+
+        Self::from_femtoseconds(femtoseconds / divisor).ok_or(0usize)
+    }
+}
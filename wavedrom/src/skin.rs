@@ -1,4 +1,7 @@
 //! Module with a WaveDrom skin
+#[cfg(feature = "json5")]
+use std::sync::OnceLock;
+
 use serde::{Deserialize, Serialize};
 
 use crate::signal::options::{
@@ -17,6 +20,54 @@ pub struct Skin {
 }
 
 impl Skin {
+    /// The names of all the built-in skins that can be retrieved with [`Skin::named`].
+    pub const NAMES: &'static [&'static str] = &["default", "narrow", "lowkey", "dark"];
+
+    /// Get one of the built-in skins by its name.
+    ///
+    /// This is the skin that is selected by the `config.skin` property of a WaveJson file. The
+    /// available skins are listed in [`Skin::NAMES`]. Returns `None` if there is no skin with the
+    /// given name.
+    ///
+    /// Each built-in skin is only parsed the first time that it is requested. The built-in skins
+    /// are stored as JSON5, so this needs the `json5` feature.
+    #[cfg(feature = "json5")]
+    pub fn named(name: &str) -> Option<Self> {
+        static NARROW: OnceLock<Skin> = OnceLock::new();
+        static LOWKEY: OnceLock<Skin> = OnceLock::new();
+        static DARK: OnceLock<Skin> = OnceLock::new();
+
+        let (skin, source) = match name {
+            "default" => {
+                return Some(Self {
+                    assemble: Some(PathAssembleOptions::default().into()),
+                    render: Some(RenderOptions::default().into()),
+                })
+            }
+            "narrow" => (&NARROW, include_str!("../skins/narrow.json5")),
+            "lowkey" => (&LOWKEY, include_str!("../skins/lowkey.json5")),
+            "dark" => (&DARK, include_str!("../skins/dark.json5")),
+            _ => return None,
+        };
+
+        let skin =
+            skin.get_or_init(|| Self::from_json5(source).expect("Built-in skins are valid JSON5"));
+        Some(skin.clone())
+    }
+
+    /// Merge the options of the [`Skin`] into an existing set of options.
+    ///
+    /// Options that were not specified by the skin are left unchanged.
+    pub fn merge_into(self, assemble: &mut PathAssembleOptions, render: &mut RenderOptions) {
+        if let Some(skin_assemble) = self.assemble {
+            assemble.merge_in(skin_assemble);
+        }
+
+        if let Some(skin_render) = self.render {
+            render.merge_in(skin_render);
+        }
+    }
+
     /// Generate a set of options from the [`Skin`].
     ///
     /// If some options was not specified by the skin it is set to the default value.
@@ -42,4 +93,32 @@ impl Skin {
     pub fn from_json(s: &str) -> Result<Self, serde_json::error::Error> {
         serde_json::from_str(s)
     }
+
+    /// Parse a [`Skin`] from a YAML file.
+    #[cfg(feature = "yaml")]
+    #[inline]
+    pub fn from_yaml(s: &str) -> Result<Self, serde_yaml::Error> {
+        serde_yaml::from_str(s)
+    }
+
+    /// Parse a [`Skin`] from a TOML file.
+    #[cfg(feature = "toml")]
+    #[inline]
+    pub fn from_toml(s: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(s)
+    }
+}
+
+#[cfg(all(test, feature = "json5"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builtin_skins_parse() {
+        for name in Skin::NAMES {
+            assert!(Skin::named(name).is_some(), "skin '{name}' does not parse");
+        }
+
+        assert!(Skin::named("unknown").is_none());
+    }
 }
@@ -0,0 +1,455 @@
+//! Rendering of signal figures as Unicode text for terminals
+//!
+//! This is meant for places where an SVG cannot be shown, such as a code review or a terminal
+//! over SSH. Every signal becomes a single line of text. Levels are drawn with `▁`, `─` and `▔`
+//! and the transitions between them with `╱` and `╲`. Data boxes are filled with `═` and contain
+//! their data field, which is truncated with `…` if it does not fit. Undefined states are hatched
+//! with `╳` and gaps are shown as `≈`. Groups are drawn as brackets in front of the signal names.
+//!
+//! The header and footer texts and the cycle enumeration markers are drawn above and below the
+//! signals. Edges, vertical markers, highlights and the styles of rich text are left out.
+//!
+//! # Examples
+//!
+//! ```
+//! use wavedrom::signal::{Signal, SignalFigure};
+//!
+//! let figure = SignalFigure::new().add_signals([
+//!     Signal::with_cycle_str("p...").name("clk"),
+//!     Signal::with_cycle_str("x=.x").name("data").add_data_field("head"),
+//! ]);
+//!
+//! let mut text = Vec::new();
+//! figure.assemble().write_text(&mut text)?;
+//!
+//! assert_eq!(
+//!     String::from_utf8(text).unwrap(),
+//!     "clk  ╱▔╲▁╱▔╲▁╱▔╲▁╱▔╲▁\n\
+//!      data ╳╳╳╳╳═head══╳╳╳╳\n",
+//! );
+//! # <Result<(), std::io::Error>>::Ok(())
+//! ```
+
+use std::io;
+
+use crate::signal::markers::CycleEnumerationMarker;
+use crate::signal::{
+    resolved_states, state_offsets, AssembledFigure, CycleOffset, CycleState, Signal,
+};
+
+/// The character that is drawn for a gap.
+const GAP: char = '≈';
+
+/// The character that is used to hatch undefined states and to separate data boxes.
+const HATCH: char = '╳';
+
+/// The options to write an [`AssembledFigure`] as text.
+#[derive(Debug, Clone)]
+pub struct TextOptions {
+    cycle_width: u16,
+}
+
+impl Default for TextOptions {
+    fn default() -> Self {
+        Self { cycle_width: 4 }
+    }
+}
+
+impl TextOptions {
+    /// Create the default options. A cycle is 4 characters wide.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the number of characters that one cycle takes up. This is multiplied by the horizontal
+    /// scale of the figure.
+    ///
+    /// A width of less than 2 characters is increased to 2, so that a clock can be drawn.
+    #[inline]
+    pub fn cycle_width(mut self, cycle_width: u16) -> Self {
+        self.cycle_width = cycle_width.max(2);
+        self
+    }
+}
+
+/// The value of a signal over a part of a line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Level {
+    Low,
+    Middle,
+    High,
+    Box(String),
+    Undefined,
+}
+
+impl Level {
+    fn height(&self) -> Option<u8> {
+        match self {
+            Level::Low => Some(0),
+            Level::Middle => Some(1),
+            Level::High => Some(2),
+            Level::Box(_) | Level::Undefined => None,
+        }
+    }
+
+    fn fill(&self) -> char {
+        match self {
+            Level::Low => '▁',
+            Level::Middle => '─',
+            Level::High => '▔',
+            Level::Box(_) => '═',
+            Level::Undefined => HATCH,
+        }
+    }
+
+    /// The character at the start of a run with `self` after a run with `prev`.
+    fn transition(&self, prev: &Level) -> char {
+        match (prev.height(), self.height()) {
+            (Some(prev), Some(next)) if prev < next => '╱',
+            (Some(prev), Some(next)) if prev > next => '╲',
+            (Some(_), Some(_)) => self.fill(),
+            _ => HATCH,
+        }
+    }
+}
+
+/// A [`Level`] from a column until the start of the next run.
+#[derive(Debug)]
+struct Run {
+    start: usize,
+    level: Level,
+}
+
+/// The wave of a signal as text that is `width` characters wide.
+fn wave_text(signal: &Signal, cycle_width: usize, width: usize) -> String {
+    let column = |at: f64| (at * cycle_width as f64).round().min(width as f64) as usize;
+    let period = CycleOffset::from(signal.get_period());
+    let data = signal.get_data_fields();
+
+    let mut runs: Vec<Run> = Vec::new();
+    let mut gaps = Vec::new();
+    let mut box_index = 0;
+
+    let states = signal.cycles();
+    let offsets: Vec<_> = state_offsets(states, signal.get_period(), signal.get_phase())
+        .take(states.len() + 1)
+        .collect();
+    let cycles = states.iter().zip(resolved_states(states));
+    for ((&state, resolved), at) in cycles.zip(offsets.windows(2)) {
+        use CycleState::*;
+
+        let (at, end) = (at[0], at[1]);
+        if state == Gap {
+            gaps.push(column((at.as_f64() + end.as_f64()) / 2.));
+        }
+        let Some(state) = resolved else {
+            continue;
+        };
+
+        let mut push = |at: CycleOffset, level: Level| {
+            let start = column(at.as_f64());
+            if runs.last().is_some_and(|run| run.start == start) {
+                runs.pop();
+            }
+            runs.push(Run { start, level });
+        };
+
+        match state {
+            PosedgeClockUnmarked | PosedgeClockMarked => {
+                push(at, Level::High);
+                push(at + period.half(), Level::Low);
+            }
+            NegedgeClockUnmarked | NegedgeClockMarked => {
+                push(at, Level::Low);
+                push(at + period.half(), Level::High);
+            }
+            Top | HighUnmarked | HighMarked | Up => push(at, Level::High),
+            Bottom | LowUnmarked | LowMarked | Down => push(at, Level::Low),
+            Middle => push(at, Level::Middle),
+            X => push(at, Level::Undefined),
+            Box2 | Box3 | Box4 | Box5 | Box6 | Box7 | Box8 | Box9 | Data => {
+                let label = data
+                    .get(box_index)
+                    .map(|text| text.to_plain_string())
+                    .unwrap_or_default();
+                box_index += 1;
+
+                push(at, Level::Box(label));
+            }
+            Continue | Gap => unreachable!(),
+        }
+    }
+
+    // The line ends after its last state, even if other lines continue.
+    let line_end = offsets.last().map_or(0, |end| column(end.as_f64()));
+
+    let mut wave = vec![' '; width];
+    for (i, run) in runs.iter().enumerate() {
+        let end = runs.get(i + 1).map_or(line_end, |next| next.start);
+        let Some(columns) = wave.get_mut(run.start..end) else {
+            continue;
+        };
+        columns.fill(run.level.fill());
+
+        // A clock that starts the line still begins with its edge.
+        let prev = match (i.checked_sub(1), &runs[0].level) {
+            (Some(prev), _) => Some(&runs[prev].level),
+            (None, Level::High) if is_clock(states.first()) => Some(&Level::Low),
+            (None, Level::Low) if is_clock(states.first()) => Some(&Level::High),
+            (None, _) => None,
+        };
+        let mut interior_start = 0;
+        if let (Some(prev), Some(first)) = (prev, columns.first_mut()) {
+            *first = run.level.transition(prev);
+            interior_start = 1;
+        }
+
+        if let Level::Box(label) = &run.level {
+            let interior = &mut columns[interior_start..];
+            let label = truncate(label, interior.len());
+            let start = (interior.len() - label.chars().count()) / 2;
+            for (column, c) in interior[start..].iter_mut().zip(label.chars()) {
+                *column = c;
+            }
+        }
+    }
+
+    for gap in gaps {
+        if let Some(column) = wave.get_mut(gap) {
+            *column = GAP;
+        }
+    }
+
+    wave.into_iter().collect()
+}
+
+fn is_clock(state: Option<&CycleState>) -> bool {
+    use CycleState::*;
+
+    matches!(
+        state,
+        Some(PosedgeClockUnmarked | PosedgeClockMarked | NegedgeClockUnmarked | NegedgeClockMarked)
+    )
+}
+
+/// Shorten `text` to at most `width` characters by replacing its end with `…`.
+fn truncate(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        return text.to_string();
+    }
+
+    match width {
+        0 => String::new(),
+        _ => text.chars().take(width - 1).chain(['…']).collect(),
+    }
+}
+
+/// Put `text` into the `line` at `column`, overwriting the characters that are already there.
+fn put_text(line: &mut Vec<char>, column: usize, text: &str) {
+    for (i, c) in text.chars().enumerate() {
+        if line.len() <= column + i {
+            line.resize(column + i + 1, ' ');
+        }
+        line[column + i] = c;
+    }
+}
+
+impl AssembledFigure<'_> {
+    /// Render a [`AssembledFigure`] as Unicode text into a `writer`.
+    #[inline]
+    pub fn write_text(&self, writer: &mut impl io::Write) -> io::Result<()> {
+        self.write_text_with_options(writer, &TextOptions::default())
+    }
+
+    /// Render a [`AssembledFigure`] as Unicode text into a `writer` with a set of options.
+    ///
+    /// Every line of the text ends with a newline and has no trailing whitespace.
+    pub fn write_text_with_options(
+        &self,
+        writer: &mut impl io::Write,
+        options: &TextOptions,
+    ) -> io::Result<()> {
+        let cycle_width = usize::from(options.cycle_width) * usize::from(self.horizontal_scale());
+        let wave_width = self.num_cycles() as usize * cycle_width;
+
+        let names: Vec<_> = self
+            .lines()
+            .iter()
+            .map(|line| line.signal().get_name().to_plain_string())
+            .collect();
+        let name_width = names.iter().map(|name| name.chars().count()).max();
+
+        // Every group nesting level gets a column with its labels and its brackets.
+        let group_markers: Vec<_> = self
+            .group_markers()
+            .iter()
+            .filter(|marker| !marker.is_empty())
+            .collect();
+        let num_depths = group_markers
+            .iter()
+            .map(|marker| marker.depth())
+            .max()
+            .unwrap_or(0) as usize;
+        let label_widths: Vec<_> = (1..=num_depths)
+            .map(|depth| {
+                group_markers
+                    .iter()
+                    .filter(|marker| marker.depth() as usize == depth)
+                    .filter_map(|marker| marker.label())
+                    .map(|label| label.chars().count() + 1)
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+        let group_width = label_widths.iter().map(|width| width + 2).sum::<usize>();
+
+        let wave_x = group_width + name_width.map_or(0, |width| width + 1);
+
+        let mut text: Vec<Vec<char>> = Vec::new();
+        let centered = |text: &mut Vec<Vec<char>>, line: &str| {
+            let x = wave_x + wave_width.saturating_sub(line.chars().count()) / 2;
+            let mut row = Vec::new();
+            put_text(&mut row, x, line);
+            text.push(row);
+        };
+        let cycle_numbers = |marker: Option<_>| {
+            let marker: CycleEnumerationMarker = marker?;
+            if marker.every() == 0 {
+                return None;
+            }
+
+            let mut row = Vec::new();
+            let start = marker.start();
+            for offset in (start..start + self.num_cycles()).step_by(marker.every() as usize) {
+                let number = offset.to_string();
+                let center = wave_x + (offset - start) as usize * cycle_width + cycle_width / 2;
+                let x = center.saturating_sub(number.len() / 2);
+
+                // Numbers that would touch the previous number are left out.
+                if row.len() < x || row.is_empty() {
+                    put_text(&mut row, x, &number);
+                }
+            }
+            Some(row)
+        };
+
+        if let Some(header) = self.header_text() {
+            for line in header.to_plain_string().lines() {
+                centered(&mut text, line);
+            }
+        }
+        text.extend(cycle_numbers(self.top_cycle_marker()));
+
+        for (idx, (line, name)) in self.lines().iter().zip(&names).enumerate() {
+            let mut row = Vec::new();
+
+            let mut x = 0;
+            for (depth, label_width) in (1..=num_depths).zip(&label_widths) {
+                let marker = group_markers.iter().find(|marker| {
+                    marker.depth() as usize == depth
+                        && (marker.start()..marker.end()).contains(&(idx as u32))
+                });
+
+                if let Some(marker) = marker {
+                    let is_first = marker.start() == idx as u32;
+                    let is_last = marker.end() == idx as u32 + 1;
+
+                    if is_first {
+                        put_text(&mut row, x, marker.label().unwrap_or_default());
+                    }
+
+                    let bracket = match (is_first, is_last) {
+                        (true, true) => '[',
+                        (true, false) => '┌',
+                        (false, true) => '└',
+                        (false, false) => '│',
+                    };
+                    put_text(&mut row, x + label_width, &bracket.to_string());
+                }
+
+                x += label_width + 2;
+            }
+
+            put_text(&mut row, group_width, name);
+            let wave = wave_text(line.signal(), cycle_width, wave_width);
+            put_text(&mut row, wave_x, &wave);
+            text.push(row);
+        }
+
+        text.extend(cycle_numbers(self.bottom_cycle_marker()));
+        if let Some(footer) = self.footer_text() {
+            for line in footer.to_plain_string().lines() {
+                centered(&mut text, line);
+            }
+        }
+
+        for row in text {
+            let row: String = row.into_iter().collect();
+            writeln!(writer, "{}", row.trim_end())?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signal::{Period, SignalFigure, SignalFigureSectionGroup};
+
+    fn render(figure: &SignalFigure) -> String {
+        let mut text = Vec::new();
+        figure.assemble().write_text(&mut text).unwrap();
+        String::from_utf8(text).unwrap()
+    }
+
+    #[test]
+    fn levels_and_transitions() {
+        let figure = SignalFigure::new().add_signals([
+            Signal::with_cycle_str("01z0.|1").name("a"),
+            Signal::with_cycle_str("n.")
+                .name("b")
+                .period(Period::new(1, 2).unwrap()),
+        ]);
+
+        assert_eq!(
+            render(&figure),
+            "a ▁▁▁▁╱▔▔▔╲───╲▁▁▁▁▁▁▁▁▁≈▁╱▔▔▔\n\
+             b ╲╱╲╱\n",
+        );
+    }
+
+    #[test]
+    fn data_boxes_are_truncated() {
+        let figure = SignalFigure::new().add_signal(
+            Signal::with_cycle_str("==.2x")
+                .name("bus")
+                .add_data_fields(["Idle", "Fetch", "Calculate"]),
+        );
+
+        assert_eq!(render(&figure), "bus Idle╳═Fetch═╳Ca…╳╳╳╳\n");
+    }
+
+    #[test]
+    fn groups_and_markers() {
+        let figure = SignalFigure::new()
+            .header_text("Title")
+            .top_cycle_marker(0, 1)
+            .add_group(SignalFigureSectionGroup::new(
+                Some("Bus".to_string()),
+                vec![
+                    Signal::with_cycle_str("1.").name("x").into(),
+                    Signal::with_cycle_str("0.").name("yy").into(),
+                ],
+            ));
+
+        assert_eq!(
+            render(&figure),
+            "          Title\n\
+             \x20          0   1\n\
+             Bus ┌ x  ▔▔▔▔▔▔▔▔\n\
+             \x20   └ yy ▁▁▁▁▁▁▁▁\n",
+        );
+    }
+}
@@ -0,0 +1,356 @@
+//! Rich text that can be used for the header, footer, signal names and data labels
+//!
+//! A [`RichText`] is a sequence of [`TextSpan`]s that each have their own [`TextStyle`]. This
+//! allows for bold, italic, subscript, superscript, overlined and colored parts of a text. A plain
+//! string is converted to a [`RichText`] with a single unstyled span.
+//!
+//! # Examples
+//!
+//! ```
+//! use wavedrom::text::{RichText, TextSpan};
+//! use wavedrom::signal::Signal;
+//!
+//! // An active-low chip select
+//! let name = RichText::new()
+//!     .add_span(TextSpan::new("CS").overline())
+//!     .add_span(TextSpan::new("0").subscript());
+//!
+//! let signal = Signal::with_cycle_str("1.0..1").name(name);
+//! ```
+
+use std::fmt::{self, Display};
+
+use crate::escape::escape_str;
+use crate::{Color, Font};
+
+/// A text that consists of a set of styled [`TextSpan`]s.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct RichText {
+    spans: Vec<TextSpan>,
+}
+
+/// A piece of text with a single [`TextStyle`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct TextSpan {
+    text: String,
+    style: TextStyle,
+}
+
+/// The style of a [`TextSpan`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct TextStyle {
+    /// Whether the text is bold
+    pub bold: bool,
+    /// Whether the text is italic
+    pub italic: bool,
+    /// Whether the text has a line above it. This is commonly used for active-low signals.
+    pub overline: bool,
+    /// Whether the text has a line below it
+    pub underline: bool,
+    /// Whether the text has a line through it
+    pub line_through: bool,
+    /// A shift of the text with regards to the baseline
+    pub baseline_shift: Option<BaselineShift>,
+    /// The font size of the text. If this is not given, the font size of the surrounding text is
+    /// used.
+    pub font_size: Option<u32>,
+    /// The color of the text. If this is not given, the color of the surrounding text is used.
+    pub color: Option<Color>,
+}
+
+/// A shift of a [`TextSpan`] with regards to the baseline
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BaselineShift {
+    /// Subscript text
+    Subscript,
+    /// Superscript text
+    Superscript,
+}
+
+impl BaselineShift {
+    /// The percentage of the font size that shifted text is rendered at
+    const FONT_SIZE_PERCENTAGE: u32 = 70;
+}
+
+impl RichText {
+    /// Create a new empty [`RichText`].
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a [`TextSpan`] to the end of the [`RichText`].
+    #[inline]
+    pub fn add_span(mut self, span: TextSpan) -> Self {
+        self.push(span);
+        self
+    }
+
+    /// Add a [`TextSpan`] to the end of the [`RichText`].
+    #[inline]
+    pub fn push(&mut self, span: TextSpan) {
+        self.spans.push(span);
+    }
+
+    /// Get the [`TextSpan`]s of the [`RichText`].
+    #[inline]
+    pub fn spans(&self) -> &[TextSpan] {
+        &self.spans
+    }
+
+    /// Returns whether the [`RichText`] contains no characters.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.spans.iter().all(|span| span.text.is_empty())
+    }
+
+    /// Returns whether the [`RichText`] contains any styled [`TextSpan`]s.
+    #[inline]
+    pub fn is_styled(&self) -> bool {
+        self.spans
+            .iter()
+            .any(|span| span.style != TextStyle::default())
+    }
+
+    /// Get the text of the [`RichText`] without any styling.
+    pub fn to_plain_string(&self) -> String {
+        self.spans.iter().map(|span| &span.text[..]).collect()
+    }
+
+    /// Returns a value that displays the [`RichText`] as a sequence of SVG `<tspan>` elements.
+    #[inline]
+    pub(crate) fn svg_tspans(&self, font_size: u32) -> SvgTspans<'_> {
+        SvgTspans {
+            text: self,
+            font_size,
+        }
+    }
+}
+
+impl TextSpan {
+    /// Create a new unstyled [`TextSpan`].
+    #[inline]
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            style: TextStyle::default(),
+        }
+    }
+
+    /// Create a new [`TextSpan`] with a [`TextStyle`].
+    #[inline]
+    pub fn with_style(text: impl Into<String>, style: TextStyle) -> Self {
+        Self {
+            text: text.into(),
+            style,
+        }
+    }
+
+    /// Make the [`TextSpan`] bold.
+    #[inline]
+    pub fn bold(mut self) -> Self {
+        self.style.bold = true;
+        self
+    }
+
+    /// Make the [`TextSpan`] italic.
+    #[inline]
+    pub fn italic(mut self) -> Self {
+        self.style.italic = true;
+        self
+    }
+
+    /// Put a line above the [`TextSpan`].
+    #[inline]
+    pub fn overline(mut self) -> Self {
+        self.style.overline = true;
+        self
+    }
+
+    /// Put a line below the [`TextSpan`].
+    #[inline]
+    pub fn underline(mut self) -> Self {
+        self.style.underline = true;
+        self
+    }
+
+    /// Put a line through the [`TextSpan`].
+    #[inline]
+    pub fn line_through(mut self) -> Self {
+        self.style.line_through = true;
+        self
+    }
+
+    /// Make the [`TextSpan`] subscript.
+    #[inline]
+    pub fn subscript(mut self) -> Self {
+        self.style.baseline_shift = Some(BaselineShift::Subscript);
+        self
+    }
+
+    /// Make the [`TextSpan`] superscript.
+    #[inline]
+    pub fn superscript(mut self) -> Self {
+        self.style.baseline_shift = Some(BaselineShift::Superscript);
+        self
+    }
+
+    /// Set the font size of the [`TextSpan`].
+    #[inline]
+    pub fn font_size(mut self, font_size: u32) -> Self {
+        self.style.font_size = Some(font_size);
+        self
+    }
+
+    /// Set the color of the [`TextSpan`].
+    #[inline]
+    pub fn color(mut self, color: Color) -> Self {
+        self.style.color = Some(color);
+        self
+    }
+
+    /// Get the text of the [`TextSpan`].
+    #[inline]
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Get the [`TextStyle`] of the [`TextSpan`].
+    #[inline]
+    pub fn style(&self) -> &TextStyle {
+        &self.style
+    }
+}
+
+impl TextStyle {
+    /// Get the font size that the text is rendered at given the font size of the surrounding
+    /// text.
+    pub fn effective_font_size(&self, font_size: u32) -> u32 {
+        let font_size = self.font_size.unwrap_or(font_size);
+
+        if self.baseline_shift.is_some() {
+            font_size * BaselineShift::FONT_SIZE_PERCENTAGE / 100
+        } else {
+            font_size
+        }
+    }
+}
+
+impl From<String> for RichText {
+    #[inline]
+    fn from(text: String) -> Self {
+        Self {
+            spans: vec![TextSpan::new(text)],
+        }
+    }
+}
+
+impl From<&str> for RichText {
+    #[inline]
+    fn from(text: &str) -> Self {
+        Self::from(text.to_string())
+    }
+}
+
+impl From<&String> for RichText {
+    #[inline]
+    fn from(text: &String) -> Self {
+        Self::from(text.clone())
+    }
+}
+
+impl From<TextSpan> for RichText {
+    #[inline]
+    fn from(span: TextSpan) -> Self {
+        Self { spans: vec![span] }
+    }
+}
+
+impl FromIterator<TextSpan> for RichText {
+    fn from_iter<T: IntoIterator<Item = TextSpan>>(iter: T) -> Self {
+        Self {
+            spans: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl Font {
+    /// Get an upperbound on the width of a [`RichText`] for a given `font_size`.
+    ///
+    /// Every [`TextSpan`] is measured at its own font size. Bold spans are assumed to be a tenth
+    /// wider than their regular counterparts.
+    pub fn get_rich_text_width(&self, text: &RichText, font_size: u32) -> u32 {
+        text.spans()
+            .iter()
+            .map(|span| {
+                let style = span.style();
+                let width = self.get_text_width(span.text(), style.effective_font_size(font_size));
+
+                if style.bold {
+                    width + width.div_ceil(10)
+                } else {
+                    width
+                }
+            })
+            .sum()
+    }
+}
+
+pub(crate) struct SvgTspans<'a> {
+    text: &'a RichText,
+    font_size: u32,
+}
+
+impl Display for SvgTspans<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for span in self.text.spans() {
+            let style = span.style();
+
+            write!(f, "<tspan")?;
+
+            if style.bold {
+                write!(f, r#" font-weight="bold""#)?;
+            }
+            if style.italic {
+                write!(f, r#" font-style="italic""#)?;
+            }
+
+            let decorations = [
+                (style.overline, "overline"),
+                (style.underline, "underline"),
+                (style.line_through, "line-through"),
+            ];
+            if decorations.iter().any(|(is_set, _)| *is_set) {
+                write!(f, r#" text-decoration=""#)?;
+                let mut is_first = true;
+                for (_, decoration) in decorations.iter().filter(|(is_set, _)| *is_set) {
+                    if !is_first {
+                        write!(f, " ")?;
+                    }
+                    write!(f, "{decoration}")?;
+                    is_first = false;
+                }
+                write!(f, r#"""#)?;
+            }
+
+            match style.baseline_shift {
+                Some(BaselineShift::Subscript) => write!(f, r#" baseline-shift="sub""#)?,
+                Some(BaselineShift::Superscript) => write!(f, r#" baseline-shift="super""#)?,
+                None => {}
+            }
+
+            let font_size = style.effective_font_size(self.font_size);
+            if font_size != self.font_size {
+                write!(f, r#" font-size="{font_size}""#)?;
+            }
+
+            if let Some(color) = style.color {
+                write!(f, r#" fill="{color}""#)?;
+            }
+
+            write!(f, ">{}</tspan>", escape_str(span.text()))?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,468 @@
+use std::io;
+
+use super::{resize_bits, MAX_WIDTH};
+use crate::signal::{
+    gcd, resolved_states, state_offsets, AssembledFigure, CycleOffset, CycleState, Signal,
+};
+use crate::signal::{TimeUnit, Timescale};
+
+/// The options to write an [`AssembledFigure`] as a VCD file.
+#[derive(Debug, Clone)]
+pub struct VcdExportOptions {
+    cycle_time: Option<Timescale>,
+    top_scope: String,
+}
+
+impl Default for VcdExportOptions {
+    fn default() -> Self {
+        Self {
+            cycle_time: None,
+            top_scope: "top".to_string(),
+        }
+    }
+}
+
+impl VcdExportOptions {
+    /// Create the default options. A cycle lasts as long as the time base of the figure, or `1ns`
+    /// if the figure has none, and the signals are placed in a module called `top`.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the time that one cycle of the figure lasts, overriding the time base of the figure.
+    ///
+    /// The `$timescale` of the VCD file is chosen so that all value changes fall on a whole
+    /// timestamp.
+    #[inline]
+    pub fn cycle_time(mut self, cycle_time: Timescale) -> Self {
+        self.cycle_time = Some(cycle_time);
+        self
+    }
+
+    /// Set the name of the module that contains all the signals.
+    #[inline]
+    pub fn top_scope(mut self, top_scope: impl Into<String>) -> Self {
+        self.top_scope = top_scope.into();
+        self
+    }
+}
+
+/// A value of a data field as a bit string and the width that was given with it.
+fn parse_data_value(text: &str) -> Option<(String, Option<u32>)> {
+    let text: String = text.trim().chars().filter(|c| *c != '_').collect();
+    let lower = text.to_ascii_lowercase();
+
+    // Verilog style sized and based numbers (e.g. `8'hFF` or `'b1010`).
+    let (width, radix, digits): (_, u32, _) = if let Some((width, rest)) = lower.split_once('\'') {
+        let width = if width.is_empty() {
+            None
+        } else {
+            Some(
+                width
+                    .parse()
+                    .ok()
+                    .filter(|width| (1..=MAX_WIDTH).contains(width))?,
+            )
+        };
+        let rest = rest.strip_prefix('s').unwrap_or(rest);
+        let radix = match rest.chars().next()? {
+            'b' => 2,
+            'o' => 8,
+            'd' => 10,
+            'h' => 16,
+            _ => return None,
+        };
+        (width, radix, &rest[1..])
+    } else if let Some(digits) = lower.strip_prefix("0x") {
+        (None, 16, digits)
+    } else if let Some(digits) = lower.strip_prefix("0b") {
+        (None, 2, digits)
+    } else if let Some(digits) = lower.strip_prefix("0o") {
+        (None, 8, digits)
+    } else {
+        (None, 10, &lower[..])
+    };
+
+    if digits.is_empty() {
+        return None;
+    }
+
+    let bits = match digits {
+        "x" | "z" => digits.to_string(),
+        _ if radix == 10 => {
+            let value: u128 = digits.parse().ok()?;
+            format!("{value:b}")
+        }
+        _ => {
+            let digit_bits = radix.trailing_zeros() as usize;
+            let mut bits = String::with_capacity(digits.len() * digit_bits);
+            for c in digits.chars() {
+                match c {
+                    'x' | 'z' => bits.extend(std::iter::repeat(c).take(digit_bits)),
+                    c => {
+                        let value = c.to_digit(radix)?;
+                        bits.push_str(&format!("{value:0digit_bits$b}"));
+                    }
+                }
+            }
+
+            // Remove leading zeros, but keep the extension of `x` and `z`.
+            let trimmed = bits.trim_start_matches('0');
+            match trimmed {
+                "" => "0".to_string(),
+                _ => trimmed.to_string(),
+            }
+        }
+    };
+
+    Some((bits, width))
+}
+
+/// The value changes of a single signal.
+struct LineChanges {
+    width: usize,
+    changes: Vec<(CycleOffset, String)>,
+}
+
+impl LineChanges {
+    fn new(signal: &Signal) -> Self {
+        let period = signal.get_period();
+        let half_period = CycleOffset::from(period).half();
+        let data = signal.get_data_fields();
+
+        let mut width = 1;
+        let mut changes = Vec::new();
+        let mut box_index = 0;
+
+        let mut push = |at: CycleOffset, value: String| {
+            if changes.last().map(|(_, last)| last) != Some(&value) {
+                changes.push((at, value));
+            }
+        };
+
+        let offsets = state_offsets(signal.cycles(), period, signal.get_phase());
+        for (state, at) in resolved_states(signal.cycles()).zip(offsets) {
+            use CycleState::*;
+
+            let Some(state) = state else {
+                continue;
+            };
+
+            match state {
+                PosedgeClockUnmarked | PosedgeClockMarked => {
+                    push(at, "1".to_string());
+                    push(at + half_period, "0".to_string());
+                }
+                NegedgeClockUnmarked | NegedgeClockMarked => {
+                    push(at, "0".to_string());
+                    push(at + half_period, "1".to_string());
+                }
+                Top | HighUnmarked | HighMarked | Up => push(at, "1".to_string()),
+                Bottom | LowUnmarked | LowMarked | Down => push(at, "0".to_string()),
+                Middle => push(at, "z".to_string()),
+                X => push(at, "x".to_string()),
+                Box2 | Box3 | Box4 | Box5 | Box6 | Box7 | Box8 | Box9 | Data => {
+                    let value = data
+                        .get(box_index)
+                        .and_then(|text| parse_data_value(&text.to_plain_string()));
+                    box_index += 1;
+
+                    let bits = match value {
+                        Some((bits, Some(explicit_width))) => {
+                            let explicit_width = explicit_width as usize;
+                            width = width.max(explicit_width);
+                            resize_bits(&bits, explicit_width)
+                        }
+                        Some((bits, None)) => {
+                            width = width.max(bits.len());
+                            bits
+                        }
+                        None => "x".to_string(),
+                    };
+                    push(at, bits);
+                }
+                Continue | Gap => unreachable!(),
+            }
+        }
+
+        Self { width, changes }
+    }
+}
+
+/// Create an identifier code from the printable ASCII characters.
+fn identifier_code(mut idx: usize) -> String {
+    const FIRST: u8 = b'!';
+    const NUM_CHARS: usize = (b'~' - b'!' + 1) as usize;
+
+    let mut code = String::new();
+    loop {
+        code.push(char::from(FIRST + (idx % NUM_CHARS) as u8));
+        idx /= NUM_CHARS;
+        if idx == 0 {
+            return code;
+        }
+        idx -= 1;
+    }
+}
+
+/// Names in VCD files cannot contain whitespace.
+fn vcd_name(name: &str) -> String {
+    name.split_whitespace().collect::<Vec<_>>().join("_")
+}
+
+impl AssembledFigure<'_> {
+    /// Write the [`AssembledFigure`] as a Value Change Dump into a `writer`.
+    ///
+    /// Every line with cycles becomes a variable. Lines with data boxes become vectors, where the
+    /// value of each box is parsed from its data field. Decimal numbers, numbers prefixed with
+    /// `0x`, `0o` or `0b`, and Verilog style numbers such as `8'hFF` are understood. Data fields
+    /// that are not a number become `x`. Labeled groups become nested scopes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wavedrom::signal::{Signal, SignalFigure, TimeUnit, Timescale};
+    /// use wavedrom::vcd::VcdExportOptions;
+    ///
+    /// let figure = SignalFigure::new().add_signals([
+    ///     Signal::with_cycle_str("p...").name("clk"),
+    ///     Signal::with_cycle_str("x=.=").name("data").add_data_fields(["0x1F", "8'd3"]),
+    /// ]);
+    ///
+    /// let mut vcd = Vec::new();
+    /// let options = VcdExportOptions::new().cycle_time(Timescale::new(10, TimeUnit::Nanoseconds).unwrap());
+    /// figure.assemble().write_vcd(&mut vcd, &options)?;
+    /// # <Result<(), std::io::Error>>::Ok(())
+    /// ```
+    pub fn write_vcd(
+        &self,
+        writer: &mut impl io::Write,
+        options: &VcdExportOptions,
+    ) -> io::Result<()> {
+        let cycle_time = options
+            .cycle_time
+            .or(self.timescale())
+            .unwrap_or(Timescale::new(1, TimeUnit::Nanoseconds).expect("Is not zero"));
+        let cycle_femtoseconds = cycle_time.as_femtoseconds();
+        let to_femtoseconds = |offset: CycleOffset| {
+            let numerator = u128::from(offset.numerator()) * cycle_femtoseconds;
+            let denominator = u128::from(offset.denominator());
+            (numerator + denominator / 2) / denominator
+        };
+
+        let lines: Vec<_> = self
+            .lines()
+            .iter()
+            .map(|line| {
+                (!line.signal().cycles().is_empty()).then(|| LineChanges::new(line.signal()))
+            })
+            .collect();
+
+        let end_time = u128::from(self.num_cycles()) * cycle_femtoseconds;
+        let mut changes: Vec<(u128, usize, &str)> = lines
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, line)| Some((idx, line.as_ref()?)))
+            .flat_map(|(idx, line)| {
+                line.changes
+                    .iter()
+                    .map(move |(at, value)| (to_femtoseconds(*at), idx, value.as_str()))
+            })
+            .collect();
+        changes.sort_by_key(|(time, idx, _)| (*time, *idx));
+
+        // The largest timescale in which all the value changes fall on a whole timestamp.
+        let divisor = changes
+            .iter()
+            .map(|(time, _, _)| *time)
+            .fold(gcd(end_time, cycle_femtoseconds), gcd);
+        let timescale = TimeUnit::ALL
+            .into_iter()
+            .rev()
+            .flat_map(|unit| [100, 10, 1].map(|value| Timescale::new(value, unit)))
+            .flatten()
+            .find(|timescale| divisor % timescale.as_femtoseconds() == 0)
+            .expect("A femtosecond divides all times");
+        let timescale_femtoseconds = timescale.as_femtoseconds();
+
+        writeln!(
+            writer,
+            "$version wavedrom-rs {} $end",
+            env!("CARGO_PKG_VERSION")
+        )?;
+        writeln!(writer, "$timescale {timescale} $end")?;
+        writeln!(
+            writer,
+            "$scope module {} $end",
+            vcd_name(&options.top_scope)
+        )?;
+
+        let mut group_markers: Vec<_> = self
+            .group_markers()
+            .iter()
+            .filter(|marker| marker.label().is_some() && !marker.is_empty())
+            .collect();
+        group_markers.sort_by_key(|marker| (marker.start(), marker.depth()));
+
+        let mut group_markers = group_markers.into_iter().peekable();
+        let mut open_groups = Vec::new();
+        let mut ids = vec![None; lines.len()];
+        let mut num_ids = 0;
+        for (idx, line) in lines.iter().enumerate() {
+            let idx_u32 = idx as u32;
+
+            while open_groups.last().is_some_and(|end| *end <= idx_u32) {
+                open_groups.pop();
+                writeln!(writer, "$upscope $end")?;
+            }
+
+            while let Some(marker) = group_markers.next_if(|marker| marker.start() == idx_u32) {
+                open_groups.push(marker.end());
+                let label = marker.label().unwrap_or_default();
+                writeln!(writer, "$scope module {} $end", vcd_name(label))?;
+            }
+
+            let Some(line) = line else {
+                continue;
+            };
+
+            let id = identifier_code(num_ids);
+            num_ids += 1;
+
+            let mut name = vcd_name(&self.lines()[idx].signal().get_name().to_plain_string());
+            if name.is_empty() {
+                name = format!("signal{idx}");
+            }
+
+            if line.width == 1 {
+                writeln!(writer, "$var wire 1 {id} {name} $end")?;
+            } else {
+                let msb = line.width - 1;
+                writeln!(
+                    writer,
+                    "$var wire {} {id} {name} [{msb}:0] $end",
+                    line.width
+                )?;
+            }
+
+            ids[idx] = Some(id);
+        }
+
+        for _ in open_groups {
+            writeln!(writer, "$upscope $end")?;
+        }
+        writeln!(writer, "$upscope $end")?;
+        writeln!(writer, "$enddefinitions $end")?;
+
+        let write_value = |writer: &mut dyn io::Write, idx: usize, value: &str| {
+            let (Some(line), Some(id)) = (&lines[idx], &ids[idx]) else {
+                return Ok(());
+            };
+
+            if line.width == 1 {
+                writeln!(writer, "{value}{id}")
+            } else {
+                writeln!(writer, "b{} {id}", resize_bits(value, line.width))
+            }
+        };
+
+        // Initial values
+        writeln!(writer, "#0")?;
+        writeln!(writer, "$dumpvars")?;
+        for (idx, line) in lines.iter().enumerate() {
+            let Some(line) = line else {
+                continue;
+            };
+
+            let initial = line
+                .changes
+                .first()
+                .filter(|(at, _)| to_femtoseconds(*at) == 0)
+                .map_or("x", |(_, value)| value.as_str());
+            write_value(writer, idx, initial)?;
+        }
+        writeln!(writer, "$end")?;
+
+        let mut current_time = 0;
+        for (time, idx, value) in changes {
+            if time == 0 {
+                continue;
+            }
+
+            if time != current_time {
+                current_time = time;
+                writeln!(writer, "#{}", time / timescale_femtoseconds)?;
+            }
+
+            write_value(writer, idx, value)?;
+        }
+
+        if end_time > current_time {
+            writeln!(writer, "#{}", end_time / timescale_femtoseconds)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signal::{Period, SignalFigure};
+    use crate::vcd::{Vcd, VcdValue};
+
+    #[test]
+    fn data_values() {
+        assert_eq!(parse_data_value("0x1F"), Some(("11111".into(), None)));
+        assert_eq!(parse_data_value("8'hx"), Some(("x".into(), Some(8))));
+        assert_eq!(parse_data_value("'b01z1"), Some(("1z1".into(), None)));
+        assert_eq!(parse_data_value("1_000"), Some(("1111101000".into(), None)));
+        assert_eq!(parse_data_value("Idle"), None);
+        assert_eq!(parse_data_value("4294967295'h1"), None);
+    }
+
+    #[test]
+    fn export_round_trip() {
+        let figure = SignalFigure::new().add_signals([
+            Signal::with_cycle_str("p.")
+                .name("clk")
+                .period(Period::from(2)),
+            Signal::with_cycle_str("z=.=x")
+                .name("data bus")
+                .add_data_fields(["4'hA", "Idle"]),
+        ]);
+
+        let mut out = Vec::new();
+        let options = VcdExportOptions::new().cycle_time("10ns".parse().unwrap());
+        figure.assemble().write_vcd(&mut out, &options).unwrap();
+
+        let vcd: Vcd = String::from_utf8(out).unwrap().parse().unwrap();
+        assert_eq!(vcd.timescale(), Timescale::new(10, TimeUnit::Nanoseconds));
+        assert_eq!(vcd.end_time(), 5);
+
+        let variables = vcd.variables();
+        assert_eq!(variables[0].0, "top.clk");
+        assert_eq!(variables[1].0, "top.data_bus");
+        assert_eq!(variables[1].1.width(), 4);
+
+        let bits = |changes: &[(u64, VcdValue)]| -> Vec<(u64, String)> {
+            changes
+                .iter()
+                .map(|(time, value)| match value {
+                    VcdValue::Bits(bits) => (*time, bits.clone()),
+                    _ => unreachable!(),
+                })
+                .collect()
+        };
+
+        assert_eq!(
+            bits(vcd.changes(variables[0].1)),
+            [(0, "1"), (1, "0"), (2, "1"), (3, "0")].map(|(t, v)| (t, v.to_string()))
+        );
+        assert_eq!(
+            bits(vcd.changes(variables[1].1)),
+            [(0, "zzzz"), (1, "1010"), (3, "xxxx")].map(|(t, v)| (t, v.to_string()))
+        );
+    }
+}
@@ -0,0 +1,509 @@
+use std::num::NonZeroU64;
+
+use super::{resize_bits, Vcd, VcdError, VcdItem, VcdValue, VcdVariable};
+use crate::signal::{
+    gcd, CycleState, Signal, SignalFigure, SignalFigureSection, SignalFigureSectionGroup, Timescale,
+};
+
+/// How the value changes of a [`Vcd`] are sampled into cycles.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum VcdSampling {
+    /// Sample with the largest time step that falls on all the value changes of the selected
+    /// signals within the window.
+    #[default]
+    Auto,
+    /// Sample every time step, in units of the timescale of the dump.
+    TimeStep(NonZeroU64),
+    /// Sample at every rising edge of a clock signal. A cycle lasts until the next rising edge.
+    ///
+    /// A rising edge is a change to `1` from any other value. The initial value of the clock is
+    /// never a rising edge. The clock itself is shown as a positive edge clock.
+    Clock(String),
+}
+
+/// How vector values are written into the data boxes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum VcdRadix {
+    /// Binary digits (e.g. `1010`)
+    Binary,
+    /// Decimal digits (e.g. `10`). Values with undefined or high-impedance bits are shown as `x`.
+    Decimal,
+    /// Hexadecimal digits (e.g. `A`). Digits with undefined bits are shown as `x` and digits that
+    /// only contain high-impedance bits are shown as `z`.
+    #[default]
+    Hexadecimal,
+}
+
+/// The options to create a [`SignalFigure`] from a [`Vcd`].
+///
+/// Times are given in units of the timescale of the dump.
+#[derive(Debug, Clone, Default)]
+pub struct VcdImportOptions {
+    signals: Option<Vec<String>>,
+    start: u64,
+    end: Option<u64>,
+    sampling: VcdSampling,
+    radix: VcdRadix,
+    max_cycles: Option<usize>,
+}
+
+impl VcdImportOptions {
+    /// The default largest amount of cycles that is imported.
+    pub const DEFAULT_MAX_CYCLES: usize = 10_000;
+
+    /// Create the default options. This imports all signals of the whole dump with
+    /// [`VcdSampling::Auto`].
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Select signals to import. By default, all signals are imported.
+    ///
+    /// A signal is selected by its hierarchical path (e.g. `top.cpu.clk`) or, if the name contains
+    /// no `.`, by its name in any scope. The bit index may be included in the name (e.g.
+    /// `data[7:0]`). The signals are placed in the order in which they are declared in the dump.
+    pub fn signals(mut self, signals: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.signals
+            .get_or_insert_with(Vec::new)
+            .extend(signals.into_iter().map(Into::into));
+        self
+    }
+
+    /// Set the time at which the window starts.
+    #[inline]
+    pub fn start(mut self, start: u64) -> Self {
+        self.start = start;
+        self
+    }
+
+    /// Set the time at which the window ends. This time is not included in the window.
+    ///
+    /// By default, the window ends at the last timestamp of the dump.
+    #[inline]
+    pub fn end(mut self, end: u64) -> Self {
+        self.end = Some(end);
+        self
+    }
+
+    /// Set how the value changes are sampled into cycles.
+    #[inline]
+    pub fn sampling(mut self, sampling: VcdSampling) -> Self {
+        self.sampling = sampling;
+        self
+    }
+
+    /// Sample every `step` units of time. A `step` of zero selects [`VcdSampling::Auto`].
+    #[inline]
+    pub fn time_step(self, step: u64) -> Self {
+        self.sampling(NonZeroU64::new(step).map_or(VcdSampling::Auto, VcdSampling::TimeStep))
+    }
+
+    /// Sample at every rising edge of the `clock` signal.
+    ///
+    /// The clock is selected in the same way as in [`VcdImportOptions::signals`].
+    #[inline]
+    pub fn clock(self, clock: impl Into<String>) -> Self {
+        self.sampling(VcdSampling::Clock(clock.into()))
+    }
+
+    /// Set how vector values are written into the data boxes.
+    #[inline]
+    pub fn radix(mut self, radix: VcdRadix) -> Self {
+        self.radix = radix;
+        self
+    }
+
+    /// Set the largest amount of cycles that is imported. By default, this is
+    /// [`VcdImportOptions::DEFAULT_MAX_CYCLES`].
+    ///
+    /// If the sampling gives more cycles, the import fails with [`VcdError::TooManyCycles`]
+    /// instead of creating a huge figure.
+    #[inline]
+    pub fn max_cycles(mut self, max_cycles: usize) -> Self {
+        self.max_cycles = Some(max_cycles);
+        self
+    }
+
+    fn is_selected(&self, path: &str, variable: &VcdVariable) -> bool {
+        self.signals.as_ref().map_or(true, |signals| {
+            signals
+                .iter()
+                .any(|selection| is_match(selection, path, variable))
+        })
+    }
+}
+
+fn is_match(selection: &str, path: &str, variable: &VcdVariable) -> bool {
+    let index = variable.index().unwrap_or_default();
+
+    if selection.contains('.') {
+        selection == path || selection.strip_prefix(path) == Some(index)
+    } else {
+        selection == variable.reference() || selection == variable.name()
+    }
+}
+
+struct Import<'a> {
+    vcd: &'a Vcd,
+    options: &'a VcdImportOptions,
+    clock: Option<&'a VcdVariable>,
+    times: Vec<u64>,
+}
+
+pub(super) fn to_signal_figure(
+    vcd: &Vcd,
+    options: &VcdImportOptions,
+) -> Result<SignalFigure, VcdError> {
+    let variables = vcd.variables();
+
+    let find = |selection: &str| {
+        variables
+            .iter()
+            .find(|(path, variable)| is_match(selection, path, variable))
+            .map(|(_, variable)| *variable)
+            .ok_or_else(|| VcdError::UnknownSignal(selection.to_string()))
+    };
+
+    for selection in options.signals.iter().flatten() {
+        find(selection)?;
+    }
+
+    let start = options.start;
+    let end = options.end.unwrap_or(vcd.end_time());
+    let max_cycles = options
+        .max_cycles
+        .unwrap_or(VcdImportOptions::DEFAULT_MAX_CYCLES);
+
+    // The samples are only created once it is known that there are not too many of them.
+    let sample = |step: u64| {
+        let cycles = end.saturating_sub(start).div_ceil(step);
+        if cycles > u64::try_from(max_cycles).unwrap_or(u64::MAX) {
+            return Err(VcdError::TooManyCycles { cycles, max_cycles });
+        }
+
+        Ok((start..end)
+            .step_by(usize::try_from(step).unwrap_or(usize::MAX))
+            .collect())
+    };
+
+    let mut clock = None;
+    let times = match &options.sampling {
+        VcdSampling::TimeStep(step) => sample(step.get())?,
+        VcdSampling::Clock(selection) => {
+            let clock_variable = find(selection)?;
+            clock = Some(clock_variable);
+
+            let mut previous = None;
+            let mut edges = Vec::new();
+            for (time, value) in vcd.changes(clock_variable) {
+                let is_high = matches!(value, VcdValue::Bits(bits) if bits.ends_with('1'));
+                if is_high && previous == Some(false) && (start..end).contains(time) {
+                    edges.push(*time);
+                }
+                previous = Some(is_high);
+            }
+
+            if edges.len() > max_cycles {
+                return Err(VcdError::TooManyCycles {
+                    cycles: edges.len() as u64,
+                    max_cycles,
+                });
+            }
+            edges
+        }
+        VcdSampling::Auto => {
+            let step = variables
+                .iter()
+                .filter(|(path, variable)| options.is_selected(path, variable))
+                .flat_map(|(_, variable)| vcd.changes(variable))
+                .map(|(time, _)| *time)
+                .filter(|time| (start..end).contains(time))
+                .fold(0, |step, time| gcd(step, time - start));
+
+            let step = if step == 0 {
+                end.saturating_sub(start)
+            } else {
+                step
+            };
+            if step == 0 {
+                Vec::new()
+            } else {
+                sample(step)?
+            }
+        }
+    };
+
+    // When the samples are evenly spaced, a cycle has a fixed duration that becomes the time base
+    // of the figure.
+    let cycle_time = match times.as_slice() {
+        [first, second, rest @ ..] => {
+            let step = second - first;
+            let is_even = rest.iter().zip(&times[1..]).all(|(b, a)| b - a == step);
+            is_even.then_some(step)
+        }
+        _ => None,
+    };
+    let timescale = cycle_time
+        .zip(vcd.timescale())
+        .and_then(|(step, timescale)| {
+            Timescale::from_femtoseconds(u128::from(step) * timescale.as_femtoseconds())
+        });
+
+    let import = Import {
+        vcd,
+        options,
+        clock,
+        times,
+    };
+
+    let sections = import.sections("", vcd.items());
+    let figure = SignalFigure::new().add_sections(sections);
+
+    Ok(match timescale {
+        Some(timescale) => figure.timescale(timescale),
+        None => figure,
+    })
+}
+
+impl Import<'_> {
+    fn sections(&self, prefix: &str, items: &[VcdItem]) -> Vec<SignalFigureSection> {
+        items
+            .iter()
+            .filter_map(|item| match item {
+                VcdItem::Scope(scope) => {
+                    let prefix = format!("{prefix}{}.", scope.name());
+                    let items = self.sections(&prefix, scope.items());
+                    (!items.is_empty()).then(|| {
+                        SignalFigureSection::Group(SignalFigureSectionGroup::new(
+                            Some(scope.name().to_string()),
+                            items,
+                        ))
+                    })
+                }
+                VcdItem::Variable(variable) => {
+                    let path = format!("{prefix}{}", variable.reference());
+                    self.options
+                        .is_selected(&path, variable)
+                        .then(|| SignalFigureSection::Signal(self.signal(variable)))
+                }
+            })
+            .collect()
+    }
+
+    fn signal(&self, variable: &VcdVariable) -> Signal {
+        let signal = Signal::new().name(variable.name());
+
+        if self.clock.is_some_and(|clock| clock.id() == variable.id()) {
+            return signal
+                .add_n_cycles(self.times.len().min(1), CycleState::PosedgeClockUnmarked)
+                .add_n_cycles(self.times.len().saturating_sub(1), CycleState::Continue);
+        }
+
+        let mut cycles = Vec::with_capacity(self.times.len());
+        let mut data = Vec::new();
+        let mut previous = None;
+        for &time in &self.times {
+            let value = self.vcd.value_at(variable, time);
+            if previous == Some(value) {
+                cycles.push(CycleState::Continue);
+                continue;
+            }
+            previous = Some(value);
+
+            let (state, label) = self.cycle_state(variable, value);
+            cycles.push(state);
+            data.extend(label);
+        }
+
+        signal.add_cycles(cycles).add_data_fields(data)
+    }
+
+    fn cycle_state(
+        &self,
+        variable: &VcdVariable,
+        value: Option<&VcdValue>,
+    ) -> (CycleState, Option<String>) {
+        let bits = match value {
+            None => return (CycleState::X, None),
+            Some(VcdValue::Real(text) | VcdValue::String(text)) => {
+                return (CycleState::Data, Some(text.clone()))
+            }
+            Some(VcdValue::Bits(bits)) => resize_bits(
+                bits,
+                usize::try_from(variable.width()).unwrap_or(usize::MAX),
+            ),
+        };
+
+        if bits.len() == 1 {
+            let state = match bits.as_str() {
+                "1" => CycleState::Top,
+                "0" => CycleState::Bottom,
+                "z" => CycleState::Middle,
+                _ => CycleState::X,
+            };
+            return (state, None);
+        }
+
+        if bits.chars().all(|c| c == 'x') {
+            (CycleState::X, None)
+        } else if bits.chars().all(|c| c == 'z') {
+            (CycleState::Middle, None)
+        } else {
+            (
+                CycleState::Data,
+                Some(format_bits(&bits, self.options.radix)),
+            )
+        }
+    }
+}
+
+fn format_bits(bits: &str, radix: VcdRadix) -> String {
+    let is_defined = bits.chars().all(|c| c == '0' || c == '1');
+
+    match radix {
+        VcdRadix::Binary => bits.to_string(),
+        VcdRadix::Decimal if !is_defined => "x".to_string(),
+        VcdRadix::Decimal if bits.len() <= 128 => u128::from_str_radix(bits, 2)
+            .expect("Only contains binary digits")
+            .to_string(),
+        VcdRadix::Decimal | VcdRadix::Hexadecimal => {
+            let padding = (4 - bits.len() % 4) % 4;
+            let bits: Vec<char> = std::iter::repeat('0')
+                .take(padding)
+                .chain(bits.chars())
+                .collect();
+
+            bits.chunks(4)
+                .map(|digit| {
+                    if digit.iter().all(|c| *c == 'z') {
+                        'z'
+                    } else if digit.iter().any(|c| *c != '0' && *c != '1') {
+                        'x'
+                    } else {
+                        let value = digit
+                            .iter()
+                            .fold(0, |value, c| (value << 1) | u32::from(*c == '1'));
+                        char::from_digit(value, 16)
+                            .expect("Is a hexadecimal digit")
+                            .to_ascii_uppercase()
+                    }
+                })
+                .collect()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DUMP: &str = r#"
+$timescale 1ns $end
+$scope module top $end
+$var wire 1 ! clk $end
+$var wire 1 % en $end
+$scope module bus $end
+$var wire 8 " data [7:0] $end
+$upscope $end
+$upscope $end
+$enddefinitions $end
+#0
+0!
+0%
+bx "
+#5
+1!
+b1010 "
+#10
+0!
+#15
+1!
+1%
+b1z1111111 "
+#20
+0!
+#25
+1!
+bz "
+z%
+#30
+0!
+"#;
+
+    fn waves(figure: &SignalFigure) -> Vec<(String, String, Vec<String>)> {
+        fn collect(sections: &[SignalFigureSection], out: &mut Vec<(String, String, Vec<String>)>) {
+            for section in sections {
+                match section {
+                    SignalFigureSection::Signal(signal) => out.push((
+                        signal.get_name().to_plain_string(),
+                        signal.cycles().iter().map(|c| c.to_char()).collect(),
+                        signal
+                            .get_data_fields()
+                            .iter()
+                            .map(|data| data.to_plain_string())
+                            .collect(),
+                    )),
+                    SignalFigureSection::Group(group) => collect(group.items(), out),
+                }
+            }
+        }
+
+        let mut out = Vec::new();
+        collect(figure.get_sections(), &mut out);
+        out
+    }
+
+    #[test]
+    fn import_with_clock() {
+        let vcd: Vcd = DUMP.parse().unwrap();
+        let figure = vcd
+            .to_signal_figure(&VcdImportOptions::new().clock("top.clk"))
+            .unwrap();
+
+        let waves = waves(&figure);
+        assert_eq!(waves[0].1, "p..");
+        assert_eq!(waves[1].1, "01z");
+        assert_eq!(waves[2].1, "==z");
+        assert_eq!(waves[2].2.len(), 2);
+        assert_eq!(waves[2].2, ["0A", "xF"]);
+        assert_eq!(figure.get_timescale(), "10ns".parse().ok());
+
+        let SignalFigureSection::Group(top) = &figure.get_sections()[0] else {
+            panic!("Expected the top scope to become a group");
+        };
+        assert_eq!(top.label(), Some("top"));
+    }
+
+    #[test]
+    fn import_window() {
+        let vcd: Vcd = DUMP.parse().unwrap();
+        let options = VcdImportOptions::new()
+            .signals(["data", "top.clk"])
+            .start(5)
+            .end(20)
+            .radix(VcdRadix::Decimal);
+        let waves = waves(&vcd.to_signal_figure(&options).unwrap());
+
+        assert_eq!(waves.len(), 2);
+        assert_eq!(waves[0].1, "101");
+        assert_eq!(waves[1].1, "=.=");
+        assert_eq!(waves[1].2, ["10", "x"]);
+
+        let error = vcd
+            .to_signal_figure(&VcdImportOptions::new().signals(["top.rst"]))
+            .unwrap_err();
+        assert_eq!(error, VcdError::UnknownSignal("top.rst".to_string()));
+
+        let error = vcd
+            .to_signal_figure(&VcdImportOptions::new().time_step(1).max_cycles(10))
+            .unwrap_err();
+        assert_eq!(
+            error,
+            VcdError::TooManyCycles {
+                cycles: 30,
+                max_cycles: 10
+            }
+        );
+    }
+}
@@ -0,0 +1,302 @@
+//! Import and export of Value Change Dump (VCD) files
+//!
+//! A [`Vcd`] is parsed from the text of a VCD file, as produced by most HDL simulators. A window
+//! of the dump can then be turned into a [`SignalFigure`] with [`Vcd::to_signal_figure`]. The
+//! [`VcdImportOptions`] select which signals are shown, the time window and how the value changes
+//! are sampled into cycles.
+//!
+//! In the other direction, an assembled figure can be written as a VCD file with
+//! [`AssembledFigure::write_vcd`][crate::signal::AssembledFigure::write_vcd]. This allows a
+//! WaveJson stimulus to be loaded into a simulator or a waveform viewer.
+//!
+//! # Examples
+//!
+//! ```
+//! use wavedrom::vcd::{Vcd, VcdImportOptions};
+//!
+//! let vcd: Vcd = r#"
+//! $timescale 1ns $end
+//! $scope module top $end
+//! $var wire 1 ! clk $end
+//! $var wire 8 " data [7:0] $end
+//! $upscope $end
+//! $enddefinitions $end
+//! #0
+//! 0!
+//! bx "
+//! #5
+//! 1!
+//! b1010 "
+//! #10
+//! 0!
+//! #15
+//! 1!
+//! b11111111 "
+//! #20
+//! 0!
+//! "#.parse().unwrap();
+//!
+//! let figure = vcd
+//!     .to_signal_figure(&VcdImportOptions::new().clock("top.clk").signals(["data"]))
+//!     .unwrap();
+//! ```
+
+mod export;
+mod import;
+mod parse;
+
+use std::collections::HashMap;
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+use crate::signal::{SignalFigure, Timescale};
+
+pub use export::VcdExportOptions;
+pub use import::{VcdImportOptions, VcdRadix, VcdSampling};
+
+/// The largest width of a variable. Dumps with wider variables are rejected and wider data values
+/// are exported as `x`.
+const MAX_WIDTH: u32 = 1 << 16;
+
+/// A parsed Value Change Dump.
+#[derive(Debug, Clone)]
+pub struct Vcd {
+    timescale: Option<Timescale>,
+    items: Vec<VcdItem>,
+    changes: HashMap<String, Vec<(u64, VcdValue)>>,
+    end_time: u64,
+}
+
+/// A scope in the variable hierarchy of a [`Vcd`] (e.g. a `module`).
+#[derive(Debug, Clone)]
+pub struct VcdScope {
+    kind: String,
+    name: String,
+    items: Vec<VcdItem>,
+}
+
+/// An item of a [`VcdScope`].
+#[derive(Debug, Clone)]
+pub enum VcdItem {
+    /// A nested [`VcdScope`]
+    Scope(VcdScope),
+    /// A [`VcdVariable`] declared in the scope
+    Variable(VcdVariable),
+}
+
+/// A variable declared in a [`Vcd`].
+#[derive(Debug, Clone)]
+pub struct VcdVariable {
+    kind: String,
+    width: u32,
+    id: String,
+    reference: String,
+    index: Option<String>,
+}
+
+/// The value of a [`VcdVariable`] after a value change.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VcdValue {
+    /// A scalar or vector value given as the bits `0`, `1`, `x` and `z` from the most significant
+    /// to the least significant bit
+    Bits(String),
+    /// A real number as written in the dump
+    Real(String),
+    /// A string value as written in the dump
+    String(String),
+}
+
+/// An error while reading a VCD file or while importing it into a [`SignalFigure`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VcdError {
+    /// The VCD file is malformed at a line
+    Syntax {
+        /// The line number, starting at 1
+        line: usize,
+        /// What was wrong
+        message: String,
+    },
+    /// A signal that was selected for the import does not exist in the VCD file
+    UnknownSignal(String),
+    /// The sampling gives more cycles than [`VcdImportOptions::max_cycles`] allows
+    TooManyCycles {
+        /// The amount of cycles that the sampling gives
+        cycles: u64,
+        /// The largest amount of cycles that is allowed
+        max_cycles: usize,
+    },
+}
+
+impl Display for VcdError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Syntax { line, message } => write!(f, "line {line}: {message}"),
+            Self::UnknownSignal(name) => write!(f, "unknown signal '{name}'"),
+            Self::TooManyCycles { cycles, max_cycles } => write!(
+                f,
+                "the import gives {cycles} cycles, but at most {max_cycles} are allowed. Narrow \
+                 the window or sample at the rising edges of a clock"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for VcdError {}
+
+impl Vcd {
+    /// Get the unit of the timestamps, if the dump specifies one.
+    #[inline]
+    pub fn timescale(&self) -> Option<Timescale> {
+        self.timescale
+    }
+
+    /// Get the scopes and variables at the top of the hierarchy.
+    #[inline]
+    pub fn items(&self) -> &[VcdItem] {
+        &self.items
+    }
+
+    /// Get the last timestamp of the dump.
+    #[inline]
+    pub fn end_time(&self) -> u64 {
+        self.end_time
+    }
+
+    /// Get the value changes of a variable as pairs of a timestamp and a value.
+    ///
+    /// The changes are sorted by their timestamp.
+    pub fn changes(&self, variable: &VcdVariable) -> &[(u64, VcdValue)] {
+        self.changes.get(&variable.id).map_or(&[], Vec::as_slice)
+    }
+
+    /// Get the value of a variable at a timestamp.
+    ///
+    /// This is the value after all the changes at `time`. Returns `None` if the variable has no
+    /// value yet.
+    pub fn value_at(&self, variable: &VcdVariable, time: u64) -> Option<&VcdValue> {
+        let changes = self.changes(variable);
+        let idx = changes.partition_point(|(change_time, _)| *change_time <= time);
+        idx.checked_sub(1).map(|idx| &changes[idx].1)
+    }
+
+    /// Get all variables together with their hierarchical path (e.g. `top.cpu.clk`).
+    pub fn variables(&self) -> Vec<(String, &VcdVariable)> {
+        fn collect<'a>(
+            prefix: &str,
+            items: &'a [VcdItem],
+            out: &mut Vec<(String, &'a VcdVariable)>,
+        ) {
+            for item in items {
+                match item {
+                    VcdItem::Scope(scope) => {
+                        collect(&format!("{prefix}{}.", scope.name), &scope.items, out)
+                    }
+                    VcdItem::Variable(variable) => {
+                        out.push((format!("{prefix}{}", variable.reference), variable))
+                    }
+                }
+            }
+        }
+
+        let mut variables = Vec::new();
+        collect("", &self.items, &mut variables);
+        variables
+    }
+
+    /// Create a [`SignalFigure`] from a window of the dump.
+    ///
+    /// See [`VcdImportOptions`] for how the signals are selected and sampled.
+    #[inline]
+    pub fn to_signal_figure(&self, options: &VcdImportOptions) -> Result<SignalFigure, VcdError> {
+        import::to_signal_figure(self, options)
+    }
+}
+
+impl FromStr for Vcd {
+    type Err = VcdError;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse::parse(s)
+    }
+}
+
+impl VcdScope {
+    /// Get the kind of scope (e.g. `module`, `task` or `begin`).
+    #[inline]
+    pub fn kind(&self) -> &str {
+        &self.kind
+    }
+
+    /// Get the name of the scope.
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the nested scopes and variables of the scope.
+    #[inline]
+    pub fn items(&self) -> &[VcdItem] {
+        &self.items
+    }
+}
+
+impl VcdVariable {
+    /// Get the kind of variable (e.g. `wire`, `reg` or `real`).
+    #[inline]
+    pub fn kind(&self) -> &str {
+        &self.kind
+    }
+
+    /// Get the number of bits of the variable.
+    #[inline]
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Get the identifier code that is used for the value changes of the variable.
+    #[inline]
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Get the name of the variable without the bit index.
+    #[inline]
+    pub fn reference(&self) -> &str {
+        &self.reference
+    }
+
+    /// Get the bit index or range of the variable (e.g. `[7:0]`), if it has one.
+    #[inline]
+    pub fn index(&self) -> Option<&str> {
+        self.index.as_deref()
+    }
+
+    /// Get the name of the variable including the bit index (e.g. `data[7:0]`).
+    pub fn name(&self) -> String {
+        match &self.index {
+            Some(index) => format!("{}{index}", self.reference),
+            None => self.reference.clone(),
+        }
+    }
+}
+
+/// Extend or truncate `bits` to `width` with the extension rules of the VCD standard.
+fn resize_bits(bits: &str, width: usize) -> String {
+    let width = width.max(1);
+
+    if bits.len() >= width {
+        return bits[bits.len() - width..].to_string();
+    }
+
+    let fill = match bits.as_bytes()[0] {
+        b'x' => 'x',
+        b'z' => 'z',
+        _ => '0',
+    };
+
+    std::iter::repeat(fill)
+        .take(width - bits.len())
+        .chain(bits.chars())
+        .collect()
+}
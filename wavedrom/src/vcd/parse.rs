@@ -0,0 +1,290 @@
+use std::collections::HashMap;
+
+use super::{Vcd, VcdError, VcdItem, VcdScope, VcdValue, VcdVariable, MAX_WIDTH};
+
+struct Tokens<'a> {
+    inner: Box<dyn Iterator<Item = (usize, &'a str)> + 'a>,
+    line: usize,
+}
+
+impl<'a> Tokens<'a> {
+    fn new(s: &'a str) -> Self {
+        let inner = s
+            .lines()
+            .enumerate()
+            .flat_map(|(idx, line)| line.split_whitespace().map(move |token| (idx + 1, token)));
+
+        Self {
+            inner: Box::new(inner),
+            line: 1,
+        }
+    }
+
+    fn next(&mut self) -> Option<&'a str> {
+        let (line, token) = self.inner.next()?;
+        self.line = line;
+        Some(token)
+    }
+
+    fn error(&self, message: impl Into<String>) -> VcdError {
+        VcdError::Syntax {
+            line: self.line,
+            message: message.into(),
+        }
+    }
+
+    fn expect(&mut self, what: &str) -> Result<&'a str, VcdError> {
+        match self.next() {
+            Some("$end") | None => Err(self.error(format!("expected {what}"))),
+            Some(token) => Ok(token),
+        }
+    }
+
+    /// Take all tokens until the next `$end`.
+    fn until_end(&mut self, keyword: &str) -> Result<Vec<&'a str>, VcdError> {
+        let mut tokens = Vec::new();
+        loop {
+            match self.next() {
+                Some("$end") => return Ok(tokens),
+                Some(token) => tokens.push(token),
+                None => return Err(self.error(format!("'{keyword}' is not closed with '$end'"))),
+            }
+        }
+    }
+}
+
+pub(super) fn parse(s: &str) -> Result<Vcd, VcdError> {
+    let mut tokens = Tokens::new(s);
+
+    let mut timescale = None;
+    let mut items = Vec::new();
+    let mut scopes: Vec<VcdScope> = Vec::new();
+    let mut changes = HashMap::new();
+
+    // Declarations
+    loop {
+        let Some(keyword) = tokens.next() else {
+            return Err(tokens.error("missing '$enddefinitions'"));
+        };
+
+        match keyword {
+            "$timescale" => {
+                let text = tokens.until_end(keyword)?.concat();
+                timescale = Some(
+                    text.parse()
+                        .map_err(|_| tokens.error(format!("invalid timescale '{text}'")))?,
+                );
+            }
+            "$scope" => {
+                let kind = tokens.expect("a scope type")?;
+                let name = tokens.expect("a scope name")?;
+                tokens.until_end(keyword)?;
+
+                scopes.push(VcdScope {
+                    kind: kind.to_string(),
+                    name: name.to_string(),
+                    items: Vec::new(),
+                });
+            }
+            "$upscope" => {
+                tokens.until_end(keyword)?;
+
+                let scope = scopes
+                    .pop()
+                    .ok_or_else(|| tokens.error("'$upscope' without an open scope"))?;
+                scopes
+                    .last_mut()
+                    .map_or(&mut items, |parent| &mut parent.items)
+                    .push(VcdItem::Scope(scope));
+            }
+            "$var" => {
+                let kind = tokens.expect("a variable type")?;
+                let width = tokens.expect("a variable width")?;
+                let width = width
+                    .parse()
+                    .ok()
+                    .filter(|width| *width <= MAX_WIDTH)
+                    .ok_or_else(|| tokens.error(format!("invalid variable width '{width}'")))?;
+                let id = tokens.expect("an identifier code")?;
+                let reference = tokens.expect("a variable name")?;
+                let index = tokens.until_end(keyword)?.concat();
+
+                changes.entry(id.to_string()).or_insert_with(Vec::new);
+
+                let variable = VcdVariable {
+                    kind: kind.to_string(),
+                    width,
+                    id: id.to_string(),
+                    reference: reference.to_string(),
+                    index: (!index.is_empty()).then_some(index),
+                };
+                scopes
+                    .last_mut()
+                    .map_or(&mut items, |parent| &mut parent.items)
+                    .push(VcdItem::Variable(variable));
+            }
+            "$enddefinitions" => {
+                tokens.until_end(keyword)?;
+                break;
+            }
+            // $comment, $date, $version and any other declaration
+            keyword if keyword.starts_with('$') => {
+                tokens.until_end(keyword)?;
+            }
+            token => return Err(tokens.error(format!("unexpected '{token}' in the declarations"))),
+        }
+    }
+
+    // Close the scopes that were never closed with `$upscope`.
+    while let Some(scope) = scopes.pop() {
+        scopes
+            .last_mut()
+            .map_or(&mut items, |parent| &mut parent.items)
+            .push(VcdItem::Scope(scope));
+    }
+
+    // Value changes
+    let mut time = 0;
+    let mut end_time = 0;
+    while let Some(token) = tokens.next() {
+        let (value, id) = match token.as_bytes()[0] {
+            b'#' => {
+                let new_time: u64 = token[1..]
+                    .parse()
+                    .map_err(|_| tokens.error(format!("invalid timestamp '{token}'")))?;
+                if new_time < time {
+                    return Err(tokens.error(format!("timestamp '{token}' goes back in time")));
+                }
+                time = new_time;
+                end_time = time;
+                continue;
+            }
+            b'$' if token == "$comment" => {
+                tokens.until_end(token)?;
+                continue;
+            }
+            // $dumpvars, $dumpall, $dumpon, $dumpoff and their $end only group value changes.
+            b'$' => continue,
+            b'0' | b'1' | b'x' | b'X' | b'z' | b'Z' => {
+                (VcdValue::Bits(token[..1].to_ascii_lowercase()), &token[1..])
+            }
+            b'b' | b'B' => (
+                VcdValue::Bits(token[1..].to_ascii_lowercase()),
+                tokens.expect("an identifier code")?,
+            ),
+            b'r' | b'R' => (
+                VcdValue::Real(token[1..].to_string()),
+                tokens.expect("an identifier code")?,
+            ),
+            b's' | b'S' => (
+                VcdValue::String(token[1..].to_string()),
+                tokens.expect("an identifier code")?,
+            ),
+            _ => return Err(tokens.error(format!("unexpected '{token}' in the value changes"))),
+        };
+
+        if let VcdValue::Bits(bits) = &value {
+            if bits.is_empty() || bits.contains(|c| !matches!(c, '0' | '1' | 'x' | 'z')) {
+                return Err(tokens.error(format!("invalid value '{token}'")));
+            }
+        }
+
+        let Some(variable_changes) = changes.get_mut(id) else {
+            return Err(tokens.error(format!("unknown identifier code '{id}'")));
+        };
+
+        match variable_changes.last_mut() {
+            // Only the last change at a timestamp is kept.
+            Some((last_time, last_value)) if *last_time == time => *last_value = value,
+            _ => variable_changes.push((time, value)),
+        }
+    }
+
+    Ok(Vcd {
+        timescale,
+        items,
+        changes,
+        end_time,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signal::{TimeUnit, Timescale};
+
+    #[test]
+    fn parse_dump() {
+        let vcd = parse(
+            r#"
+$date today $end
+$timescale
+    10 ps
+$end
+$scope module top $end
+$var wire 1 ! clk $end
+$scope module cpu $end
+$var reg 4 # state [3:0] $end
+$var real 64 $ voltage $end
+$upscope $end
+$upscope $end
+$enddefinitions $end
+$dumpvars
+0!
+bx #
+r0 $
+$end
+#10
+1! b10 #
+#10
+0!
+#20
+r1.5 $
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(vcd.timescale(), Timescale::new(10, TimeUnit::Picoseconds));
+        assert_eq!(vcd.end_time(), 20);
+
+        let variables = vcd.variables();
+        let names: Vec<_> = variables.iter().map(|(path, _)| path.as_str()).collect();
+        assert_eq!(names, ["top.clk", "top.cpu.state", "top.cpu.voltage"]);
+
+        let (_, clk) = variables[0];
+        assert_eq!(
+            vcd.changes(clk),
+            [
+                (0, VcdValue::Bits("0".into())),
+                (10, VcdValue::Bits("0".into()))
+            ]
+        );
+
+        let (_, state) = variables[1];
+        assert_eq!(state.name(), "state[3:0]");
+        assert_eq!(vcd.value_at(state, 5), Some(&VcdValue::Bits("x".into())));
+        assert_eq!(vcd.value_at(state, 15), Some(&VcdValue::Bits("10".into())));
+
+        let (_, voltage) = variables[2];
+        assert_eq!(
+            vcd.value_at(voltage, 20),
+            Some(&VcdValue::Real("1.5".into()))
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        let error = parse("$var wire 1 ! a $end\n$enddefinitions $end\n#0\n1?\n").unwrap_err();
+        assert_eq!(
+            error,
+            VcdError::Syntax {
+                line: 4,
+                message: "unknown identifier code '?'".into()
+            }
+        );
+
+        assert!(parse("$scope module top $end").is_err());
+        assert!(parse("$enddefinitions $end\n#10\n#5").is_err());
+        assert!(parse("$var wire 4294967295 ! a $end").is_err());
+    }
+}
@@ -0,0 +1,61 @@
+use serde::{Deserialize, Serialize};
+
+use crate::assign::{AssignFigure, AssignGate, AssignNode};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct AssignJson {
+    pub assign: Vec<AssignItem>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct AssignItem(pub String, pub AssignExpression);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[serde(untagged)]
+pub enum AssignExpression {
+    Input(String),
+    Gate(Vec<AssignExpression>),
+}
+
+impl AssignJson {
+    pub(crate) const KEYS: &'static [&'static str] = &["assign"];
+}
+
+impl From<AssignExpression> for AssignNode {
+    fn from(value: AssignExpression) -> Self {
+        match value {
+            AssignExpression::Input(name) => AssignNode::Input(name),
+            AssignExpression::Gate(expressions) => {
+                let mut expressions = expressions.into_iter();
+
+                let gate = match expressions.next() {
+                    Some(AssignExpression::Input(operator)) => AssignGate::from_operator(&operator),
+                    Some(expression) => {
+                        // There is no operator, so the first expression is also an input.
+                        return AssignNode::gate(
+                            AssignGate::Box(String::new()),
+                            std::iter::once(expression)
+                                .chain(expressions)
+                                .map(Into::into),
+                        );
+                    }
+                    None => AssignGate::Box(String::new()),
+                };
+
+                AssignNode::gate(gate, expressions.map(Into::into))
+            }
+        }
+    }
+}
+
+impl From<AssignJson> for AssignFigure {
+    fn from(value: AssignJson) -> Self {
+        value.assign.into_iter().fold(
+            AssignFigure::new(),
+            |figure, AssignItem(name, expression)| figure.add_output(name, expression.into()),
+        )
+    }
+}
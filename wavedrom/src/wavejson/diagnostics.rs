@@ -0,0 +1,935 @@
+//! Diagnostics for WaveJson sources.
+//!
+//! WaveJson is quite forgiving. Unknown wave characters are shown as undefined, malformed edges
+//! are ignored and superfluous data fields are never shown. The diagnostics in this module point
+//! out these problems without refusing to render the figure.
+
+use std::fmt;
+
+use crate::register::RegisterFigure;
+use crate::signal::edges::{parse_nodes, EdgeArrowHead, EdgeDefinition, EdgeNode};
+use crate::signal::markers::{LineDash, TimeRulerPosition};
+use crate::signal::{CycleState, SignalFigure, Timescale};
+#[cfg(feature = "skins")]
+use crate::skin::Skin;
+use crate::Color;
+
+use super::assign::{AssignExpression, AssignJson};
+use super::register::{RegisterConfig, RegisterFieldJson, RegisterJson};
+use super::signal::{
+    Config, EdgeItem, EdgeObject, Foot, Head, Highlight, Marker, SignalData, SignalGroupItem,
+    SignalItem, SignalJson, SignalObject,
+};
+use super::source::{SourceMap, SourceNode};
+use super::WaveJson;
+
+/// A problem found in a WaveJson source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// How severe the problem is
+    pub severity: Severity,
+    /// The part of the source that the problem refers to
+    pub span: Span,
+    /// A human readable description of the problem
+    pub message: String,
+}
+
+/// The severity of a [`Diagnostic`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// The figure can be rendered, but probably does not look as intended.
+    Warning,
+    /// The figure cannot be rendered.
+    Error,
+}
+
+/// A range in a source given by its start and end [`Location`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Span {
+    /// The location of the first character
+    pub start: Location,
+    /// The location just after the last character
+    pub end: Location,
+}
+
+/// A line and column in a source. Both start counting at 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Location {
+    /// The line number
+    pub line: usize,
+    /// The column in characters
+    pub column: usize,
+}
+
+/// An error loading a figure in strict mode.
+#[derive(Debug)]
+pub enum StrictError<E> {
+    /// The source could not be deserialized.
+    Parse(E),
+    /// The source contains at least one [`Severity::Error`]. All diagnostics, including the
+    /// warnings, are given.
+    Invalid(Vec<Diagnostic>),
+}
+
+impl<E> StrictError<E> {
+    /// Get the diagnostics of the error. This is empty for [`StrictError::Parse`].
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        match self {
+            Self::Parse(_) => &[],
+            Self::Invalid(diagnostics) => diagnostics,
+        }
+    }
+}
+
+impl<E> From<E> for StrictError<E> {
+    #[inline]
+    fn from(error: E) -> Self {
+        Self::Parse(error)
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for StrictError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Parse(err) => err.fmt(f),
+            Self::Invalid(diagnostics) => {
+                let mut errors = diagnostics
+                    .iter()
+                    .filter(|diagnostic| diagnostic.severity == Severity::Error);
+
+                if let Some(first) = errors.next() {
+                    first.fmt(f)?;
+                }
+
+                for diagnostic in errors {
+                    writeln!(f)?;
+                    diagnostic.fmt(f)?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+impl<E: std::error::Error> std::error::Error for StrictError<E> {}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {}: {}",
+            self.span.start, self.severity, self.message
+        )
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Warning => "warning",
+            Self::Error => "error",
+        })
+    }
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+impl WaveJson {
+    /// Find the problems in a [`WaveJson`] that was deserialized from `source`.
+    ///
+    /// The `source` is used to find the locations of the problems. It may either be JSON or JSON5.
+    /// All problems are reported as [`Severity::Warning`].
+    pub fn diagnostics(&self, source: &str) -> Vec<Diagnostic> {
+        match self {
+            WaveJson::Signal(signal) => signal.check(source, false),
+            WaveJson::Register(register) => register.check(source, false),
+            WaveJson::Assign(assign) => assign.check(source, false),
+        }
+    }
+
+    /// Find the problems in a [`WaveJson`] that was deserialized from `source` in strict mode.
+    ///
+    /// In addition to [`WaveJson::diagnostics`], unknown object keys are reported. Unknown keys,
+    /// unknown wave characters, invalid `period` and `phase` values, malformed edges, invalid
+    /// register configurations and gates without inputs are reported as [`Severity::Error`].
+    /// Other problems remain a [`Severity::Warning`].
+    pub fn strict_diagnostics(&self, source: &str) -> Vec<Diagnostic> {
+        match self {
+            WaveJson::Signal(signal) => signal.check(source, true),
+            WaveJson::Register(register) => register.check(source, true),
+            WaveJson::Assign(assign) => assign.check(source, true),
+        }
+    }
+}
+
+impl SignalJson {
+    fn check(&self, source: &str, strict: bool) -> Vec<Diagnostic> {
+        let map = SourceMap::new(source);
+        let mut checker = Checker {
+            map: &map,
+            strict,
+            nodes: Vec::new(),
+            diagnostics: Vec::new(),
+        };
+
+        let root = map.root();
+
+        checker.check_keys(root, SignalJson::KEYS);
+        checker.check_keys(root.and_then(|root| root.get("head")), Head::KEYS);
+        checker.check_keys(root.and_then(|root| root.get("foot")), Foot::KEYS);
+        let config_node = root.and_then(|root| root.get("config"));
+        checker.check_keys(config_node, Config::KEYS);
+        if let Some(config) = &self.config {
+            checker.check_config(config, config_node);
+        }
+
+        let signal_node = root.and_then(|root| root.get("signal"));
+        for (i, item) in self.signal.iter().enumerate() {
+            checker.check_item(item, signal_node.and_then(|node| node.index(i)));
+        }
+
+        let edge_node = root.and_then(|root| root.get("edge"));
+        for (i, edge) in self.edge.iter().flatten().enumerate() {
+            checker.check_edge_item(edge, edge_node.and_then(|node| node.index(i)));
+        }
+
+        // Markers and highlights can only be checked against the length of the assembled figure.
+        let num_cycles = if self.marker.is_some() || self.highlight.is_some() {
+            SignalFigure::from(self.clone()).assemble().num_cycles()
+        } else {
+            0
+        };
+
+        let marker_node = root.and_then(|root| root.get("marker"));
+        for (i, marker) in self.marker.iter().flatten().enumerate() {
+            checker.check_marker(
+                marker,
+                marker_node.and_then(|node| node.index(i)),
+                num_cycles,
+            );
+        }
+
+        let highlight_node = root.and_then(|root| root.get("highlight"));
+        for (i, highlight) in self.highlight.iter().flatten().enumerate() {
+            checker.check_highlight(
+                highlight,
+                highlight_node.and_then(|node| node.index(i)),
+                num_cycles,
+            );
+        }
+
+        checker.diagnostics
+    }
+}
+
+impl RegisterJson {
+    fn check(&self, source: &str, strict: bool) -> Vec<Diagnostic> {
+        let map = SourceMap::new(source);
+        let mut checker = Checker {
+            map: &map,
+            strict,
+            nodes: Vec::new(),
+            diagnostics: Vec::new(),
+        };
+
+        let root = map.root();
+
+        checker.check_keys(root, RegisterJson::KEYS);
+
+        let reg_node = root.and_then(|root| root.get("reg"));
+        for (i, field) in self.reg.iter().enumerate() {
+            checker.check_register_field(field, reg_node.and_then(|node| node.index(i)));
+        }
+
+        let config_node = root.and_then(|root| root.get("config"));
+        checker.check_keys(config_node, RegisterConfig::KEYS);
+
+        let bits = self
+            .config
+            .as_ref()
+            .and_then(|config| config.bits)
+            .unwrap_or_else(|| {
+                self.reg
+                    .iter()
+                    .fold(0, |sum: u32, field| sum.saturating_add(field.bits))
+            });
+        if bits > RegisterFigure::MAX_BITS {
+            let bits_node = config_node.and_then(|node| node.get("bits"));
+            checker.report(
+                checker.span(bits_node.or(reg_node)),
+                format!(
+                    "register has {bits} bits, but at most {} bits are drawn",
+                    RegisterFigure::MAX_BITS
+                ),
+                "and is cut off",
+            );
+        }
+
+        if let Some(config) = &self.config {
+            checker.check_register_config(config, config_node);
+        }
+
+        checker.diagnostics
+    }
+}
+
+impl AssignJson {
+    fn check(&self, source: &str, strict: bool) -> Vec<Diagnostic> {
+        let map = SourceMap::new(source);
+        let mut checker = Checker {
+            map: &map,
+            strict,
+            nodes: Vec::new(),
+            diagnostics: Vec::new(),
+        };
+
+        let root = map.root();
+
+        checker.check_keys(root, AssignJson::KEYS);
+
+        let assign_node = root.and_then(|root| root.get("assign"));
+        for (i, item) in self.assign.iter().enumerate() {
+            let item_node = assign_node.and_then(|node| node.index(i));
+            checker.check_assign_expression(&item.1, item_node.and_then(|node| node.index(1)));
+        }
+
+        checker.diagnostics
+    }
+}
+
+struct Checker<'a, 'b> {
+    map: &'a SourceMap<'b>,
+    strict: bool,
+    nodes: Vec<EdgeNode>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Checker<'_, '_> {
+    fn span(&self, node: Option<&SourceNode>) -> Span {
+        node.map_or_else(|| self.map.span(0, 0), |node| self.map.node_span(node))
+    }
+
+    fn char_span(&self, node: Option<&SourceNode>, index: usize) -> Span {
+        node.map_or_else(
+            || self.map.span(0, 0),
+            |node| self.map.string_char_span(node, index),
+        )
+    }
+
+    /// Report a problem that is always a warning.
+    fn warn(&mut self, span: Span, message: String) {
+        self.diagnostics.push(Diagnostic {
+            severity: Severity::Warning,
+            span,
+            message,
+        });
+    }
+
+    /// Report a problem that is an error in strict mode. Otherwise, it is a warning that mentions
+    /// how the problem is handled.
+    fn report(&mut self, span: Span, message: String, handling: &str) {
+        let (severity, message) = if self.strict {
+            (Severity::Error, message)
+        } else {
+            (Severity::Warning, format!("{message} {handling}"))
+        };
+
+        self.diagnostics.push(Diagnostic {
+            severity,
+            span,
+            message,
+        });
+    }
+
+    fn check_keys(&mut self, node: Option<&SourceNode>, keys: &[&str]) {
+        if !self.strict {
+            return;
+        }
+
+        let Some(node) = node else {
+            return;
+        };
+
+        for member in node.members() {
+            if !keys.contains(&member.key.as_str()) {
+                self.diagnostics.push(Diagnostic {
+                    severity: Severity::Error,
+                    span: self.map.span(member.key_start, member.key_end),
+                    message: format!(
+                        "unknown key '{}', expected one of: {}",
+                        member.key,
+                        keys.join(", ")
+                    ),
+                });
+            }
+        }
+    }
+
+    fn check_config(&mut self, config: &Config, node: Option<&SourceNode>) {
+        #[cfg(feature = "skins")]
+        if let Some(skin) = &config.skin {
+            if !Skin::NAMES.contains(&skin.as_str()) {
+                self.report(
+                    self.span(node.and_then(|node| node.get("skin"))),
+                    format!(
+                        "unknown skin '{skin}', expected one of: {}",
+                        Skin::NAMES.join(", ")
+                    ),
+                    "and is ignored",
+                );
+            }
+        }
+
+        if let Some(timescale) = &config.timescale {
+            if let Err(idx) = timescale.parse::<Timescale>() {
+                self.report(
+                    self.char_span(node.and_then(|node| node.get("timescale")), idx),
+                    format!("invalid timescale '{timescale}'"),
+                    "and is ignored",
+                );
+            }
+        }
+
+        if let Some(ruler) = &config.ruler {
+            if TimeRulerPosition::from_name(ruler).is_none() {
+                self.report(
+                    self.span(node.and_then(|node| node.get("ruler"))),
+                    format!(
+                        "unknown ruler position '{ruler}', expected one of: {}",
+                        TimeRulerPosition::NAMES.join(", ")
+                    ),
+                    "and is replaced by 'bottom'",
+                );
+            }
+        }
+    }
+
+    fn check_marker(&mut self, marker: &Marker, node: Option<&SourceNode>, num_cycles: u32) {
+        self.check_keys(node, Marker::KEYS);
+
+        if !marker.at.is_finite() || marker.at < 0.0 {
+            self.report(
+                self.span(node.and_then(|node| node.get("at"))),
+                format!("marker position {} is not a non-negative number", marker.at),
+                "and is replaced by 0",
+            );
+        } else if marker.at > num_cycles as f32 {
+            self.report(
+                self.span(node.and_then(|node| node.get("at"))),
+                format!(
+                    "marker position {} is past the end of the figure at cycle {num_cycles}",
+                    marker.at
+                ),
+                "and is not drawn",
+            );
+        }
+
+        self.check_color(marker.color.as_deref(), node);
+
+        if let Some(dash) = &marker.dash {
+            if LineDash::from_name(dash).is_none() {
+                self.report(
+                    self.span(node.and_then(|node| node.get("dash"))),
+                    format!(
+                        "unknown dash style '{dash}', expected one of: {}",
+                        LineDash::NAMES.join(", ")
+                    ),
+                    "and is replaced by 'dashed'",
+                );
+            }
+        }
+    }
+
+    fn check_highlight(
+        &mut self,
+        highlight: &Highlight,
+        node: Option<&SourceNode>,
+        num_cycles: u32,
+    ) {
+        self.check_keys(node, Highlight::KEYS);
+
+        for (key, value) in [("from", highlight.from), ("to", highlight.to)] {
+            if !value.is_finite() || value < 0.0 {
+                self.report(
+                    self.span(node.and_then(|node| node.get(key))),
+                    format!("highlight '{key}' {value} is not a non-negative number"),
+                    "and is replaced by 0",
+                );
+            } else if value > num_cycles as f32 {
+                self.report(
+                    self.span(node.and_then(|node| node.get(key))),
+                    format!(
+                        "highlight '{key}' {value} is past the end of the figure at cycle \
+                         {num_cycles}"
+                    ),
+                    "and is cut off",
+                );
+            }
+        }
+
+        if highlight.to <= highlight.from {
+            self.warn(
+                self.span(node),
+                format!(
+                    "highlight ends at {} before it starts at {}",
+                    highlight.to, highlight.from
+                ),
+            );
+        }
+
+        if let Some(opacity) = highlight.opacity {
+            if !(0.0..=1.0).contains(&opacity) {
+                self.report(
+                    self.span(node.and_then(|node| node.get("opacity"))),
+                    format!("opacity {opacity} is not between 0 and 1"),
+                    "and the default opacity is used",
+                );
+            }
+        }
+
+        self.check_color(highlight.color.as_deref(), node);
+    }
+
+    fn check_color(&mut self, color: Option<&str>, node: Option<&SourceNode>) {
+        if let Some(color) = color {
+            if let Err(idx) = color.parse::<Color>() {
+                self.report(
+                    self.char_span(node.and_then(|node| node.get("color")), idx),
+                    format!("invalid color '{color}'"),
+                    "and the default color is used",
+                );
+            }
+        }
+    }
+
+    fn check_item(&mut self, item: &SignalItem, node: Option<&SourceNode>) {
+        match item {
+            SignalItem::Group(items) => {
+                for (i, item) in items.iter().enumerate() {
+                    if let SignalGroupItem::Item(item) = item {
+                        self.check_item(item, node.and_then(|node| node.index(i)));
+                    }
+                }
+            }
+            SignalItem::Item(object) => self.check_object(object, node),
+        }
+    }
+
+    fn check_object(&mut self, object: &SignalObject, node: Option<&SourceNode>) {
+        self.check_keys(node, SignalObject::KEYS);
+
+        let wave_node = node.and_then(|node| node.get("wave"));
+        let wave = object.wave.as_deref().unwrap_or_default();
+
+        for (i, c) in wave.chars().enumerate() {
+            if CycleState::from_char(c).is_none() {
+                self.report(
+                    self.char_span(wave_node, i),
+                    format!("unknown wave character '{c}'"),
+                    "is shown as undefined",
+                );
+            }
+        }
+
+        if let Some(data) = &object.data {
+            let num_data = match data {
+                SignalData::One(data) => data.split_whitespace().count(),
+                SignalData::Multiple(data) => data.len(),
+            };
+            let num_boxes = wave
+                .chars()
+                .filter(|c| matches!(c, '2'..='9' | '='))
+                .count();
+
+            if num_data != num_boxes {
+                self.warn(
+                    self.span(node.and_then(|node| node.get("data"))),
+                    format!(
+                        "wave has {num_boxes} data boxes, but {num_data} data fields are given"
+                    ),
+                );
+            }
+        }
+
+        if let Some(period) = object.period {
+            if !period.is_finite() || period <= 0.0 {
+                self.report(
+                    self.span(node.and_then(|node| node.get("period"))),
+                    format!("period {period} is not a positive number"),
+                    "and is replaced by 1",
+                );
+            }
+        }
+
+        if let Some(phase) = object.phase {
+            if !phase.is_finite() || phase < 0.0 {
+                self.report(
+                    self.span(node.and_then(|node| node.get("phase"))),
+                    format!("phase {phase} is not a non-negative number"),
+                    "and is replaced by 0",
+                );
+            }
+        }
+
+        if let Some(nodes) = &object.node {
+            self.nodes.extend(parse_nodes(nodes).flatten());
+        }
+    }
+
+    fn check_register_field(&mut self, field: &RegisterFieldJson, node: Option<&SourceNode>) {
+        self.check_keys(node, RegisterFieldJson::KEYS);
+
+        if let Some(field_type @ 10..) = field.field_type {
+            self.report(
+                self.span(node.and_then(|node| node.get("type"))),
+                format!("unknown field type {field_type}, expected a number from 0 to 9"),
+                "and is drawn without a background",
+            );
+        }
+    }
+
+    fn check_register_config(&mut self, config: &RegisterConfig, node: Option<&SourceNode>) {
+        if config.lanes == Some(0) {
+            self.report(
+                self.span(node.and_then(|node| node.get("lanes"))),
+                "lanes 0 is not a positive number".to_string(),
+                "and is replaced by 1",
+            );
+        }
+
+        for (key, value) in [("hspace", config.hspace), ("vspace", config.vspace)] {
+            if let Some(value) = value.filter(|value| *value > RegisterFigure::MAX_SPACE) {
+                self.report(
+                    self.span(node.and_then(|node| node.get(key))),
+                    format!(
+                        "'{key}' {value} is larger than {}",
+                        RegisterFigure::MAX_SPACE
+                    ),
+                    "and is limited to it",
+                );
+            }
+        }
+    }
+
+    fn check_assign_expression(
+        &mut self,
+        expression: &AssignExpression,
+        node: Option<&SourceNode>,
+    ) {
+        let AssignExpression::Gate(expressions) = expression else {
+            return;
+        };
+
+        match expressions.as_slice() {
+            [] => self.report(
+                self.span(node),
+                "gate has no operator and no inputs".to_string(),
+                "and is drawn as an empty box",
+            ),
+            [AssignExpression::Input(operator)] => self.report(
+                self.span(node),
+                format!("gate '{operator}' has no inputs"),
+                "and is drawn without inputs",
+            ),
+            _ => {}
+        }
+
+        for (i, expression) in expressions.iter().enumerate() {
+            self.check_assign_expression(expression, node.and_then(|node| node.index(i)));
+        }
+    }
+
+    fn check_edge_item(&mut self, edge: &EdgeItem, node: Option<&SourceNode>) {
+        let object = match edge {
+            EdgeItem::String(edge) => return self.check_edge(edge, node),
+            EdgeItem::Object(object) => object,
+        };
+
+        self.check_keys(node, EdgeObject::KEYS);
+        self.check_edge(&object.def, node.and_then(|node| node.get("def")));
+        self.check_color(object.color.as_deref(), node);
+
+        if object.width == Some(0) {
+            self.report(
+                self.span(node.and_then(|node| node.get("width"))),
+                "edge width 0 is not a positive number".to_string(),
+                "and is replaced by 1",
+            );
+        }
+
+        if let Some(dash) = &object.dash {
+            if LineDash::from_name(dash).is_none() {
+                self.report(
+                    self.span(node.and_then(|node| node.get("dash"))),
+                    format!(
+                        "unknown dash style '{dash}', expected one of: {}",
+                        LineDash::NAMES.join(", ")
+                    ),
+                    "and is replaced by 'solid'",
+                );
+            }
+        }
+
+        if let Some(arrow) = &object.arrow {
+            if EdgeArrowHead::from_name(arrow).is_none() {
+                self.report(
+                    self.span(node.and_then(|node| node.get("arrow"))),
+                    format!(
+                        "unknown arrowhead '{arrow}', expected one of: {}",
+                        EdgeArrowHead::NAMES.join(", ")
+                    ),
+                    "and is replaced by 'filled'",
+                );
+            }
+        }
+    }
+
+    fn check_edge(&mut self, edge: &str, node: Option<&SourceNode>) {
+        let (definition, from_idx, to_idx) = match EdgeDefinition::parse_with_positions(edge) {
+            Ok(result) => result,
+            Err(error) => {
+                let index = edge[..error.position()].chars().count();
+                self.report(
+                    self.char_span(node, index),
+                    format!("edge '{edge}' cannot be parsed ({})", error.kind()),
+                    "and is ignored",
+                );
+                return;
+            }
+        };
+
+        for (edge_node, idx) in [
+            (definition.from_node(), from_idx),
+            (definition.to_node(), to_idx),
+        ] {
+            if !self.nodes.contains(edge_node) {
+                let index = edge[..idx].chars().count();
+                self.warn(
+                    self.char_span(node, index),
+                    format!("edge '{edge}' refers to undefined node '{edge_node}'"),
+                );
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "json5"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signal_diagnostics() {
+        let source = r#"{
+    signal: [
+        { name: "a", wave: "01?.", node: ".a.." },
+        [ "group", { name: "b", wave: "=.=", data: "x y z", node: "..b" } ],
+    ],
+    edge: ["a~>b", "a~>c", "a?"],
+}"#;
+
+        let wavejson = WaveJson::from_json5(source).unwrap();
+        let diagnostics = wavejson.diagnostics(source);
+
+        let locations = diagnostics
+            .iter()
+            .map(|diagnostic| diagnostic.span.start)
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            locations,
+            [
+                // Unknown wave character
+                Location {
+                    line: 3,
+                    column: 31
+                },
+                // Data count mismatch
+                Location {
+                    line: 4,
+                    column: 52
+                },
+                // Undefined node
+                Location {
+                    line: 6,
+                    column: 24
+                },
+                // Invalid edge
+                Location {
+                    line: 6,
+                    column: 30
+                },
+            ]
+        );
+        assert!(diagnostics
+            .iter()
+            .all(|diagnostic| diagnostic.severity == Severity::Warning));
+    }
+
+    #[test]
+    fn strict_diagnostics() {
+        let source = r#"{
+    signal: [{ nmae: "clk", wave: "p.O", period: 0, node: "a.b" }],
+    edge: ["a~>b", "a", { def: "a-b", arrow: "round" }],
+    config: { hscale: 2, hbounds: [0, 1] },
+}"#;
+
+        let wavejson = WaveJson::from_json5(source).unwrap();
+
+        let errors = wavejson
+            .strict_diagnostics(source)
+            .into_iter()
+            .filter(|diagnostic| diagnostic.severity == Severity::Error)
+            .map(|diagnostic| diagnostic.span.start)
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            errors,
+            [
+                // Unknown key in config
+                Location {
+                    line: 4,
+                    column: 26
+                },
+                // Unknown key in signal
+                Location {
+                    line: 2,
+                    column: 16
+                },
+                // Unknown wave character
+                Location {
+                    line: 2,
+                    column: 38
+                },
+                // Invalid period
+                Location {
+                    line: 2,
+                    column: 50
+                },
+                // Malformed edge
+                Location {
+                    line: 3,
+                    column: 22
+                },
+                // Unknown arrowhead
+                Location {
+                    line: 3,
+                    column: 46
+                },
+            ]
+        );
+        assert!(wavejson
+            .diagnostics(source)
+            .iter()
+            .all(|diagnostic| diagnostic.severity == Severity::Warning));
+    }
+
+    #[test]
+    fn register_and_assign_diagnostics() {
+        let source = r#"{
+    reg: [{ bits: 8, name: "a", tpye: 2 }, { bits: 8, type: 12 }],
+    config: { lanes: 0, hspace: 4294967295, vpsace: 10 },
+}"#;
+
+        let errors = WaveJson::from_json5(source)
+            .unwrap()
+            .strict_diagnostics(source)
+            .into_iter()
+            .filter(|diagnostic| diagnostic.severity == Severity::Error)
+            .map(|diagnostic| diagnostic.span.start)
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            errors,
+            [
+                // Unknown key in field
+                Location {
+                    line: 2,
+                    column: 33
+                },
+                // Unknown field type
+                Location {
+                    line: 2,
+                    column: 61
+                },
+                // Unknown key in config
+                Location {
+                    line: 3,
+                    column: 45
+                },
+                // Invalid lanes
+                Location {
+                    line: 3,
+                    column: 22
+                },
+                // Too large hspace
+                Location {
+                    line: 3,
+                    column: 33
+                },
+            ]
+        );
+
+        let source = r#"{ assign: [["out", ["&", "a", []]], ["x", ["|"]]], asign: [] }"#;
+
+        let wavejson = WaveJson::from_json5(source).unwrap();
+        let diagnostics = wavejson.strict_diagnostics(source);
+
+        assert_eq!(
+            diagnostics
+                .iter()
+                .map(|diagnostic| diagnostic.span.start.column)
+                .collect::<Vec<_>>(),
+            [52, 31, 43]
+        );
+        assert!(diagnostics
+            .iter()
+            .all(|diagnostic| diagnostic.severity == Severity::Error));
+        assert_eq!(wavejson.diagnostics(source).len(), 2);
+    }
+
+    #[test]
+    fn markers_and_highlights_past_the_end() {
+        let source = r#"{
+    signal: [{ name: "a", wave: "0101" }],
+    marker: [{ at: 2 }, { at: 100000000 }, { at: 1e12 }],
+    highlight: [{ from: 0, to: 100000000 }],
+}"#;
+
+        let wavejson = WaveJson::from_json5(source).unwrap();
+        let errors = wavejson
+            .strict_diagnostics(source)
+            .into_iter()
+            .map(|diagnostic| diagnostic.span.start)
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            errors,
+            [
+                Location {
+                    line: 3,
+                    column: 31
+                },
+                Location {
+                    line: 3,
+                    column: 50
+                },
+                Location {
+                    line: 4,
+                    column: 32
+                },
+            ]
+        );
+
+        // Only the marker within the figure is drawn and the highlight ends with the figure.
+        let mut svg = Vec::new();
+        crate::Figure::from(wavejson).write_svg(&mut svg).unwrap();
+        let svg = String::from_utf8(svg).unwrap();
+        assert_eq!(svg.matches(r##"xlink:href="#vl" stroke-width"##).count(), 1);
+        assert!(svg.contains(r#"width="192" height="40" fill="#));
+    }
+}
@@ -3,17 +3,28 @@
 
 use serde::{Deserialize, Serialize};
 
+use self::assign::AssignJson;
+#[cfg(any(feature = "json5", feature = "serde_json"))]
+use self::diagnostics::{Diagnostic, Severity, StrictError};
+use self::register::RegisterJson;
 use self::signal::SignalJson;
 
 use crate::Figure;
 
+pub mod assign;
+pub mod diagnostics;
+pub mod register;
 pub mod signal;
+mod source;
+pub mod text;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[serde(untagged)]
 pub enum WaveJson {
     Signal(SignalJson),
+    Register(RegisterJson),
+    Assign(AssignJson),
 }
 
 impl Figure {
@@ -26,9 +37,159 @@ impl Figure {
     pub fn from_json5(s: &str) -> Result<Self, json5::Error> {
         WaveJson::from_json5(s).map(Into::into)
     }
+
+    /// Load a [`Figure`] from the WaveJson structure written in YAML.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wavedrom::Figure;
+    ///
+    /// let figure = Figure::from_yaml(r#"
+    /// signal:
+    ///   - { name: clk, wave: p..... }
+    ///   - name: data
+    ///     wave: "x.=.=x"
+    ///     data: [head, tail]
+    /// "#).unwrap();
+    /// ```
+    #[cfg(feature = "yaml")]
+    pub fn from_yaml(s: &str) -> Result<Self, serde_yaml::Error> {
+        WaveJson::from_yaml(s).map(Into::into)
+    }
+
+    /// Load a [`Figure`] from the WaveJson structure written in TOML.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wavedrom::Figure;
+    ///
+    /// let figure = Figure::from_toml(r#"
+    /// [[signal]]
+    /// name = "clk"
+    /// wave = "p....."
+    ///
+    /// [[signal]]
+    /// name = "data"
+    /// wave = "x.=.=x"
+    /// data = ["head", "tail"]
+    /// "#).unwrap();
+    /// ```
+    #[cfg(feature = "toml")]
+    pub fn from_toml(s: &str) -> Result<Self, toml::de::Error> {
+        WaveJson::from_toml(s).map(Into::into)
+    }
+
+    /// Load a [`Figure`] from JSON and report the problems found in the source.
+    ///
+    /// See [`WaveJson::diagnostics`] for the problems that are reported.
+    #[cfg(feature = "serde_json")]
+    pub fn from_json_with_diagnostics(
+        s: &str,
+    ) -> Result<(Self, Vec<Diagnostic>), serde_json::Error> {
+        let wavejson = WaveJson::from_json(s)?;
+        let diagnostics = wavejson.diagnostics(s);
+
+        Ok((wavejson.into(), diagnostics))
+    }
+
+    /// Load a [`Figure`] from JSON in strict mode.
+    ///
+    /// See [`Figure::from_json5_strict`].
+    #[cfg(feature = "serde_json")]
+    pub fn from_json_strict(
+        s: &str,
+    ) -> Result<(Self, Vec<Diagnostic>), StrictError<serde_json::Error>> {
+        let wavejson = WaveJson::from_json(s)?;
+        let diagnostics = wavejson.strict_diagnostics(s);
+
+        strict_result(wavejson, diagnostics)
+    }
+
+    /// Load a [`Figure`] from JSON5 and report the problems found in the source.
+    ///
+    /// The figure is loaded exactly as with [`Figure::from_json5`]. Problems such as unknown wave
+    /// characters, malformed edges, edges to undefined nodes and mismatched data fields are
+    /// returned as [`Diagnostic`]s with their location in the source.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wavedrom::Figure;
+    ///
+    /// let (_figure, diagnostics) = Figure::from_json5_with_diagnostics(
+    ///     r#"{ signal: [ { wave: "01?" } ] }"#,
+    /// ).unwrap();
+    ///
+    /// assert_eq!(diagnostics.len(), 1);
+    /// assert_eq!(diagnostics[0].span.start.column, 24);
+    /// ```
+    #[cfg(feature = "json5")]
+    pub fn from_json5_with_diagnostics(
+        s: &str,
+    ) -> Result<(Self, Vec<Diagnostic>), json5::Error> {
+        let wavejson = WaveJson::from_json5(s)?;
+        let diagnostics = wavejson.diagnostics(s);
+
+        Ok((wavejson.into(), diagnostics))
+    }
+
+    /// Load a [`Figure`] from JSON5 in strict mode.
+    ///
+    /// In strict mode, unknown object keys, unknown wave characters, invalid `period` and `phase`
+    /// values, malformed edges, invalid register configurations and gates without inputs are
+    /// errors instead of being silently accepted. When such an error is found, all the
+    /// [`Diagnostic`]s are returned in a [`StrictError::Invalid`]. Otherwise, the figure is
+    /// returned together with the remaining warnings.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wavedrom::Figure;
+    ///
+    /// let result = Figure::from_json5_strict(r#"{ signal: [ { nmae: "clk" } ] }"#);
+    /// let err = result.err().unwrap();
+    /// assert_eq!(err.diagnostics()[0].message, "unknown key 'nmae', expected one of: name, \
+    ///     wave, data, node, period, phase");
+    /// ```
+    #[cfg(feature = "json5")]
+    pub fn from_json5_strict(
+        s: &str,
+    ) -> Result<(Self, Vec<Diagnostic>), StrictError<json5::Error>> {
+        let wavejson = WaveJson::from_json5(s)?;
+        let diagnostics = wavejson.strict_diagnostics(s);
+
+        strict_result(wavejson, diagnostics)
+    }
+}
+
+#[cfg(any(feature = "json5", feature = "serde_json"))]
+fn strict_result<E>(
+    wavejson: WaveJson,
+    diagnostics: Vec<Diagnostic>,
+) -> Result<(Figure, Vec<Diagnostic>), StrictError<E>> {
+    if diagnostics
+        .iter()
+        .any(|diagnostic| diagnostic.severity == Severity::Error)
+    {
+        return Err(StrictError::Invalid(diagnostics));
+    }
+
+    Ok((wavejson.into(), diagnostics))
 }
 
 impl WaveJson {
+    #[cfg(feature = "yaml")]
+    pub fn from_yaml(s: &str) -> Result<Self, serde_yaml::Error> {
+        serde_yaml::from_str(s)
+    }
+
+    #[cfg(feature = "toml")]
+    pub fn from_toml(s: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(s)
+    }
+
     #[cfg(feature = "serde_json")]
     pub fn from_json(s: &str) -> Result<Self, serde_json::Error> {
         serde_json::from_str(s)
@@ -44,6 +205,8 @@ impl From<WaveJson> for Figure {
     fn from(value: WaveJson) -> Self {
         match value {
             WaveJson::Signal(signal_json) => Figure::Signal(signal_json.into()),
+            WaveJson::Register(register_json) => Figure::Register(register_json.into()),
+            WaveJson::Assign(assign_json) => Figure::Assign(assign_json.into()),
         }
     }
 }
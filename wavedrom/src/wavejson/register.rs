@@ -0,0 +1,124 @@
+use serde::{Deserialize, Serialize};
+
+use crate::register::{RegisterField, RegisterFigure};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct RegisterJson {
+    pub reg: Vec<RegisterFieldJson>,
+    pub config: Option<RegisterConfig>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct RegisterFieldJson {
+    pub bits: u32,
+    pub name: Option<RegisterText>,
+    pub attr: Option<RegisterAttributes>,
+    #[serde(rename = "type")]
+    pub field_type: Option<u8>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[serde(untagged)]
+pub enum RegisterText {
+    String(String),
+    Number(u64),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[serde(untagged)]
+pub enum RegisterAttributes {
+    One(RegisterText),
+    Multiple(Vec<RegisterText>),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct RegisterConfig {
+    pub bits: Option<u32>,
+    pub lanes: Option<u32>,
+    pub hspace: Option<u32>,
+    pub vspace: Option<u32>,
+    pub compact: Option<bool>,
+    pub offset: Option<u32>,
+}
+
+impl RegisterJson {
+    pub(crate) const KEYS: &'static [&'static str] = &["reg", "config"];
+}
+
+impl RegisterFieldJson {
+    pub(crate) const KEYS: &'static [&'static str] = &["bits", "name", "attr", "type"];
+}
+
+impl RegisterConfig {
+    pub(crate) const KEYS: &'static [&'static str] =
+        &["bits", "lanes", "hspace", "vspace", "compact", "offset"];
+}
+
+impl From<RegisterText> for String {
+    fn from(value: RegisterText) -> Self {
+        match value {
+            RegisterText::String(s) => s,
+            RegisterText::Number(n) => n.to_string(),
+        }
+    }
+}
+
+impl From<RegisterFieldJson> for RegisterField {
+    fn from(value: RegisterFieldJson) -> Self {
+        let mut field = RegisterField::new(value.bits);
+
+        match value.name {
+            Some(RegisterText::String(name)) => field = field.name(name),
+            Some(RegisterText::Number(number)) => field = field.value(number),
+            None => {}
+        }
+
+        match value.attr {
+            Some(RegisterAttributes::One(attribute)) => field = field.attribute(attribute),
+            Some(RegisterAttributes::Multiple(attributes)) => {
+                field = field.attributes(attributes.into_iter().map(String::from))
+            }
+            None => {}
+        }
+
+        if let Some(field_type) = value.field_type {
+            field = field.field_type(field_type);
+        }
+
+        field
+    }
+}
+
+impl From<RegisterJson> for RegisterFigure {
+    fn from(value: RegisterJson) -> Self {
+        let mut figure = RegisterFigure::new().add_fields(value.reg.into_iter().map(Into::into));
+
+        if let Some(config) = value.config {
+            if let Some(bits) = config.bits {
+                figure = figure.bits(bits);
+            }
+            if let Some(lanes) = config.lanes {
+                figure = figure.lanes(lanes);
+            }
+            if let Some(hspace) = config.hspace {
+                figure = figure.horizontal_space(hspace);
+            }
+            if let Some(vspace) = config.vspace {
+                figure = figure.vertical_space(vspace);
+            }
+            if let Some(compact) = config.compact {
+                figure = figure.compact(compact);
+            }
+            if let Some(offset) = config.offset {
+                figure = figure.offset(offset);
+            }
+        }
+
+        figure
+    }
+}
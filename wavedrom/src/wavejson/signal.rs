@@ -1,17 +1,31 @@
 use serde::{Deserialize, Serialize};
 
-use crate::signal::markers::CycleEnumerationMarker;
-use crate::signal::{CycleOffset, CycleState};
+use crate::signal::edges::{EdgeArrowHead, EdgeDefinition, EdgeStyle};
+use crate::signal::markers::{
+    CycleEnumerationMarker, HighlightRegion, LineDash, TimeRulerPosition, VerticalMarker,
+};
+use crate::signal::{CycleOffset, CycleState, Period};
 use crate::signal::{Signal, SignalFigure, SignalFigureSection, SignalFigureSectionGroup};
+use crate::text::RichText;
+
+use super::text::JsonMl;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct SignalJson {
     pub signal: Vec<SignalItem>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub head: Option<Head>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub foot: Option<Foot>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub config: Option<Config>,
-    pub edge: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub edge: Option<Vec<EdgeItem>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub marker: Option<Vec<Marker>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub highlight: Option<Vec<Highlight>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -33,11 +47,17 @@ pub enum SignalGroupItem {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct SignalObject {
-    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<JsonMl>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub wave: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub data: Option<SignalData>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub node: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub period: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub phase: Option<f32>,
 }
 
@@ -46,36 +66,142 @@ pub struct SignalObject {
 #[serde(untagged)]
 pub enum SignalData {
     One(String),
-    Multiple(Vec<String>),
+    Multiple(Vec<JsonMl>),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Head {
-    pub text: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<JsonMl>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub tick: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub every: Option<u32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Foot {
-    pub text: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<JsonMl>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub tock: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub every: Option<u32>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Config {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub hscale: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub skin: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timescale: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ruler: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[serde(untagged)]
+pub enum EdgeItem {
+    String(String),
+    Object(EdgeObject),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct EdgeObject {
+    pub def: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub width: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dash: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub arrow: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct Marker {
+    pub at: f32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dash: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct Highlight {
+    pub from: f32,
+    pub to: f32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lanes: Option<Vec<u32>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub opacity: Option<f32>,
+}
+
+// The keys that are known for each of the objects. These are used to report unknown keys in
+// strict mode and should be kept in sync with the fields above.
+
+impl SignalJson {
+    pub(crate) const KEYS: &'static [&'static str] = &[
+        "signal",
+        "head",
+        "foot",
+        "config",
+        "edge",
+        "marker",
+        "highlight",
+    ];
+}
+
+impl SignalObject {
+    pub(crate) const KEYS: &'static [&'static str] =
+        &["name", "wave", "data", "node", "period", "phase"];
+}
+
+impl Head {
+    pub(crate) const KEYS: &'static [&'static str] = &["text", "tick", "every"];
+}
+
+impl Foot {
+    pub(crate) const KEYS: &'static [&'static str] = &["text", "tock", "every"];
+}
+
+impl Config {
+    pub(crate) const KEYS: &'static [&'static str] = &["hscale", "skin", "timescale", "ruler"];
+}
+
+impl EdgeObject {
+    pub(crate) const KEYS: &'static [&'static str] = &["def", "color", "width", "dash", "arrow"];
+}
+
+impl Marker {
+    pub(crate) const KEYS: &'static [&'static str] = &["at", "label", "color", "dash"];
+}
+
+impl Highlight {
+    pub(crate) const KEYS: &'static [&'static str] =
+        &["from", "to", "lanes", "label", "color", "opacity"];
 }
 
 impl From<SignalJson> for SignalFigure {
     fn from(value: SignalJson) -> Self {
         let (title, top_cycle_marker) = if let Some(head) = value.head {
-            let title = head.text;
+            let title = head.text.map(Into::into);
             let top_cycle_marker = match (head.tick, head.every) {
                 (Some(start), Some(every)) => Some(CycleEnumerationMarker::new(start, every)),
                 (Some(start), None) => Some(CycleEnumerationMarker::new(start, 1)),
@@ -87,7 +213,7 @@ impl From<SignalJson> for SignalFigure {
             (None, None)
         };
         let (footer, bottom_cycle_marker) = if let Some(foot) = value.foot {
-            let footer = foot.text;
+            let footer = foot.text.map(Into::into);
             let bottom_cycle_marker = match (foot.tock, foot.every) {
                 (Some(start), Some(every)) => Some(CycleEnumerationMarker::new(start, every)),
                 (Some(start), None) => Some(CycleEnumerationMarker::new(start, 1)),
@@ -99,7 +225,16 @@ impl From<SignalJson> for SignalFigure {
             (None, None)
         };
 
-        let hscale = value.config.and_then(|config| config.hscale).unwrap_or(1);
+        let config = value.config.unwrap_or_default();
+        let hscale = config.hscale.unwrap_or(1);
+        let timescale = config
+            .timescale
+            .and_then(|timescale| timescale.parse().ok());
+        let time_ruler = config
+            .ruler
+            .as_deref()
+            .and_then(TimeRulerPosition::from_name)
+            .unwrap_or_default();
 
         let sections = value
             .signal
@@ -111,13 +246,13 @@ impl From<SignalJson> for SignalFigure {
 
         if let Some(edge) = value.edge {
             for e in edge {
-                if let Ok(def) = e.parse() {
+                if let Some(def) = e.to_definition() {
                     edges.push(def);
                 }
             }
         }
 
-        SignalFigure::with(
+        let mut figure = SignalFigure::with(
             title,
             footer,
             top_cycle_marker,
@@ -126,6 +261,104 @@ impl From<SignalJson> for SignalFigure {
             sections,
             edges,
         )
+        .time_ruler(time_ruler)
+        .add_markers(value.marker.into_iter().flatten().map(VerticalMarker::from))
+        .add_highlights(
+            value
+                .highlight
+                .into_iter()
+                .flatten()
+                .map(HighlightRegion::from),
+        );
+
+        if let Some(skin) = config.skin {
+            figure = figure.skin(skin);
+        }
+        if let Some(timescale) = timescale {
+            figure = figure.timescale(timescale);
+        }
+
+        figure
+    }
+}
+
+impl EdgeItem {
+    fn to_definition(&self) -> Option<EdgeDefinition> {
+        match self {
+            EdgeItem::String(def) => def.parse().ok(),
+            EdgeItem::Object(object) => {
+                let definition: EdgeDefinition = object.def.parse().ok()?;
+
+                let mut style = EdgeStyle::new();
+                if let Some(color) = object.color.as_deref().and_then(|c| c.parse().ok()) {
+                    style = style.color(color);
+                }
+                if let Some(width) = object.width.filter(|width| *width > 0) {
+                    style = style.width(width);
+                }
+                if let Some(dash) = object.dash.as_deref().and_then(LineDash::from_name) {
+                    style = style.dash(dash);
+                }
+                if let Some(arrow) = object.arrow.as_deref().and_then(EdgeArrowHead::from_name) {
+                    style = style.arrow_head(arrow);
+                }
+
+                Some(definition.with_style(style))
+            }
+        }
+    }
+}
+
+impl From<&EdgeDefinition> for EdgeItem {
+    fn from(definition: &EdgeDefinition) -> Self {
+        let style = definition.style();
+        if style.is_empty() {
+            return EdgeItem::String(definition.to_string());
+        }
+
+        EdgeItem::Object(EdgeObject {
+            def: definition.to_string(),
+            color: style.get_color().map(|color| color.to_string()),
+            width: style.get_width(),
+            dash: style.get_dash().map(|dash| dash.name().to_string()),
+            arrow: style.get_arrow_head().map(|arrow| arrow.name().to_string()),
+        })
+    }
+}
+
+impl From<Marker> for VerticalMarker {
+    fn from(marker: Marker) -> Self {
+        let color = marker.color.and_then(|color| color.parse().ok());
+        let dash = marker
+            .dash
+            .as_deref()
+            .and_then(LineDash::from_name)
+            .unwrap_or_default();
+
+        VerticalMarker::with(
+            CycleOffset::from(marker.at),
+            marker.label.unwrap_or_default(),
+            color,
+            dash,
+        )
+    }
+}
+
+impl From<Highlight> for HighlightRegion {
+    fn from(highlight: Highlight) -> Self {
+        let color = highlight.color.and_then(|color| color.parse().ok());
+        let opacity = highlight
+            .opacity
+            .filter(|opacity| (0.0..=1.0).contains(opacity));
+
+        HighlightRegion::with(
+            CycleOffset::from(highlight.from),
+            CycleOffset::from(highlight.to),
+            highlight.lanes.unwrap_or_default(),
+            highlight.label.unwrap_or_default(),
+            color,
+            opacity,
+        )
     }
 }
 
@@ -158,7 +391,7 @@ impl From<SignalItem> for SignalFigureSection {
 
 impl From<SignalObject> for Signal {
     fn from(item: SignalObject) -> Self {
-        let name = item.name.unwrap_or_default();
+        let name = item.name.map(Into::into).unwrap_or_default();
         let cycles = item
             .wave
             .unwrap_or_default()
@@ -171,16 +404,440 @@ impl From<SignalObject> for Signal {
                 SignalData::One(data) => data
                     .split(char::is_whitespace)
                     .filter(|s| !s.is_empty())
-                    .map(str::to_string)
+                    .map(Into::into)
                     .collect(),
-                SignalData::Multiple(data) => data,
+                SignalData::Multiple(data) => data.into_iter().map(Into::into).collect(),
             });
         let node = item.node.unwrap_or_default();
-        let period = item.period.map_or(0, |f| f.ceil() as u16);
-        let phase = item.phase.map_or_else(CycleOffset::default, |f| {
-            CycleOffset::try_from(f).unwrap_or_default()
-        });
+        let period = item
+            .period
+            .and_then(|f| Period::from_f64(f.into()))
+            .unwrap_or_default();
+        let phase = item.phase.map_or_else(CycleOffset::default, CycleOffset::from);
 
         Signal::with(name, cycles, data, node, period, phase)
     }
 }
+
+impl From<&SignalFigure> for SignalJson {
+    /// Convert a [`SignalFigure`] back to WaveJson.
+    ///
+    /// Loading the result with `SignalFigure::from` gives back the same figure. The only
+    /// exceptions are periods and phases that cannot be approximated by a fraction with a
+    /// denominator of at most 1000 and edge nodes or labels that start with whitespace.
+    fn from(figure: &SignalFigure) -> Self {
+        let head = head_or_foot(figure.get_header_text(), figure.get_top_cycle_marker());
+        let head = head.map(|(text, tick, every)| Head { text, tick, every });
+
+        let foot = head_or_foot(figure.get_footer_text(), figure.get_bottom_cycle_marker());
+        let foot = foot.map(|(text, tock, every)| Foot { text, tock, every });
+
+        let hscale = figure.get_horizontal_scale();
+        let skin = figure.get_skin();
+        let timescale = figure.get_timescale();
+        let time_ruler = figure.get_time_ruler();
+        let config = Config {
+            hscale: (hscale != 1).then_some(hscale),
+            skin: skin.map(str::to_string),
+            timescale: timescale.map(|timescale| timescale.to_string()),
+            ruler: (time_ruler != TimeRulerPosition::default())
+                .then(|| time_ruler.name().to_string()),
+        };
+        let config = (config.hscale.is_some()
+            || config.skin.is_some()
+            || config.timescale.is_some()
+            || config.ruler.is_some())
+        .then_some(config);
+
+        let edges = figure.get_edges();
+        let edge = (!edges.is_empty()).then(|| edges.iter().map(EdgeItem::from).collect());
+
+        let markers = figure.get_markers();
+        let marker = (!markers.is_empty()).then(|| markers.iter().map(Marker::from).collect());
+
+        let highlights = figure.get_highlights();
+        let highlight =
+            (!highlights.is_empty()).then(|| highlights.iter().map(Highlight::from).collect());
+
+        SignalJson {
+            signal: figure.get_sections().iter().map(SignalItem::from).collect(),
+            head,
+            foot,
+            config,
+            edge,
+            marker,
+            highlight,
+        }
+    }
+}
+
+impl From<&HighlightRegion> for Highlight {
+    fn from(region: &HighlightRegion) -> Self {
+        let lanes = region.lanes();
+        let label = region.label();
+
+        Highlight {
+            from: region.start().as_f64() as f32,
+            to: region.end().as_f64() as f32,
+            lanes: (!lanes.is_empty()).then(|| lanes.to_vec()),
+            label: (!label.is_empty()).then(|| label.to_string()),
+            color: region.color().map(|color| color.to_string()),
+            opacity: region.opacity(),
+        }
+    }
+}
+
+impl From<&VerticalMarker> for Marker {
+    fn from(marker: &VerticalMarker) -> Self {
+        let label = marker.label();
+        let dash = marker.dash();
+
+        Marker {
+            at: marker.at().as_f64() as f32,
+            label: (!label.is_empty()).then(|| label.to_string()),
+            color: marker.color().map(|color| color.to_string()),
+            dash: (dash != LineDash::default()).then(|| dash.name().to_string()),
+        }
+    }
+}
+
+fn head_or_foot(
+    text: Option<&RichText>,
+    marker: Option<CycleEnumerationMarker>,
+) -> Option<(Option<JsonMl>, Option<u32>, Option<u32>)> {
+    if text.is_none() && marker.is_none() {
+        return None;
+    }
+
+    let text = text.map(JsonMl::from);
+    let start = marker.map(CycleEnumerationMarker::start);
+    let every = marker
+        .map(CycleEnumerationMarker::every)
+        .filter(|every| *every != 1);
+
+    Some((text, start, every))
+}
+
+impl From<&SignalFigureSection> for SignalItem {
+    fn from(section: &SignalFigureSection) -> Self {
+        match section {
+            SignalFigureSection::Signal(signal) => SignalItem::Item(SignalObject::from(signal)),
+            SignalFigureSection::Group(group) => {
+                let label = group
+                    .label()
+                    .map(|label| SignalGroupItem::String(label.to_string()));
+                let items = group
+                    .items()
+                    .iter()
+                    .map(|item| SignalGroupItem::Item(SignalItem::from(item)));
+
+                SignalItem::Group(label.into_iter().chain(items).collect())
+            }
+        }
+    }
+}
+
+impl From<&Signal> for SignalObject {
+    fn from(signal: &Signal) -> Self {
+        let name = signal.get_name();
+        let name = (!name.spans().is_empty()).then(|| JsonMl::from(name));
+
+        let cycles = signal.cycles();
+        let wave = (!cycles.is_empty()).then(|| cycles.iter().map(|c| c.to_char()).collect());
+
+        let data = signal.get_data_fields();
+        let data = (!data.is_empty())
+            .then(|| SignalData::Multiple(data.iter().map(JsonMl::from).collect()));
+
+        let node = signal.get_nodes();
+        let node = (!node.is_empty()).then(|| node.to_string());
+
+        let period = signal.get_period();
+        let period = (period != Period::ONE).then(|| period.as_f64() as f32);
+
+        let phase = signal.get_phase();
+        let phase = (phase != CycleOffset::default()).then(|| phase.as_f64() as f32);
+
+        SignalObject {
+            name,
+            wave,
+            data,
+            node,
+            period,
+            phase,
+        }
+    }
+}
+
+impl SignalFigure {
+    /// Convert the [`SignalFigure`] to a WaveJson JSON string.
+    ///
+    /// See the conversion from a [`SignalFigure`] to a [`SignalJson`] for the details.
+    #[cfg(feature = "serde_json")]
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(&SignalJson::from(self))
+    }
+
+    /// Convert the [`SignalFigure`] to a WaveJson JSON5 string.
+    ///
+    /// See the conversion from a [`SignalFigure`] to a [`SignalJson`] for the details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wavedrom::signal::{Signal, SignalFigure};
+    ///
+    /// let figure = SignalFigure::new()
+    ///     .add_signal(Signal::with_cycle_str("p....").name("clk"));
+    ///
+    /// let json5 = figure.to_json5().unwrap();
+    /// assert_eq!(json5, r#"{"signal":[{"name":"clk","wave":"p...."}]}"#);
+    /// ```
+    #[cfg(feature = "json5")]
+    pub fn to_json5(&self) -> Result<String, json5::Error> {
+        json5::to_string(&SignalJson::from(self))
+    }
+}
+
+#[cfg(all(test, feature = "json5"))]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::signal::edges::{EdgeArrowType, EdgeVariant, SharpEdgeVariant, SplineEdgeVariant};
+    use crate::signal::{TimeUnit, Timescale};
+    use crate::text::{BaselineShift, TextSpan, TextStyle};
+    use crate::Color;
+
+    fn cycle_state() -> impl Strategy<Value = CycleState> {
+        "[10zxpPnN2-9.|=udhHlL]".prop_map(|s| CycleState::from(s.chars().next().unwrap()))
+    }
+
+    fn rich_text() -> impl Strategy<Value = RichText> {
+        let style = (
+            any::<[bool; 5]>(),
+            prop::option::of(prop_oneof![
+                Just(BaselineShift::Subscript),
+                Just(BaselineShift::Superscript)
+            ]),
+            prop::option::of(1u32..40),
+            prop::option::of(any::<[u8; 3]>()),
+        )
+            .prop_map(
+                |([bold, italic, overline, underline, line_through], baseline_shift, font_size, color)| {
+                    TextStyle {
+                        bold,
+                        italic,
+                        overline,
+                        underline,
+                        line_through,
+                        baseline_shift,
+                        font_size,
+                        color: color.map(|[red, green, blue]| Color { red, green, blue }),
+                    }
+                },
+            );
+
+        prop::collection::vec(("[a-zA-Z0-9 _]{0,6}", style), 0..4).prop_map(|spans| {
+            spans
+                .into_iter()
+                .map(|(text, style)| TextSpan::with_style(text, style))
+                .collect()
+        })
+    }
+
+    fn signal() -> impl Strategy<Value = Signal> {
+        (
+            rich_text(),
+            prop::collection::vec(cycle_state(), 0..12),
+            prop::collection::vec(rich_text(), 0..4),
+            "[a-e.]{0,8}",
+            (1u32..20, 1u32..20),
+            (0u64..40, 1u64..20),
+        )
+            .prop_map(|(name, cycles, data, node, period, phase)| {
+                Signal::with(
+                    name,
+                    cycles,
+                    data,
+                    node,
+                    Period::new(period.0, period.1).unwrap(),
+                    CycleOffset::new(phase.0, phase.1).unwrap(),
+                )
+            })
+    }
+
+    fn section() -> impl Strategy<Value = SignalFigureSection> {
+        signal()
+            .prop_map(SignalFigureSection::Signal)
+            .prop_recursive(3, 16, 4, |inner| {
+                (
+                    prop::option::of("[a-zA-Z ]{0,8}"),
+                    prop::collection::vec(inner, 0..4),
+                )
+                    .prop_map(|(label, items)| {
+                        SignalFigureSection::Group(SignalFigureSectionGroup::new(label, items))
+                    })
+            })
+    }
+
+    fn edge() -> impl Strategy<Value = EdgeDefinition> {
+        let arrow_type = prop_oneof![
+            Just(EdgeArrowType::None),
+            Just(EdgeArrowType::Start),
+            Just(EdgeArrowType::End),
+            Just(EdgeArrowType::Both),
+        ];
+        let variant = (0..8usize, arrow_type).prop_map(|(i, a)| match i {
+            0 => EdgeVariant::Spline(SplineEdgeVariant::BothHorizontal(a)),
+            1 => EdgeVariant::Spline(SplineEdgeVariant::StartHorizontal(a)),
+            2 => EdgeVariant::Spline(SplineEdgeVariant::EndHorizontal(a)),
+            3 => EdgeVariant::Sharp(SharpEdgeVariant::Straight(a)),
+            4 => EdgeVariant::Sharp(SharpEdgeVariant::BothHorizontal(a)),
+            5 => EdgeVariant::Sharp(SharpEdgeVariant::StartHorizontal(a)),
+            6 => EdgeVariant::Sharp(SharpEdgeVariant::EndHorizontal(a)),
+            _ => EdgeVariant::Sharp(SharpEdgeVariant::Cross),
+        });
+
+        (
+            variant,
+            "[a-e<>|~+-]|[a-z_]{2,6}",
+            "[a-e<>|~+-]|[a-z_]{2,6}",
+            prop::option::of("([a-zA-Z0-9][a-zA-Z0-9 ]{0,6})?"),
+            edge_style(),
+        )
+            .prop_map(|(variant, from, to, label, style)| {
+                EdgeDefinition::new(variant, from, to, label).with_style(style)
+            })
+    }
+
+    fn edge_style() -> impl Strategy<Value = EdgeStyle> {
+        (
+            prop::option::of(any::<[u8; 3]>()),
+            prop::option::of(1u32..4),
+            prop::option::of(0..LineDash::NAMES.len()),
+            prop::option::of(0..EdgeArrowHead::NAMES.len()),
+        )
+            .prop_map(|(color, width, dash, arrow_head)| {
+                let mut style = EdgeStyle::new();
+                if let Some([red, green, blue]) = color {
+                    style = style.color(Color { red, green, blue });
+                }
+                if let Some(width) = width {
+                    style = style.width(width);
+                }
+                if let Some(dash) = dash {
+                    style = style.dash(LineDash::from_name(LineDash::NAMES[dash]).unwrap());
+                }
+                if let Some(arrow_head) = arrow_head {
+                    style = style.arrow_head(
+                        EdgeArrowHead::from_name(EdgeArrowHead::NAMES[arrow_head]).unwrap(),
+                    );
+                }
+                style
+            })
+    }
+
+    fn marker() -> impl Strategy<Value = VerticalMarker> {
+        (
+            (0u64..40, 1u64..20),
+            "[a-zA-Z0-9 ]{0,6}",
+            prop::option::of(any::<[u8; 3]>()),
+            0..LineDash::NAMES.len(),
+        )
+            .prop_map(|(at, label, color, dash)| {
+                VerticalMarker::with(
+                    CycleOffset::new(at.0, at.1).unwrap(),
+                    label,
+                    color.map(|[red, green, blue]| Color { red, green, blue }),
+                    LineDash::from_name(LineDash::NAMES[dash]).unwrap(),
+                )
+            })
+    }
+
+    fn highlight() -> impl Strategy<Value = HighlightRegion> {
+        (
+            ((0u64..40, 1u64..20), (0u64..40, 1u64..20)),
+            prop::collection::vec(0u32..8, 0..3),
+            "[a-zA-Z0-9 ]{0,6}",
+            prop::option::of(any::<[u8; 3]>()),
+            prop::option::of(0u8..=100),
+        )
+            .prop_map(|((start, end), lanes, label, color, opacity)| {
+                HighlightRegion::with(
+                    CycleOffset::new(start.0, start.1).unwrap(),
+                    CycleOffset::new(end.0, end.1).unwrap(),
+                    lanes,
+                    label,
+                    color.map(|[red, green, blue]| Color { red, green, blue }),
+                    opacity.map(|opacity| f32::from(opacity) / 100.0),
+                )
+            })
+    }
+
+    fn figure() -> impl Strategy<Value = SignalFigure> {
+        (
+            prop::option::of(rich_text()),
+            prop::option::of(rich_text()),
+            prop::option::of((0u32..10, 1u32..4)),
+            prop::option::of((0u32..10, 1u32..4)),
+            1u16..4,
+            prop::option::of("[a-z]{1,8}"),
+            prop::option::of((1u32..1000, 0..TimeUnit::ALL.len(), 0..3usize)),
+            prop::collection::vec(section(), 0..5),
+            prop::collection::vec(edge(), 0..4),
+            (
+                prop::collection::vec(marker(), 0..3),
+                prop::collection::vec(highlight(), 0..3),
+            ),
+        )
+            .prop_map(
+                |(
+                    header,
+                    footer,
+                    top,
+                    bottom,
+                    hscale,
+                    skin,
+                    timescale,
+                    sections,
+                    edges,
+                    (markers, highlights),
+                )| {
+                    let mut figure = SignalFigure::with(
+                        header,
+                        footer,
+                        top.map(|(start, every)| CycleEnumerationMarker::new(start, every)),
+                        bottom.map(|(start, every)| CycleEnumerationMarker::new(start, every)),
+                        hscale,
+                        sections,
+                        edges,
+                    );
+
+                    if let Some(skin) = skin {
+                        figure = figure.skin(skin);
+                    }
+                    if let Some((value, unit, ruler)) = timescale {
+                        let femtoseconds =
+                            u128::from(value) * TimeUnit::ALL[unit].as_femtoseconds();
+                        let timescale = Timescale::from_femtoseconds(femtoseconds).unwrap();
+                        let ruler = TimeRulerPosition::from_name(TimeRulerPosition::NAMES[ruler]);
+                        figure = figure.timescale(timescale).time_ruler(ruler.unwrap());
+                    }
+
+                    figure.add_markers(markers).add_highlights(highlights)
+                },
+            )
+    }
+
+    proptest! {
+        #[test]
+        fn signal_figure_round_trip(figure in figure()) {
+            let json5 = figure.to_json5().unwrap();
+            let wavejson: SignalJson = json5::from_str(&json5).unwrap();
+            let round_trip = SignalFigure::from(wavejson);
+
+            prop_assert_eq!(format!("{figure:?}"), format!("{round_trip:?}"));
+        }
+    }
+}
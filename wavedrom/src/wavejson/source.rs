@@ -0,0 +1,460 @@
+//! A lightweight scanner that finds the locations of values in a JSON5 / JSON source.
+//!
+//! The [`json5`] and [`serde_json`] deserializers do not keep track of where values are located.
+//! This scanner recovers the structure of the source so that diagnostics can point to the
+//! offending location. It assumes that the source was already accepted by a deserializer and
+//! makes no attempt to validate it.
+
+use super::diagnostics::{Location, Span};
+
+/// A value in the source with its byte range.
+#[derive(Debug, Clone)]
+pub(crate) struct SourceNode {
+    pub start: usize,
+    pub end: usize,
+    pub kind: SourceKind,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum SourceKind {
+    Object(Vec<SourceMember>),
+    Array(Vec<SourceNode>),
+    String,
+    Other,
+}
+
+/// A key-value pair in an object.
+#[derive(Debug, Clone)]
+pub(crate) struct SourceMember {
+    pub key: String,
+    pub key_start: usize,
+    pub key_end: usize,
+    pub value: SourceNode,
+}
+
+/// The structure of a source together with the source itself.
+pub(crate) struct SourceMap<'a> {
+    source: &'a str,
+    root: Option<SourceNode>,
+}
+
+impl<'a> SourceMap<'a> {
+    pub fn new(source: &'a str) -> Self {
+        let mut scanner = Scanner { source, at: 0 };
+        let root = scanner.value();
+
+        Self { source, root }
+    }
+
+    #[inline]
+    pub fn root(&self) -> Option<&SourceNode> {
+        self.root.as_ref()
+    }
+
+    /// Get the [`Location`] of a byte offset.
+    pub fn location(&self, offset: usize) -> Location {
+        let offset = offset.min(self.source.len());
+        let before = &self.source[..offset];
+
+        let line = before.matches('\n').count() + 1;
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let column = before[line_start..].chars().count() + 1;
+
+        Location { line, column }
+    }
+
+    /// Get the [`Span`] of a byte range.
+    #[inline]
+    pub fn span(&self, start: usize, end: usize) -> Span {
+        Span {
+            start: self.location(start),
+            end: self.location(end),
+        }
+    }
+
+    /// Get the [`Span`] of a whole value.
+    #[inline]
+    pub fn node_span(&self, node: &SourceNode) -> Span {
+        self.span(node.start, node.end)
+    }
+
+    /// Get the [`Span`] of the `index`-th character in the decoded content of a string value.
+    ///
+    /// An `index` just past the end of the string refers to the closing quote. Falls back to the
+    /// span of the whole value if the value is not a string or the string is not long enough.
+    pub fn string_char_span(&self, node: &SourceNode, index: usize) -> Span {
+        let SourceKind::String = node.kind else {
+            return self.node_span(node);
+        };
+
+        // Skip the opening quote
+        let content = &self.source[node.start + 1..node.end.saturating_sub(1).max(node.start + 1)];
+        let mut chars = content.char_indices().peekable();
+        let mut decoded_index = 0;
+
+        while let Some((i, c)) = chars.next() {
+            let start = node.start + 1 + i;
+
+            let is_decoded_char = if c == '\\' {
+                match chars.next() {
+                    // A line continuation does not produce a character
+                    Some((_, '\n' | '\u{2028}' | '\u{2029}')) => false,
+                    Some((_, '\r')) => {
+                        chars.next_if(|(_, c)| *c == '\n');
+                        false
+                    }
+                    Some((_, 'u')) => {
+                        for _ in 0..4 {
+                            chars.next();
+                        }
+                        true
+                    }
+                    Some((_, 'x')) => {
+                        for _ in 0..2 {
+                            chars.next();
+                        }
+                        true
+                    }
+                    _ => true,
+                }
+            } else {
+                true
+            };
+
+            if !is_decoded_char {
+                continue;
+            }
+
+            if decoded_index == index {
+                let end = chars
+                    .peek()
+                    .map_or(node.end - 1, |(i, _)| node.start + 1 + i);
+                return self.span(start, end);
+            }
+
+            decoded_index += 1;
+        }
+
+        // Point to the closing quote when the index is just past the end of the string
+        if decoded_index == index && node.end > node.start + 1 {
+            return self.span(node.end - 1, node.end);
+        }
+
+        self.node_span(node)
+    }
+}
+
+impl SourceNode {
+    /// Get the member of an object with a specific `key`.
+    pub fn member(&self, key: &str) -> Option<&SourceMember> {
+        match &self.kind {
+            SourceKind::Object(members) => members.iter().find(|member| member.key == key),
+            _ => None,
+        }
+    }
+
+    /// Get the value of the member of an object with a specific `key`.
+    #[inline]
+    pub fn get(&self, key: &str) -> Option<&SourceNode> {
+        self.member(key).map(|member| &member.value)
+    }
+
+    /// Get the members of an object.
+    pub fn members(&self) -> &[SourceMember] {
+        match &self.kind {
+            SourceKind::Object(members) => members,
+            _ => &[],
+        }
+    }
+
+    /// Get the `index`-th item of an array.
+    pub fn index(&self, index: usize) -> Option<&SourceNode> {
+        match &self.kind {
+            SourceKind::Array(items) => items.get(index),
+            _ => None,
+        }
+    }
+}
+
+struct Scanner<'a> {
+    source: &'a str,
+    at: usize,
+}
+
+impl Scanner<'_> {
+    #[inline]
+    fn peek(&self) -> Option<char> {
+        self.source[self.at..].chars().next()
+    }
+
+    #[inline]
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.at += c.len_utf8();
+        Some(c)
+    }
+
+    fn skip_trivia(&mut self) {
+        loop {
+            let rest = &self.source[self.at..];
+
+            if rest.starts_with("//") {
+                self.at += rest.find('\n').unwrap_or(rest.len());
+            } else if let Some(comment) = rest.strip_prefix("/*") {
+                self.at += comment.find("*/").map_or(rest.len(), |i| i + 4);
+            } else if rest.starts_with(|c: char| c.is_whitespace() || c == '\u{FEFF}') {
+                self.bump();
+            } else {
+                return;
+            }
+        }
+    }
+
+    fn value(&mut self) -> Option<SourceNode> {
+        self.skip_trivia();
+
+        let start = self.at;
+        let kind = match self.peek()? {
+            '{' => {
+                self.bump();
+                SourceKind::Object(self.members())
+            }
+            '[' => {
+                self.bump();
+                SourceKind::Array(self.items())
+            }
+            '"' | '\'' => {
+                self.string();
+                SourceKind::String
+            }
+            _ => {
+                self.literal();
+                SourceKind::Other
+            }
+        };
+
+        Some(SourceNode {
+            start,
+            end: self.at,
+            kind,
+        })
+    }
+
+    fn members(&mut self) -> Vec<SourceMember> {
+        let mut members = Vec::new();
+
+        loop {
+            self.skip_trivia();
+
+            match self.peek() {
+                None => return members,
+                Some('}') => {
+                    self.bump();
+                    return members;
+                }
+                Some(',') => {
+                    self.bump();
+                    continue;
+                }
+                _ => {}
+            }
+
+            let key_start = self.at;
+            let key = match self.peek() {
+                Some('"' | '\'') => {
+                    self.string();
+                    decode_string(&self.source[key_start..self.at])
+                }
+                _ => {
+                    self.identifier();
+                    decode_identifier(&self.source[key_start..self.at])
+                }
+            };
+            let key_end = self.at;
+
+            self.skip_trivia();
+            if self.peek() == Some(':') {
+                self.bump();
+            }
+
+            let Some(value) = self.value() else {
+                return members;
+            };
+
+            members.push(SourceMember {
+                key,
+                key_start,
+                key_end,
+                value,
+            });
+        }
+    }
+
+    fn items(&mut self) -> Vec<SourceNode> {
+        let mut items = Vec::new();
+
+        loop {
+            self.skip_trivia();
+
+            match self.peek() {
+                None => return items,
+                Some(']') => {
+                    self.bump();
+                    return items;
+                }
+                Some(',') => {
+                    self.bump();
+                    continue;
+                }
+                _ => {}
+            }
+
+            let Some(item) = self.value() else {
+                return items;
+            };
+
+            items.push(item);
+        }
+    }
+
+    fn string(&mut self) {
+        let Some(quote) = self.bump() else {
+            return;
+        };
+
+        while let Some(c) = self.bump() {
+            if c == '\\' {
+                self.bump();
+            } else if c == quote {
+                return;
+            }
+        }
+    }
+
+    fn identifier(&mut self) {
+        while let Some(c) = self.peek() {
+            if c == '\\' {
+                self.bump();
+                self.bump();
+            } else if c.is_alphanumeric() || matches!(c, '_' | '$' | '\u{200C}' | '\u{200D}') {
+                self.bump();
+            } else {
+                return;
+            }
+        }
+    }
+
+    fn literal(&mut self) {
+        while let Some(c) = self.peek() {
+            if c.is_whitespace()
+                || matches!(c, ',' | ':' | ']' | '}')
+                || self.source[self.at..].starts_with("//")
+                || self.source[self.at..].starts_with("/*")
+            {
+                return;
+            }
+
+            self.bump();
+        }
+    }
+}
+
+/// Decode a quoted string including its quotes.
+fn decode_string(s: &str) -> String {
+    let content = s.get(1..s.len().saturating_sub(1)).unwrap_or_default();
+    decode_escapes(content)
+}
+
+#[inline]
+fn decode_identifier(s: &str) -> String {
+    decode_escapes(s)
+}
+
+fn decode_escapes(s: &str) -> String {
+    let mut decoded = String::with_capacity(s.len());
+    let mut chars = s.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            decoded.push(c);
+            continue;
+        }
+
+        let Some(escaped) = chars.next() else {
+            break;
+        };
+
+        match escaped {
+            'b' => decoded.push('\u{8}'),
+            'f' => decoded.push('\u{C}'),
+            'n' => decoded.push('\n'),
+            'r' => decoded.push('\r'),
+            't' => decoded.push('\t'),
+            'v' => decoded.push('\u{B}'),
+            '0' => decoded.push('\0'),
+            '\n' | '\r' | '\u{2028}' | '\u{2029}' => {}
+            'u' | 'x' => {
+                let num_digits = if escaped == 'u' { 4 } else { 2 };
+                let digits = chars.by_ref().take(num_digits).collect::<String>();
+
+                if let Some(c) = u32::from_str_radix(&digits, 16)
+                    .ok()
+                    .and_then(char::from_u32)
+                {
+                    decoded.push(c);
+                }
+            }
+            c => decoded.push(c),
+        }
+    }
+
+    decoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scan_locations() {
+        let source = r#"{
+    // A comment
+    signal: [
+        { name: 'clk', "wave": "p\x2EA." },
+    ],
+    /* Another comment */ edge: ["a~>b"],
+}"#;
+
+        let map = SourceMap::new(source);
+        let root = map.root().unwrap();
+
+        let signal = root.get("signal").unwrap().index(0).unwrap();
+        let wave = signal.get("wave").unwrap();
+        assert_eq!(
+            map.node_span(wave).start,
+            Location {
+                line: 4,
+                column: 32
+            }
+        );
+
+        // The escaped character counts as a single character
+        let span = map.string_char_span(wave, 3);
+        assert_eq!(
+            span.start,
+            Location {
+                line: 4,
+                column: 39
+            }
+        );
+
+        let edge = root.get("edge").unwrap().index(0).unwrap();
+        assert_eq!(
+            map.node_span(edge).start,
+            Location {
+                line: 6,
+                column: 34
+            }
+        );
+    }
+}
@@ -0,0 +1,311 @@
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::text::{BaselineShift, RichText, TextSpan, TextStyle};
+use crate::Color;
+
+/// A text in the [JsonML](http://www.jsonml.org/) format as used by WaveDrom-js.
+///
+/// This is either a plain string or an element of the form `[tag, attributes?, children...]`.
+/// For example, `["tspan", {"class": "info h3"}, "text"]`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[serde(untagged)]
+pub enum JsonMl {
+    Text(String),
+    Element(Vec<JsonMl>),
+    Attributes(BTreeMap<String, JsonMlValue>),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[serde(untagged)]
+pub enum JsonMlValue {
+    String(String),
+    Number(f64),
+    Other(IgnoredValue),
+}
+
+/// An attribute value of any other type (e.g. a boolean or `null`), which is ignored in the same
+/// way as unknown attributes.
+#[derive(Debug, Clone, Copy, Serialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct IgnoredValue;
+
+impl<'de> Deserialize<'de> for IgnoredValue {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        serde::de::IgnoredAny::deserialize(deserializer)?;
+        Ok(IgnoredValue)
+    }
+}
+
+impl From<JsonMl> for RichText {
+    fn from(value: JsonMl) -> Self {
+        let mut text = RichText::new();
+        push_jsonml(&mut text, value, TextStyle::default());
+        text
+    }
+}
+
+impl From<&RichText> for JsonMl {
+    /// Convert a [`RichText`] to [JsonML](http://www.jsonml.org/).
+    ///
+    /// A single unstyled span becomes a plain string. Otherwise, all spans are wrapped in a
+    /// `tspan` element and styled spans get their own `tspan` element with attributes.
+    fn from(text: &RichText) -> Self {
+        match text.spans() {
+            [span] if span.style() == &TextStyle::default() => {
+                JsonMl::Text(span.text().to_string())
+            }
+            spans => {
+                let mut items = vec![JsonMl::Text("tspan".to_string())];
+                items.extend(spans.iter().map(span_to_jsonml));
+                JsonMl::Element(items)
+            }
+        }
+    }
+}
+
+fn span_to_jsonml(span: &TextSpan) -> JsonMl {
+    let style = span.style();
+    let text = JsonMl::Text(span.text().to_string());
+
+    if style == &TextStyle::default() {
+        return text;
+    }
+
+    let mut attributes = BTreeMap::new();
+
+    if style.bold {
+        attributes.insert(
+            "font-weight".to_string(),
+            JsonMlValue::String("bold".into()),
+        );
+    }
+
+    if style.italic {
+        attributes.insert(
+            "font-style".to_string(),
+            JsonMlValue::String("italic".into()),
+        );
+    }
+
+    let decorations = [
+        (style.overline, "overline"),
+        (style.underline, "underline"),
+        (style.line_through, "line-through"),
+    ]
+    .into_iter()
+    .filter_map(|(is_set, decoration)| is_set.then_some(decoration))
+    .collect::<Vec<&str>>();
+
+    if !decorations.is_empty() {
+        attributes.insert(
+            "text-decoration".to_string(),
+            JsonMlValue::String(decorations.join(" ")),
+        );
+    }
+
+    if let Some(baseline_shift) = style.baseline_shift {
+        let value = match baseline_shift {
+            BaselineShift::Subscript => "sub",
+            BaselineShift::Superscript => "super",
+        };
+        attributes.insert(
+            "baseline-shift".to_string(),
+            JsonMlValue::String(value.into()),
+        );
+    }
+
+    if let Some(font_size) = style.font_size {
+        attributes.insert(
+            "font-size".to_string(),
+            JsonMlValue::Number(f64::from(font_size)),
+        );
+    }
+
+    if let Some(color) = style.color {
+        attributes.insert("fill".to_string(), JsonMlValue::String(color.to_string()));
+    }
+
+    JsonMl::Element(vec![
+        JsonMl::Text("tspan".to_string()),
+        JsonMl::Attributes(attributes),
+        text,
+    ])
+}
+
+fn push_jsonml(text: &mut RichText, node: JsonMl, mut style: TextStyle) {
+    match node {
+        JsonMl::Text(s) => text.push(TextSpan::with_style(s, style)),
+        JsonMl::Attributes(_) => {}
+        JsonMl::Element(items) => {
+            let mut items = items.into_iter().peekable();
+
+            if let Some(JsonMl::Text(tag)) = items.peek() {
+                apply_tag(&mut style, tag);
+                items.next();
+            }
+
+            if let Some(JsonMl::Attributes(attributes)) = items.peek() {
+                for (key, value) in attributes {
+                    apply_attribute(&mut style, key, value);
+                }
+                items.next();
+            }
+
+            for item in items {
+                push_jsonml(text, item, style);
+            }
+        }
+    }
+}
+
+fn apply_tag(style: &mut TextStyle, tag: &str) {
+    match tag {
+        "b" | "strong" => style.bold = true,
+        "i" | "em" => style.italic = true,
+        "o" => style.overline = true,
+        "u" => style.underline = true,
+        "s" => style.line_through = true,
+        "sub" => style.baseline_shift = Some(BaselineShift::Subscript),
+        "sup" => style.baseline_shift = Some(BaselineShift::Superscript),
+        _ => {}
+    }
+}
+
+fn apply_attribute(style: &mut TextStyle, key: &str, value: &JsonMlValue) {
+    let value = match value {
+        JsonMlValue::String(s) => &s[..],
+        JsonMlValue::Number(n) => {
+            if key == "font-size" && n.is_finite() && *n > 0.0 {
+                style.font_size = Some(n.round() as u32);
+            }
+
+            return;
+        }
+        JsonMlValue::Other(_) => return,
+    };
+
+    match key {
+        "class" => {
+            for class in value.split_whitespace() {
+                apply_class(style, class);
+            }
+        }
+        "fill" => {
+            if let Ok(color) = Color::from_str(value) {
+                style.color = Some(color);
+            }
+        }
+        "font-weight" => {
+            style.bold = match value {
+                "bold" | "bolder" => true,
+                "normal" | "lighter" => false,
+                _ => value
+                    .parse::<u32>()
+                    .map_or(style.bold, |weight| weight >= 600),
+            }
+        }
+        "font-style" => style.italic = matches!(value, "italic" | "oblique"),
+        "text-decoration" => {
+            for decoration in value.split_whitespace() {
+                match decoration {
+                    "overline" => style.overline = true,
+                    "underline" => style.underline = true,
+                    "line-through" => style.line_through = true,
+                    "none" => {
+                        style.overline = false;
+                        style.underline = false;
+                        style.line_through = false;
+                    }
+                    _ => {}
+                }
+            }
+        }
+        "baseline-shift" => {
+            style.baseline_shift = match value {
+                "sub" => Some(BaselineShift::Subscript),
+                "super" => Some(BaselineShift::Superscript),
+                _ => None,
+            }
+        }
+        "font-size" => {
+            let digits = value.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+            if let Ok(font_size) = digits.trim().parse::<u32>() {
+                style.font_size = Some(font_size);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Apply one of the WaveDrom-js text classes.
+fn apply_class(style: &mut TextStyle, class: &str) {
+    let font_size = match class {
+        "h1" => Some(33),
+        "h2" => Some(27),
+        "h3" => Some(20),
+        "h4" => Some(14),
+        "h5" => Some(11),
+        "h6" => Some(8),
+        _ => None,
+    };
+    let color = match class {
+        "muted" => Some("#AAA"),
+        "warning" => Some("#F6B900"),
+        "error" => Some("#F60000"),
+        "info" => Some("#0041C4"),
+        "success" => Some("#00AB00"),
+        _ => None,
+    };
+
+    if font_size.is_some() {
+        style.font_size = font_size;
+    }
+
+    if let Some(color) = color.and_then(|color| Color::from_str(color).ok()) {
+        style.color = Some(color);
+    }
+}
+
+#[cfg(all(test, feature = "json5"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn jsonml_to_rich_text() {
+        let jsonml: JsonMl = json5::from_str(
+            r##"["tspan", "E=mc", ["sup", "2"], ["tspan", {class: "info", "font-weight": "bold"}, ["o", "CS"]]]"##,
+        )
+        .unwrap();
+        let text = RichText::from(jsonml);
+
+        assert_eq!(text.to_plain_string(), "E=mc2CS");
+
+        let spans = text.spans();
+        assert_eq!(spans.len(), 3);
+        assert_eq!(spans[0].style(), &TextStyle::default());
+        assert_eq!(
+            spans[1].style().baseline_shift,
+            Some(BaselineShift::Superscript)
+        );
+
+        let style = spans[2].style();
+        assert!(style.bold && style.overline);
+        assert_eq!(style.color, Color::from_str("#0041C4").ok());
+    }
+
+    #[test]
+    fn other_attribute_values_are_ignored() {
+        let jsonml: JsonMl =
+            json5::from_str(r#"["tspan", {x: true, y: null, z: [1], "font-weight": "bold"}, "a"]"#)
+                .unwrap();
+        let text = RichText::from(jsonml);
+
+        assert_eq!(text.to_plain_string(), "a");
+        assert!(text.spans()[0].style().bold);
+    }
+}